@@ -4,15 +4,38 @@
 //! `ethos-cli search <query> -n <limit> --json` and parses the stdout as QMD-format JSON.
 //!
 //! # Subcommands
-//! - `search <query> [-n <limit>] [--json]` — semantic search
-//! - `query <query> [-n <limit>] [--json]`  — alias for search
+//! - `search <query> [-n <limit>] [--json | --format <text|json|jsonl>] [--verbose] [--after <when>] [--before <when>] [--agent <id>] [--output-file <path>] [--title-len <n>] [--snippet-len <n>]` — semantic search
+//! - `query <query> [-n <limit>] [--json | --format <text|json|jsonl>]`  — alias for search
+//! - `facts [--subject <s>] [--flagged] [-n <limit>] [--json]` — browse consolidated facts
 //! - `status`                                — show server health
+//!
+//! Search normally goes over HTTP; `--socket <path>` sends it to the server's
+//! Unix socket IPC interface instead, so the HTTP server can be disabled.
 
-use clap::{Parser, Subcommand};
+use chrono::{DateTime, Duration, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
+use ethos_core::ipc::{EthosRequest, EthosResponse};
 use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
 
 const DEFAULT_SERVER: &str = "http://127.0.0.1:8766";
 const DEFAULT_LIMIT: usize = 5;
+const DEFAULT_FACTS_LIMIT: usize = 50;
+
+/// QMD `title` / `snippet` lengths in characters unless `--title-len` /
+/// `--snippet-len` are given
+const DEFAULT_TITLE_LEN: usize = 60;
+const DEFAULT_SNIPPET_LEN: usize = 300;
+
+/// First retry delay; doubles on each further attempt
+const RETRY_BASE_DELAY_MS: u64 = 200;
+/// Upper bound on a single retry delay (before jitter)
+const RETRY_MAX_DELAY_MS: u64 = 5_000;
+
+/// Request timeout for searches unless `--timeout` is given
+const SEARCH_TIMEOUT_SECS: u64 = 30;
+/// Request timeout for `status` unless `--timeout` is given
+const STATUS_TIMEOUT_SECS: u64 = 10;
 
 // ============================================================================
 // CLI Definition
@@ -29,10 +52,34 @@ struct Cli {
     #[arg(long, env = "ETHOS_HTTP_URL", default_value = DEFAULT_SERVER)]
     server: String,
 
+    /// Retry connection failures and 5xx responses this many times with
+    /// exponential backoff (0 = fail immediately)
+    #[arg(long, global = true, default_value_t = 0)]
+    retries: u32,
+
+    /// Request timeout in seconds (default: 30 for searches, 10 for status)
+    #[arg(long, global = true, value_name = "SECS")]
+    timeout: Option<u64>,
+
+    /// Bearer token for protected endpoints such as `/facts` (`[http] api_token`)
+    #[arg(long, env = "ETHOS_API_TOKEN", global = true, hide_env_values = true)]
+    api_token: Option<String>,
+
+    /// Search over the Ethos IPC Unix socket (e.g. /tmp/ethos.sock) instead of HTTP
+    #[arg(long, env = "ETHOS_SOCKET", global = true)]
+    socket: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+impl Cli {
+    /// The `--timeout` override, or `default_secs` when unset.
+    fn timeout_or(&self, default_secs: u64) -> std::time::Duration {
+        std::time::Duration::from_secs(self.timeout.unwrap_or(default_secs))
+    }
+}
+
 #[derive(Debug, Subcommand)]
 enum Commands {
     /// Search memory semantically (QMD-compatible)
@@ -48,9 +95,43 @@ enum Commands {
         #[arg(long)]
         json: bool,
 
-        /// Enable spreading activation for associative retrieval
+        /// Output format; `jsonl` prints one compact QMD result per line
+        /// (`--json` is short for `--format json`)
+        #[arg(long, value_enum, conflicts_with = "json")]
+        format: Option<OutputFormat>,
+
+        /// Text output only: also print server timing and each hit's source and score breakdown
+        #[arg(short, long)]
+        verbose: bool,
+
+        /// Enable spreading activation for associative retrieval (without it,
+        /// the server's `default_use_spreading` applies)
         #[arg(long)]
         spreading: bool,
+
+        /// Only memories created after this time (RFC 3339, or relative like `7d`, `12h`)
+        #[arg(long, value_parser = parse_time_bound)]
+        after: Option<DateTime<Utc>>,
+
+        /// Only memories created before this time (RFC 3339, or relative like `7d`, `12h`)
+        #[arg(long, value_parser = parse_time_bound)]
+        before: Option<DateTime<Utc>>,
+
+        /// Only memories of this agent (metadata, episode or fact agent)
+        #[arg(long, value_name = "AGENT_ID")]
+        agent: Option<String>,
+
+        /// Write the output (JSON or text) to this file instead of stdout
+        #[arg(long, value_name = "PATH")]
+        output_file: Option<std::path::PathBuf>,
+
+        /// JSON output: characters of the first line kept as each result's title
+        #[arg(long, value_name = "CHARS", default_value_t = DEFAULT_TITLE_LEN)]
+        title_len: usize,
+
+        /// JSON output: characters of content kept in each result's snippet
+        #[arg(long, value_name = "CHARS", default_value_t = DEFAULT_SNIPPET_LEN)]
+        snippet_len: usize,
     },
 
     /// Query memory semantically (alias for search)
@@ -66,15 +147,128 @@ enum Commands {
         #[arg(long)]
         json: bool,
 
-        /// Enable spreading activation for associative retrieval
+        /// Output format; `jsonl` prints one compact QMD result per line
+        /// (`--json` is short for `--format json`)
+        #[arg(long, value_enum, conflicts_with = "json")]
+        format: Option<OutputFormat>,
+
+        /// Text output only: also print server timing and each hit's source and score breakdown
+        #[arg(short, long)]
+        verbose: bool,
+
+        /// Enable spreading activation for associative retrieval (without it,
+        /// the server's `default_use_spreading` applies)
         #[arg(long)]
         spreading: bool,
+
+        /// Only memories created after this time (RFC 3339, or relative like `7d`, `12h`)
+        #[arg(long, value_parser = parse_time_bound)]
+        after: Option<DateTime<Utc>>,
+
+        /// Only memories created before this time (RFC 3339, or relative like `7d`, `12h`)
+        #[arg(long, value_parser = parse_time_bound)]
+        before: Option<DateTime<Utc>>,
+
+        /// Only memories of this agent (metadata, episode or fact agent)
+        #[arg(long, value_name = "AGENT_ID")]
+        agent: Option<String>,
+
+        /// Write the output (JSON or text) to this file instead of stdout
+        #[arg(long, value_name = "PATH")]
+        output_file: Option<std::path::PathBuf>,
+
+        /// JSON output: characters of the first line kept as each result's title
+        #[arg(long, value_name = "CHARS", default_value_t = DEFAULT_TITLE_LEN)]
+        title_len: usize,
+
+        /// JSON output: characters of content kept in each result's snippet
+        #[arg(long, value_name = "CHARS", default_value_t = DEFAULT_SNIPPET_LEN)]
+        snippet_len: usize,
+    },
+
+    /// List consolidated facts (subject predicate object)
+    Facts {
+        /// Only facts about this subject (case-insensitive)
+        #[arg(long)]
+        subject: Option<String>,
+
+        /// Only conflicts flagged for review
+        #[arg(long)]
+        flagged: bool,
+
+        /// Maximum number of facts to return
+        #[arg(short = 'n', long, default_value_t = DEFAULT_FACTS_LIMIT)]
+        limit: usize,
+
+        /// Output the server's facts as a JSON array
+        #[arg(long)]
+        json: bool,
     },
 
     /// Show Ethos server status
     Status,
 }
 
+/// How `search` prints its results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text (mirrors QMD text output)
+    Text,
+    /// QMD-compatible pretty JSON array
+    Json,
+    /// One compact QMD-compatible JSON object per line
+    Jsonl,
+}
+
+impl OutputFormat {
+    /// `--format` if given, else `json` for `--json`, else `text`.
+    pub fn resolve(format: Option<OutputFormat>, json: bool) -> Self {
+        format.unwrap_or(if json {
+            OutputFormat::Json
+        } else {
+            OutputFormat::Text
+        })
+    }
+}
+
+/// Server-side filters of a search: `created_at` bounds (`--after` /
+/// `--before`) and the owning agent (`--agent`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchScope {
+    pub after: Option<DateTime<Utc>>,
+    pub before: Option<DateTime<Utc>>,
+    pub agent: Option<String>,
+}
+
+/// Parse `--after` / `--before`: an RFC 3339 timestamp, or a relative age
+/// (`30m`, `12h`, `7d`, `2w`) measured back from now.
+pub fn parse_time_bound(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&Utc));
+    }
+
+    let invalid = || {
+        format!(
+            "invalid time '{}': expected RFC 3339 (2026-01-31T09:00:00Z) or a relative age like 7d",
+            value
+        )
+    };
+    let (unit_at, _) = value.char_indices().next_back().ok_or_else(invalid)?;
+    let (amount, unit) = value.split_at(unit_at);
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let age = match unit {
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => None,
+    }
+    .filter(|_| amount >= 0)
+    .ok_or_else(invalid)?;
+
+    Ok(Utc::now() - age)
+}
+
 // ============================================================================
 // API Response Types
 // ============================================================================
@@ -88,6 +282,17 @@ pub struct EthosSearchResult {
     pub source: String,
     pub created_at: Option<String>,
     pub metadata: Option<serde_json::Value>,
+    /// Ranking breakdown (cosine / spreading / structural)
+    #[serde(default)]
+    pub metadata_scores: Option<RetrievalScores>,
+}
+
+/// Per-result score breakdown reported by the server
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct RetrievalScores {
+    pub cosine_score: f32,
+    pub spread_score: f32,
+    pub structural_score: f32,
 }
 
 /// The full search response from POST /search
@@ -97,6 +302,77 @@ pub struct EthosSearchResponse {
     pub query: String,
     pub count: usize,
     pub took_ms: Option<u64>,
+    /// Set when the server could not run a full vector search
+    #[serde(default)]
+    pub degraded: bool,
+    /// "vector" | "keyword" | "none"
+    #[serde(default)]
+    pub mode: Option<String>,
+}
+
+/// Stderr warning for a degraded search, e.g. keyword-only mode.
+pub fn degraded_notice(resp: &EthosSearchResponse) -> Option<String> {
+    resp.degraded.then(|| {
+        format!(
+            "ethos-cli: search degraded — running in {} mode",
+            resp.mode.as_deref().unwrap_or("unknown")
+        )
+    })
+}
+
+/// A consolidated fact from GET /facts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EthosFact {
+    pub id: String,
+    pub subject: String,
+    pub predicate: String,
+    pub object: String,
+    pub confidence: f64,
+    #[serde(default)]
+    pub flagged_for_review: bool,
+}
+
+/// The response from GET /facts
+#[derive(Debug, Deserialize)]
+pub struct EthosFactsResponse {
+    pub facts: Vec<EthosFact>,
+    pub count: usize,
+}
+
+/// One line per fact, columns padded to the widest subject and predicate:
+/// `subject  predicate  object (confidence)`; flagged facts end in `[flagged]`.
+pub fn format_facts_table(facts: &[EthosFact]) -> String {
+    use std::fmt::Write as _;
+
+    let subject_width = facts
+        .iter()
+        .map(|f| f.subject.chars().count())
+        .max()
+        .unwrap_or(0);
+    let predicate_width = facts
+        .iter()
+        .map(|f| f.predicate.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    for f in facts {
+        let _ = write!(
+            out,
+            "{:<sw$}  {:<pw$}  {} ({:.2})",
+            f.subject,
+            f.predicate,
+            f.object,
+            f.confidence,
+            sw = subject_width,
+            pw = predicate_width
+        );
+        if f.flagged_for_review {
+            out.push_str(" [flagged]");
+        }
+        out.push('\n');
+    }
+    out
 }
 
 // ============================================================================
@@ -115,33 +391,92 @@ pub struct QmdResult {
     pub score: f64,
     /// Source URI: "ethos://memory/{uuid}"
     pub file: String,
-    /// First line of content, truncated to 60 characters
+    /// First line of content, truncated to 60 characters (`--title-len`)
     pub title: String,
     /// Diff-header snippet: "@@ -1,4 @@\n\n{content truncated to 300 chars}"
+    /// (`--snippet-len`)
     pub snippet: String,
 }
 
-/// Convert an Ethos search result to QMD wire format.
+/// Character limits applied when converting results to QMD format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QmdOptions {
+    pub title_len: usize,
+    pub snippet_len: usize,
+}
+
+impl Default for QmdOptions {
+    fn default() -> Self {
+        Self {
+            title_len: DEFAULT_TITLE_LEN,
+            snippet_len: DEFAULT_SNIPPET_LEN,
+        }
+    }
+}
+
+/// Human-readable search output (mirrors QMD text output). `verbose` adds the
+/// server's `took_ms` and each hit's source and score breakdown.
+pub fn format_text_results(resp: &EthosSearchResponse, verbose: bool) -> String {
+    use std::fmt::Write as _;
+
+    let mut out = String::new();
+    if verbose {
+        if let Some(took_ms) = resp.took_ms {
+            let _ = writeln!(out, "Took:   {} ms\n", took_ms);
+        }
+    }
+    for r in &resp.results {
+        let _ = writeln!(out, "ethos://memory/{} #{}", r.id, short_id(&r.id));
+        if verbose {
+            let _ = writeln!(out, "Source: {}", r.source);
+            if let Some(scores) = r.metadata_scores {
+                let _ = writeln!(
+                    out,
+                    "Scores: cosine {:.3}  spread {:.3}  structural {:.3}",
+                    scores.cosine_score, scores.spread_score, scores.structural_score
+                );
+            }
+        }
+        let _ = writeln!(out, "Score:  {:.0}%\n", r.score * 100.0);
+        let preview: String = r.content.chars().take(200).collect();
+        let _ = writeln!(out, "{}\n", preview);
+    }
+    out
+}
+
+/// First 6 characters of `id` with dashes removed (the QMD docid body).
+/// Counts characters, not bytes, so a malformed id cannot split one.
+fn short_id(id: &str) -> String {
+    id.chars().filter(|&c| c != '-').take(6).collect()
+}
+
+/// Convert an Ethos search result to QMD wire format with the default
+/// title and snippet lengths.
 pub fn to_qmd_result(r: &EthosSearchResult) -> QmdResult {
+    to_qmd_result_with(r, QmdOptions::default())
+}
+
+/// Convert an Ethos search result to QMD wire format, truncating the title
+/// and snippet to `opts` (in characters, never splitting one).
+pub fn to_qmd_result_with(r: &EthosSearchResult, opts: QmdOptions) -> QmdResult {
     // docid: "#" + first 6 hex chars of UUID (dashes removed)
-    let uuid_hex = r.id.replace('-', "");
-    let docid = format!("#{}", &uuid_hex[..6.min(uuid_hex.len())]);
+    let docid = format!("#{}", short_id(&r.id));
 
     // file: ethos://memory/{uuid}
     let file = format!("ethos://memory/{}", r.id);
 
-    // title: first non-empty line of content, capped at 60 chars
+    // title: first non-empty line of content, capped at title_len chars
     let title: String = r
         .content
         .lines()
         .find(|l| !l.trim().is_empty())
         .unwrap_or("")
         .chars()
-        .take(60)
+        .take(opts.title_len)
         .collect();
 
     // snippet: QMD diff-header format + truncated content
-    let content_preview: String = r.content.chars().take(300).collect();
+    let content_preview: String = r.content.chars().take(opts.snippet_len).collect();
     let snippet = format!("@@ -1,4 @@\n\n{}", content_preview);
 
     QmdResult {
@@ -157,26 +492,184 @@ pub fn to_qmd_result(r: &EthosSearchResult) -> QmdResult {
 // HTTP Client Calls
 // ============================================================================
 
-/// Perform a semantic search against the Ethos HTTP API.
+/// Backoff before retry `attempt` (1-based): 200ms, 400ms, 800ms, … capped at 5s.
+pub fn backoff_delay(attempt: u32) -> std::time::Duration {
+    let exp = attempt.saturating_sub(1).min(16);
+    let ms = RETRY_BASE_DELAY_MS
+        .saturating_mul(1 << exp)
+        .min(RETRY_MAX_DELAY_MS);
+    std::time::Duration::from_millis(ms)
+}
+
+/// Add up to 25% random jitter so concurrent clients don't retry in lockstep.
+fn with_jitter(delay: std::time::Duration) -> std::time::Duration {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let fraction = (nanos % 1000) as f64 / 4000.0;
+    delay.mul_f64(1.0 + fraction)
+}
+
+/// Send a request, retrying transport errors and 5xx responses up to `retries`
+/// times. 4xx responses are returned immediately, as is a 504, which carries
+/// the partial page of a search that hit the server's deadline.
+fn send_with_retries(
+    retries: u32,
+    url: &str,
+    send: impl Fn() -> reqwest::Result<reqwest::blocking::Response>,
+) -> reqwest::Result<reqwest::blocking::Response> {
+    let mut attempt = 0;
+    loop {
+        let result = send();
+        let reason = match &result {
+            Ok(r) if r.status() == reqwest::StatusCode::GATEWAY_TIMEOUT => return result,
+            Ok(r) if r.status().is_server_error() => format!("server returned {}", r.status()),
+            Ok(_) => return result,
+            Err(e) => format!("connection failed: {}", e),
+        };
+
+        if attempt >= retries {
+            return result;
+        }
+        attempt += 1;
+        let delay = with_jitter(backoff_delay(attempt));
+        eprintln!(
+            "ethos-cli: {} ({}) — retry {}/{} in {}ms",
+            url,
+            reason,
+            attempt,
+            retries,
+            delay.as_millis()
+        );
+        std::thread::sleep(delay);
+    }
+}
+
+/// Perform a semantic search over HTTP, or over the IPC socket when `socket` is set.
+#[allow(clippy::too_many_arguments)]
 fn do_search(
     server: &str,
+    socket: Option<&str>,
     query: &str,
     limit: usize,
-    json_output: bool,
+    format: OutputFormat,
+    verbose: bool,
     use_spreading: bool,
+    scope: SearchScope,
+    output_file: Option<&std::path::Path>,
+    qmd: QmdOptions,
+    retries: u32,
+    timeout: std::time::Duration,
 ) -> anyhow::Result<()> {
+    let search_resp = match socket {
+        Some(path) => match search_via_socket(path, query, limit, use_spreading, scope, timeout) {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("ethos-cli: socket search via {} failed: {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        None => search_via_http(server, query, limit, use_spreading, scope, retries, timeout)?,
+    };
+
+    if let Some(notice) = degraded_notice(&search_resp) {
+        eprintln!("{}", notice);
+    }
+
+    if format == OutputFormat::Text && search_resp.results.is_empty() {
+        eprintln!("No results found for: {}", query);
+        if output_file.is_none() {
+            return Ok(());
+        }
+    }
+
+    let output = match render_search_output(&search_resp, format, verbose, qmd) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("ethos-cli: failed to serialize results: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match output_file {
+        Some(path) => {
+            if let Err(e) = write_output_file(path, &output) {
+                eprintln!("ethos-cli: failed to write {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+            eprintln!(
+                "ethos-cli: wrote {} results to {}",
+                search_resp.results.len(),
+                path.display()
+            );
+        }
+        None => print!("{}", output),
+    }
+
+    Ok(())
+}
+
+/// Exactly what `search` prints to stdout: the QMD-compatible JSON array,
+/// one QMD result per line, or the human-readable text.
+pub fn render_search_output(
+    resp: &EthosSearchResponse,
+    format: OutputFormat,
+    verbose: bool,
+    qmd: QmdOptions,
+) -> serde_json::Result<String> {
+    let qmd_results = || resp.results.iter().map(|r| to_qmd_result_with(r, qmd));
+    match format {
+        OutputFormat::Json => {
+            let qmd_results: Vec<QmdResult> = qmd_results().collect();
+            Ok(format!("{}\n", serde_json::to_string_pretty(&qmd_results)?))
+        }
+        OutputFormat::Jsonl => {
+            let mut out = String::new();
+            for result in qmd_results() {
+                out.push_str(&serde_json::to_string(&result)?);
+                out.push('\n');
+            }
+            Ok(out)
+        }
+        // Human-readable format (mirrors QMD text output)
+        OutputFormat::Text => Ok(format_text_results(resp, verbose)),
+    }
+}
+
+/// Write `contents` to `path`, creating parent directories as needed.
+pub fn write_output_file(path: &std::path::Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)
+}
+
+/// POST /search against the Ethos HTTP API.
+fn search_via_http(
+    server: &str,
+    query: &str,
+    limit: usize,
+    use_spreading: bool,
+    scope: SearchScope,
+    retries: u32,
+    timeout: std::time::Duration,
+) -> anyhow::Result<EthosSearchResponse> {
     let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
+        .timeout(timeout)
         .build()?;
 
     let url = format!("{}/search", server);
     let body = serde_json::json!({
         "query": query,
         "limit": limit,
-        "use_spreading": use_spreading,
+        "use_spreading": use_spreading.then_some(true),
+        "created_after": scope.after,
+        "created_before": scope.before,
+        "agent_id": scope.agent,
     });
 
-    let resp = client.post(&url).json(&body).send();
+    let resp = send_with_retries(retries, &url, || client.post(&url).json(&body).send());
 
     let resp = match resp {
         Ok(r) => r,
@@ -186,7 +679,9 @@ fn do_search(
         }
     };
 
-    if !resp.status().is_success() {
+    // 504 is a search cut short by the server's deadline; its body is the
+    // partial page, flagged degraded
+    if !resp.status().is_success() && resp.status() != reqwest::StatusCode::GATEWAY_TIMEOUT {
         let status = resp.status();
         let body = resp.text().unwrap_or_default();
         eprintln!("ethos-cli: server returned {}: {}", status, body);
@@ -201,46 +696,170 @@ fn do_search(
         }
     };
 
+    Ok(search_resp)
+}
+
+/// List consolidated facts via GET /facts.
+#[allow(clippy::too_many_arguments)]
+fn do_facts(
+    server: &str,
+    api_token: Option<&str>,
+    subject: Option<&str>,
+    flagged: bool,
+    limit: usize,
+    json_output: bool,
+    retries: u32,
+    timeout: std::time::Duration,
+) -> anyhow::Result<()> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .build()?;
+
+    let url = format!("{}/facts", server);
+    let mut params = vec![
+        ("limit", limit.to_string()),
+        ("flagged", flagged.to_string()),
+    ];
+    if let Some(subject) = subject {
+        params.push(("subject", subject.to_string()));
+    }
+
+    let resp = send_with_retries(retries, &url, || {
+        let request = client.get(&url).query(&params);
+        match api_token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+        .send()
+    });
+
+    let resp = match resp {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("ethos-cli: connection failed to {}: {}", url, e);
+            std::process::exit(1);
+        }
+    };
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().unwrap_or_default();
+        eprintln!("ethos-cli: server returned {}: {}", status, body);
+        std::process::exit(1);
+    }
+
+    let facts_resp: EthosFactsResponse = match resp.json() {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("ethos-cli: failed to parse facts response: {}", e);
+            std::process::exit(1);
+        }
+    };
+
     if json_output {
-        // QMD-compatible JSON array output
-        let qmd_results: Vec<QmdResult> = search_resp.results.iter().map(to_qmd_result).collect();
-        match serde_json::to_string_pretty(&qmd_results) {
+        match serde_json::to_string_pretty(&facts_resp.facts) {
             Ok(json) => println!("{}", json),
             Err(e) => {
-                eprintln!("ethos-cli: failed to serialize results: {}", e);
+                eprintln!("ethos-cli: failed to serialize facts: {}", e);
                 std::process::exit(1);
             }
         }
+    } else if facts_resp.count == 0 {
+        eprintln!("No facts found");
     } else {
-        // Human-readable format (mirrors QMD text output)
-        if search_resp.results.is_empty() {
-            eprintln!("No results found for: {}", query);
-            return Ok(());
-        }
-        for r in &search_resp.results {
-            let uuid_hex = r.id.replace('-', "");
-            println!(
-                "ethos://memory/{} #{}",
-                r.id,
-                &uuid_hex[..6.min(uuid_hex.len())]
-            );
-            println!("Score:  {:.0}%\n", r.score * 100.0);
-            let preview: String = r.content.chars().take(200).collect();
-            println!("{}\n", preview);
-        }
+        print!("{}", format_facts_table(&facts_resp.facts));
     }
 
     Ok(())
 }
 
+/// Send `EthosRequest::Search` over the IPC Unix socket.
+fn search_via_socket(
+    path: &str,
+    query: &str,
+    limit: usize,
+    use_spreading: bool,
+    scope: SearchScope,
+    timeout: std::time::Duration,
+) -> anyhow::Result<EthosSearchResponse> {
+    let mut stream = std::os::unix::net::UnixStream::connect(path)?;
+    stream.set_read_timeout(Some(timeout))?;
+
+    let request = EthosRequest::Search {
+        query: query.to_string(),
+        limit: Some(limit as u32),
+        offset: None,
+        use_spreading: use_spreading.then_some(true),
+        diversify: false,
+        group_by_document: false,
+        explain: false,
+        trace: false,
+        expand: false,
+        task_type: None,
+        search_dimensions: None,
+        include_related_facts: false,
+        resource_id: None,
+        thread_id: None,
+        agent_id: scope.agent,
+        min_score: None,
+        source_filter: None,
+        metadata_contains: None,
+        created_after: scope.after,
+        created_before: scope.before,
+        topics: None,
+        include_pruned: false,
+    };
+
+    let response = ipc_call(&mut stream, &request)?;
+    if response.status != "ok" {
+        anyhow::bail!(response
+            .error
+            .unwrap_or_else(|| "unknown error".to_string()));
+    }
+    let data = response.data.unwrap_or_default();
+    Ok(serde_json::from_value(data)?)
+}
+
+// ============================================================================
+// IPC Socket Framing
+// ============================================================================
+
+/// Write one IPC frame: 4-byte little-endian length prefix + payload.
+pub fn write_frame(writer: &mut impl Write, payload: &[u8]) -> std::io::Result<()> {
+    let len = u32::try_from(payload.len())
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "frame too large"))?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(payload)?;
+    writer.flush()
+}
+
+/// Read one IPC frame written by [`write_frame`].
+pub fn read_frame(reader: &mut impl Read) -> std::io::Result<Vec<u8>> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let mut payload = vec![0u8; u32::from_le_bytes(len) as usize];
+    reader.read_exact(&mut payload)?;
+    Ok(payload)
+}
+
+/// Send a MessagePack-encoded request and decode the response frame.
+pub fn ipc_call(
+    stream: &mut (impl Read + Write),
+    request: &EthosRequest,
+) -> anyhow::Result<EthosResponse> {
+    write_frame(stream, &rmp_serde::to_vec_named(request)?)?;
+    let frame = read_frame(stream)?;
+    Ok(rmp_serde::from_slice(&frame)?)
+}
+
 /// Show the server status by calling GET /health.
-fn do_status(server: &str) -> anyhow::Result<()> {
+fn do_status(server: &str, retries: u32, timeout: std::time::Duration) -> anyhow::Result<()> {
     let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
+        .timeout(timeout)
         .build()?;
 
     let url = format!("{}/health", server);
-    let resp = client.get(&url).send();
+    let resp = send_with_retries(retries, &url, || client.get(&url).send());
 
     match resp {
         Ok(r) if r.status().is_success() => {
@@ -279,20 +898,74 @@ fn main() {
     let cli = Cli::parse();
     let server = cli.server.trim_end_matches('/').to_string();
 
+    let search_timeout = cli.timeout_or(SEARCH_TIMEOUT_SECS);
+    let status_timeout = cli.timeout_or(STATUS_TIMEOUT_SECS);
+
     let result = match cli.command {
         Commands::Search {
             query,
             limit,
             json,
+            format,
+            verbose,
             spreading,
+            after,
+            before,
+            agent,
+            output_file,
+            title_len,
+            snippet_len,
         }
         | Commands::Query {
             query,
             limit,
             json,
+            format,
+            verbose,
             spreading,
-        } => do_search(&server, &query, limit, json, spreading),
-        Commands::Status => do_status(&server),
+            after,
+            before,
+            agent,
+            output_file,
+            title_len,
+            snippet_len,
+        } => do_search(
+            &server,
+            cli.socket.as_deref(),
+            &query,
+            limit,
+            OutputFormat::resolve(format, json),
+            verbose,
+            spreading,
+            SearchScope {
+                after,
+                before,
+                agent,
+            },
+            output_file.as_deref(),
+            QmdOptions {
+                title_len,
+                snippet_len,
+            },
+            cli.retries,
+            search_timeout,
+        ),
+        Commands::Facts {
+            subject,
+            flagged,
+            limit,
+            json,
+        } => do_facts(
+            &server,
+            cli.api_token.as_deref(),
+            subject.as_deref(),
+            flagged,
+            limit,
+            json,
+            cli.retries,
+            status_timeout,
+        ),
+        Commands::Status => do_status(&server, cli.retries, status_timeout),
     };
 
     if let Err(e) = result {
@@ -318,6 +991,7 @@ mod tests {
             source: "user".to_string(),
             created_at: Some("2026-02-23T10:00:00Z".to_string()),
             metadata: None,
+            metadata_scores: None,
         }
     }
 
@@ -338,7 +1012,7 @@ mod tests {
         assert_eq!(qmd.docid.len(), 7, "docid should be '#' + 6 hex chars");
         // The 6 chars after '#' come from UUID with dashes removed
         let uuid_hex = result.id.replace('-', "");
-        assert_eq!(&qmd.docid[1..], &uuid_hex[..6]);
+        assert!(uuid_hex.starts_with(qmd.docid.strip_prefix('#').unwrap()));
     }
 
     // ========================================================================
@@ -411,9 +1085,9 @@ mod tests {
         // snippet = "@@ -1,4 @@\n\n" + content[..300]
         let header = "@@ -1,4 @@\n\n";
         assert!(qmd.snippet.starts_with(header));
-        let content_part = &qmd.snippet[header.len()..];
+        let content_part = qmd.snippet.strip_prefix(header).unwrap();
         assert_eq!(
-            content_part.len(),
+            content_part.chars().count(),
             300,
             "Content part of snippet should be 300 chars"
         );
@@ -423,6 +1097,7 @@ mod tests {
     // TEST 6: QMD output serialises correctly as JSON array
     // ========================================================================
     #[test]
+    #[allow(clippy::useless_vec)]
     fn test_qmd_json_array_serialization() {
         let results = vec![
             mock_result("7b5c24ab-1234-5678-9abc-def012345678", "First memory", 0.9),
@@ -522,4 +1197,520 @@ mod tests {
         let qmd = to_qmd_result(&result);
         assert_eq!(qmd.title, "First real line");
     }
+
+    // ========================================================================
+    // TEST 11: backoff doubles per attempt and is capped
+    // ========================================================================
+    #[test]
+    fn test_backoff_delay_doubles_and_caps() {
+        let delays: Vec<u64> = (1..=8)
+            .map(|attempt| backoff_delay(attempt).as_millis() as u64)
+            .collect();
+        assert_eq!(delays, vec![200, 400, 800, 1600, 3200, 5000, 5000, 5000]);
+
+        // Very large attempt counts must not overflow
+        assert_eq!(backoff_delay(u32::MAX).as_millis(), 5000);
+
+        // Jitter only ever lengthens the delay, by at most 25%
+        for attempt in 1..=4 {
+            let base = backoff_delay(attempt);
+            let jittered = with_jitter(base);
+            assert!(jittered >= base && jittered <= base.mul_f64(1.25));
+        }
+    }
+
+    // ========================================================================
+    // TEST 12: IPC framing round-trips a request and response over a socket
+    // ========================================================================
+    #[test]
+    fn test_ipc_call_round_trips_through_socket_framing() {
+        let (mut client, mut server) = std::os::unix::net::UnixStream::pair().expect("socket pair");
+
+        let server_thread = std::thread::spawn(move || {
+            let frame = read_frame(&mut server).expect("read request frame");
+            let request: EthosRequest = rmp_serde::from_slice(&frame).expect("decode request");
+            let query = match request {
+                EthosRequest::Search { query, limit, .. } => {
+                    assert_eq!(limit, Some(3));
+                    query
+                }
+                other => panic!("expected search, got {:?}", other),
+            };
+            let response = EthosResponse::ok(serde_json::json!({
+                "results": [],
+                "query": query,
+                "count": 0,
+            }));
+            let bytes = rmp_serde::to_vec_named(&response).expect("encode response");
+            write_frame(&mut server, &bytes).expect("write response frame");
+        });
+
+        let request = EthosRequest::Search {
+            query: "socket test".to_string(),
+            limit: Some(3),
+            offset: None,
+            use_spreading: None,
+            diversify: false,
+            group_by_document: false,
+            explain: false,
+            trace: false,
+            expand: false,
+            task_type: None,
+            search_dimensions: None,
+            include_related_facts: false,
+            resource_id: None,
+            thread_id: None,
+            agent_id: None,
+            min_score: None,
+            source_filter: None,
+            metadata_contains: None,
+            created_after: None,
+            created_before: None,
+            topics: None,
+            include_pruned: false,
+        };
+        let response = ipc_call(&mut client, &request).expect("ipc call");
+        server_thread.join().unwrap();
+
+        assert_eq!(response.status, "ok");
+        let parsed: EthosSearchResponse =
+            serde_json::from_value(response.data.unwrap()).expect("search response");
+        assert_eq!(parsed.query, "socket test");
+        assert!(parsed.results.is_empty());
+    }
+
+    // ========================================================================
+    // TEST 13: --after / --before accept RFC 3339 and relative ages
+    // ========================================================================
+    #[test]
+    fn test_parse_time_bound_absolute_and_relative() {
+        let at = parse_time_bound("2026-01-31T09:00:00+01:00").expect("rfc3339");
+        assert_eq!(at.to_rfc3339(), "2026-01-31T08:00:00+00:00");
+
+        let before = Utc::now();
+        let week_ago = parse_time_bound("7d").expect("relative days");
+        let expected = before - Duration::days(7);
+        assert!((week_ago - expected).num_seconds().abs() <= 1);
+
+        let hours_ago = parse_time_bound("12h").expect("relative hours");
+        assert!(hours_ago > week_ago);
+
+        for bad in ["", "d", "7y", "-3d", "last week", "7日", "🎉"] {
+            assert!(
+                parse_time_bound(bad).is_err(),
+                "{:?} should be rejected",
+                bad
+            );
+        }
+
+        let cli = Cli::try_parse_from([
+            "ethos-cli",
+            "search",
+            "q",
+            "--after",
+            "2d",
+            "--before",
+            "1d",
+        ])
+        .expect("parse args");
+        match cli.command {
+            Commands::Search { after, before, .. } => assert!(after.unwrap() < before.unwrap()),
+            other => panic!("expected search, got {:?}", other),
+        }
+    }
+
+    // ========================================================================
+    // TEST 14: --timeout overrides both default request timeouts
+    // ========================================================================
+    #[test]
+    fn test_timeout_flag_overrides_defaults() {
+        let cli = Cli::try_parse_from(["ethos-cli", "status"]).expect("parse args");
+        assert_eq!(cli.timeout, None);
+        assert_eq!(
+            cli.timeout_or(SEARCH_TIMEOUT_SECS),
+            std::time::Duration::from_secs(30)
+        );
+        assert_eq!(
+            cli.timeout_or(STATUS_TIMEOUT_SECS),
+            std::time::Duration::from_secs(10)
+        );
+
+        // Global, so it is accepted after the subcommand too
+        let cli =
+            Cli::try_parse_from(["ethos-cli", "status", "--timeout", "2"]).expect("parse args");
+        assert_eq!(cli.timeout, Some(2));
+        assert_eq!(
+            cli.timeout_or(STATUS_TIMEOUT_SECS),
+            std::time::Duration::from_secs(2)
+        );
+        assert_eq!(
+            cli.timeout_or(SEARCH_TIMEOUT_SECS),
+            std::time::Duration::from_secs(2)
+        );
+
+        assert!(Cli::try_parse_from(["ethos-cli", "--timeout", "soon", "status"]).is_err());
+    }
+
+    // ========================================================================
+    // TEST 15: --verbose adds timing and score lines; default text unchanged
+    // ========================================================================
+    #[test]
+    fn test_verbose_text_output() {
+        let resp: EthosSearchResponse = serde_json::from_value(serde_json::json!({
+            "results": [{
+                "id": "a1b2c3d4-0000-0000-0000-000000000000",
+                "content": "Ethos uses spreading activation",
+                "score": 0.82,
+                "source": "assistant",
+                "created_at": null,
+                "metadata": {},
+                "metadata_scores": {
+                    "cosine_score": 0.8,
+                    "spread_score": 0.125,
+                    "structural_score": 0.05
+                }
+            }],
+            "query": "spreading",
+            "count": 1,
+            "took_ms": 42
+        }))
+        .expect("parse response");
+
+        let plain = format_text_results(&resp, false);
+        assert_eq!(
+            plain,
+            "ethos://memory/a1b2c3d4-0000-0000-0000-000000000000 #a1b2c3\n\
+             Score:  82%\n\n\
+             Ethos uses spreading activation\n\n"
+        );
+
+        let verbose = format_text_results(&resp, true);
+        assert!(verbose.starts_with("Took:   42 ms\n"), "{}", verbose);
+        assert!(verbose.contains("Source: assistant\n"), "{}", verbose);
+        assert!(
+            verbose.contains("Scores: cosine 0.800  spread 0.125  structural 0.050\n"),
+            "{}",
+            verbose
+        );
+
+        let untimed = EthosSearchResponse {
+            took_ms: None,
+            ..resp
+        };
+        let verbose = format_text_results(&untimed, true);
+        assert!(!verbose.contains("Took:"), "{}", verbose);
+        assert!(verbose.starts_with("ethos://memory/"), "{}", verbose);
+    }
+
+    // ========================================================================
+    // TEST 16: facts table aligns subject/predicate columns and marks conflicts
+    // ========================================================================
+    #[test]
+    fn test_format_facts_table() {
+        let resp: EthosFactsResponse = serde_json::from_value(serde_json::json!({
+            "count": 2,
+            "facts": [
+                {
+                    "id": "a1b2c3d4-0000-0000-0000-000000000001",
+                    "subject": "Michael",
+                    "predicate": "prefers",
+                    "object": "dark mode",
+                    "confidence": 0.9,
+                    "flagged_for_review": false,
+                    "updated_at": "2026-02-23T10:00:00Z"
+                },
+                {
+                    "id": "a1b2c3d4-0000-0000-0000-000000000002",
+                    "subject": "Ethos",
+                    "predicate": "is written in",
+                    "object": "Rust",
+                    "confidence": 0.756,
+                    "flagged_for_review": true
+                }
+            ]
+        }))
+        .expect("parse facts response");
+
+        assert_eq!(
+            format_facts_table(&resp.facts),
+            "Michael  prefers        dark mode (0.90)\n\
+             Ethos    is written in  Rust (0.76) [flagged]\n"
+        );
+        assert_eq!(format_facts_table(&[]), "");
+
+        let cli = Cli::try_parse_from([
+            "ethos-cli",
+            "facts",
+            "--subject",
+            "Ethos",
+            "--flagged",
+            "-n",
+            "10",
+        ])
+        .expect("parse facts");
+        match cli.command {
+            Commands::Facts {
+                subject,
+                flagged,
+                limit,
+                json,
+            } => {
+                assert_eq!(subject.as_deref(), Some("Ethos"));
+                assert!(flagged);
+                assert_eq!(limit, 10);
+                assert!(!json);
+            }
+            other => panic!("expected facts, got {:?}", other),
+        }
+    }
+
+    // ========================================================================
+    // TEST 17: --output-file writes exactly what stdout would have shown
+    // ========================================================================
+    #[test]
+    fn test_output_file_matches_stdout_rendering() {
+        let resp = EthosSearchResponse {
+            results: vec![
+                mock_result("a1b2c3d4-0000-0000-0000-000000000001", "First memory", 0.9),
+                mock_result("a1b2c3d4-0000-0000-0000-000000000002", "Second memory", 0.4),
+            ],
+            query: "memory".to_string(),
+            count: 2,
+            took_ms: Some(7),
+            degraded: false,
+            mode: None,
+        };
+        let dir = std::env::temp_dir().join(format!("ethos-cli-output-{}", std::process::id()));
+        let path = dir.join("nested").join("results.json");
+
+        for format in [OutputFormat::Json, OutputFormat::Text] {
+            let rendered =
+                render_search_output(&resp, format, false, QmdOptions::default()).unwrap();
+            write_output_file(&path, &rendered).expect("write output file");
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), rendered);
+        }
+
+        let json =
+            render_search_output(&resp, OutputFormat::Json, false, QmdOptions::default()).unwrap();
+        assert!(json.ends_with("]\n"), "{}", json);
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(
+            render_search_output(&resp, OutputFormat::Text, true, QmdOptions::default()).unwrap(),
+            format_text_results(&resp, true)
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+
+        let cli = Cli::try_parse_from([
+            "ethos-cli",
+            "query",
+            "memory",
+            "--json",
+            "--output-file",
+            "out/results.json",
+        ])
+        .expect("parse query");
+        match cli.command {
+            Commands::Query { output_file, .. } => {
+                assert_eq!(
+                    output_file.as_deref(),
+                    Some(std::path::Path::new("out/results.json"))
+                )
+            }
+            other => panic!("expected query, got {:?}", other),
+        }
+    }
+
+    // ========================================================================
+    // TEST 18: degraded responses produce a stderr notice naming the mode
+    // ========================================================================
+    #[test]
+    fn test_degraded_notice() {
+        let parse = |value: serde_json::Value| -> EthosSearchResponse {
+            serde_json::from_value(value).expect("parse response")
+        };
+
+        let full = parse(serde_json::json!({
+            "results": [], "query": "q", "count": 0,
+            "degraded": false, "mode": "vector"
+        }));
+        assert_eq!(degraded_notice(&full), None);
+
+        let legacy = parse(serde_json::json!({ "results": [], "query": "q", "count": 0 }));
+        assert_eq!(
+            degraded_notice(&legacy),
+            None,
+            "older servers omit the fields"
+        );
+
+        let keyword = parse(serde_json::json!({
+            "results": [], "query": "q", "count": 0,
+            "degraded": true, "mode": "keyword"
+        }));
+        assert_eq!(
+            degraded_notice(&keyword).as_deref(),
+            Some("ethos-cli: search degraded — running in keyword mode")
+        );
+    }
+
+    // ========================================================================
+    // TEST 19: --snippet-len / --title-len widen the QMD preview
+    // ========================================================================
+    #[test]
+    fn test_qmd_custom_snippet_len() {
+        let content = "x".repeat(800);
+        let result = mock_result("aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee", &content, 0.5);
+        let header = "@@ -1,4 @@\n\n";
+
+        let qmd = to_qmd_result_with(
+            &result,
+            QmdOptions {
+                title_len: 100,
+                snippet_len: 500,
+            },
+        );
+        assert_eq!(
+            qmd.snippet.strip_prefix(header).unwrap().chars().count(),
+            500
+        );
+        assert_eq!(qmd.title.chars().count(), 100);
+
+        let default = to_qmd_result(&result);
+        assert_eq!(
+            default
+                .snippet
+                .strip_prefix(header)
+                .unwrap()
+                .chars()
+                .count(),
+            300
+        );
+        assert_eq!(default.title.chars().count(), 60);
+
+        let cli =
+            Cli::try_parse_from(["ethos-cli", "search", "q", "--snippet-len", "500"]).unwrap();
+        match cli.command {
+            Commands::Search {
+                title_len,
+                snippet_len,
+                ..
+            } => assert_eq!((title_len, snippet_len), (DEFAULT_TITLE_LEN, 500)),
+            other => panic!("expected search, got {:?}", other),
+        }
+    }
+
+    // ========================================================================
+    // TEST 20: custom lengths truncate multibyte content on char boundaries
+    // ========================================================================
+    #[test]
+    fn test_qmd_custom_len_multibyte_safe() {
+        let content = "日本語のメモ🎉".repeat(100);
+        let result = mock_result("aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee", &content, 0.5);
+
+        for len in [1, 7, 499, 500] {
+            let qmd = to_qmd_result_with(
+                &result,
+                QmdOptions {
+                    title_len: len,
+                    snippet_len: len,
+                },
+            );
+            let preview = qmd.snippet.strip_prefix("@@ -1,4 @@\n\n").unwrap();
+            assert_eq!(preview.chars().count(), len);
+            assert_eq!(qmd.title.chars().count(), len);
+            assert!(content.starts_with(preview));
+        }
+    }
+
+    // ========================================================================
+    // TEST 21: emoji content never panics and is counted in characters
+    // ========================================================================
+    #[test]
+    fn test_qmd_emoji_content_char_counts() {
+        let content = format!("🎉 decided to use Rust 🚀 {}", "🚀".repeat(400));
+        let result = mock_result("🎉🎉🎉-aaaa-bbbb", &content, 0.5);
+
+        let qmd = to_qmd_result(&result);
+        assert_eq!(qmd.docid, "#🎉🎉🎉aaa");
+        assert_eq!(qmd.title.chars().count(), DEFAULT_TITLE_LEN);
+        assert!(qmd.title.starts_with("🎉 decided to use Rust 🚀"));
+        let preview = qmd.snippet.strip_prefix("@@ -1,4 @@\n\n").unwrap();
+        assert_eq!(preview.chars().count(), DEFAULT_SNIPPET_LEN);
+
+        let short = to_qmd_result(&mock_result("🎉", "🎉 decided to use Rust 🚀", 0.5));
+        assert_eq!(short.docid, "#🎉");
+        assert_eq!(short.title, "🎉 decided to use Rust 🚀");
+
+        let resp = EthosSearchResponse {
+            results: vec![result],
+            query: "rust".to_string(),
+            count: 1,
+            took_ms: None,
+            degraded: false,
+            mode: None,
+        };
+        let text = format_text_results(&resp, false);
+        assert!(text.contains("#🎉🎉🎉aaa"), "{}", text);
+    }
+
+    // ========================================================================
+    // TEST 22: --format jsonl prints one parseable QMD object per line
+    // ========================================================================
+    #[test]
+    fn test_jsonl_output_one_object_per_line() {
+        let results: Vec<EthosSearchResult> = (0..3)
+            .map(|i| {
+                mock_result(
+                    &format!("a1b2c3d4-0000-0000-0000-00000000000{}", i),
+                    &format!("Memory {}\nwith a second line", i),
+                    0.9 - i as f64 * 0.1,
+                )
+            })
+            .collect();
+        let resp = EthosSearchResponse {
+            count: results.len(),
+            results,
+            query: "memory".to_string(),
+            took_ms: None,
+            degraded: false,
+            mode: None,
+        };
+
+        let jsonl =
+            render_search_output(&resp, OutputFormat::Jsonl, false, QmdOptions::default()).unwrap();
+        assert!(jsonl.ends_with('\n'));
+        let lines: Vec<&str> = jsonl.lines().collect();
+        assert_eq!(lines.len(), 3, "{}", jsonl);
+        for (line, result) in lines.iter().zip(&resp.results) {
+            let parsed: serde_json::Value = serde_json::from_str(line).expect("line is JSON");
+            assert_eq!(parsed, serde_json::to_value(to_qmd_result(result)).unwrap());
+        }
+
+        let empty = EthosSearchResponse {
+            results: vec![],
+            count: 0,
+            ..resp
+        };
+        assert_eq!(
+            render_search_output(&empty, OutputFormat::Jsonl, false, QmdOptions::default())
+                .unwrap(),
+            ""
+        );
+
+        let cli = Cli::try_parse_from(["ethos-cli", "search", "memory", "--format", "jsonl"])
+            .expect("parse --format");
+        match cli.command {
+            Commands::Search { format, json, .. } => {
+                assert_eq!(OutputFormat::resolve(format, json), OutputFormat::Jsonl)
+            }
+            other => panic!("expected search, got {:?}", other),
+        }
+        assert!(
+            Cli::try_parse_from(["ethos-cli", "search", "q", "--json", "--format", "jsonl"])
+                .is_err()
+        );
+        assert_eq!(OutputFormat::resolve(None, true), OutputFormat::Json);
+        assert_eq!(OutputFormat::resolve(None, false), OutputFormat::Text);
+    }
 }
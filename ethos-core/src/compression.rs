@@ -0,0 +1,98 @@
+//! Transparent compression of stored memory content (`[storage] compress_content`)
+//!
+//! Compressed content is gzip, base64-encoded so it still fits the TEXT
+//! `memory_vectors.content` column. The `content_compressed` flag column
+//! records which form a row holds; readers pass both to [`decode_content`].
+
+use crate::config::StorageConfig;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{self, Read, Write};
+
+/// Prepare content for storage, returning `(stored_content, compressed)`.
+///
+/// Content at or below the threshold, or when compression is disabled, is
+/// stored verbatim.
+pub fn encode_content(content: &str, config: &StorageConfig) -> io::Result<(String, bool)> {
+    if !config.compress_content || content.len() <= config.compress_threshold_bytes {
+        return Ok((content.to_string(), false));
+    }
+    Ok((compress(content)?, true))
+}
+
+/// Recover the original content from a stored row.
+pub fn decode_content(stored: String, compressed: bool) -> io::Result<String> {
+    if !compressed {
+        return Ok(stored);
+    }
+    decompress(&stored)
+}
+
+fn compress(content: &str) -> io::Result<String> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(content.as_bytes())?;
+    Ok(STANDARD.encode(encoder.finish()?))
+}
+
+fn decompress(stored: &str) -> io::Result<String> {
+    let bytes = STANDARD
+        .decode(stored)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let mut content = String::new();
+    GzDecoder::new(bytes.as_slice()).read_to_string(&mut content)?;
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage(compress_content: bool, compress_threshold_bytes: usize) -> StorageConfig {
+        StorageConfig {
+            compress_content,
+            compress_threshold_bytes,
+        }
+    }
+
+    // ========================================================================
+    // TEST 1: Large content compresses and round-trips
+    // ========================================================================
+    #[test]
+    fn test_large_content_round_trips() {
+        let content = "The quick brown fox — jumps over the lazy dog. ".repeat(200);
+        let (stored, compressed) = encode_content(&content, &storage(true, 1024)).unwrap();
+
+        assert!(compressed);
+        assert!(stored.len() < content.len());
+        assert_eq!(decode_content(stored, compressed).unwrap(), content);
+    }
+
+    // ========================================================================
+    // TEST 2: Small content and disabled compression are stored verbatim
+    // ========================================================================
+    #[test]
+    fn test_small_or_disabled_is_verbatim() {
+        let small = "short memory";
+        assert_eq!(
+            encode_content(small, &storage(true, 1024)).unwrap(),
+            (small.to_string(), false)
+        );
+
+        let large = "x".repeat(4096);
+        assert_eq!(
+            encode_content(&large, &storage(false, 1024)).unwrap(),
+            (large.clone(), false)
+        );
+    }
+
+    // ========================================================================
+    // TEST 3: Corrupt compressed content is an error, not garbage
+    // ========================================================================
+    #[test]
+    fn test_corrupt_content_errors() {
+        assert!(decode_content("not base64 gzip!".to_string(), true).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use config::{Config, ConfigError, File};
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct EthosConfig {
@@ -12,18 +13,74 @@ pub struct EthosConfig {
     pub conflict_resolution: ConflictResolutionConfig,
     #[serde(default)]
     pub http: HttpConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    #[serde(default)]
+    pub linker: LinkerConfig,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ServiceConfig {
     pub socket_path: String,
     pub log_level: String,
+    /// Optional TCP address (e.g. `127.0.0.1:8767`) serving the IPC protocol
+    /// with length-prefixed JSON frames. Unset disables the TCP listener.
+    /// Without `ipc_tcp_token`, non-loopback addresses are refused.
+    #[serde(default)]
+    pub ipc_tcp_addr: Option<String>,
+    /// Token every TCP connection must present in a first
+    /// `{"token": "..."}` frame before any request is served
+    #[serde(default)]
+    pub ipc_tcp_token: Option<String>,
+    /// Ingest rejects `content` longer than this many bytes before any DB
+    /// write. `0` removes the limit.
+    #[serde(default = "default_max_content_bytes")]
+    pub max_content_bytes: usize,
+    /// Issue one throwaway query embedding and vector search on boot, so the
+    /// first real search doesn't pay the backend's and pgvector's cold start
+    #[serde(default)]
+    pub warm_up: bool,
+    /// Characters per memory when a payload is ingested with `"chunk": true`
+    #[serde(default = "default_chunk_size")]
+    pub chunk_size: usize,
+    /// Characters each chunk repeats from the end of the previous one
+    #[serde(default = "default_chunk_overlap")]
+    pub chunk_overlap: usize,
+}
+
+fn default_max_content_bytes() -> usize {
+    1024 * 1024
+}
+fn default_chunk_size() -> usize {
+    2000
+}
+fn default_chunk_overlap() -> usize {
+    200
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct DatabaseConfig {
     pub url: String,
     pub max_connections: u32,
+    /// Connections the pool keeps open even when idle
+    #[serde(default)]
+    pub min_connections: u32,
+    /// How long a query waits for a free connection before failing
+    #[serde(default = "default_acquire_timeout_seconds")]
+    pub acquire_timeout_seconds: u64,
+    /// Close connections idle for longer than this. `0` keeps them open.
+    #[serde(default = "default_idle_timeout_seconds")]
+    pub idle_timeout_seconds: u64,
+}
+
+fn default_acquire_timeout_seconds() -> u64 {
+    30
+}
+
+fn default_idle_timeout_seconds() -> u64 {
+    600
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -36,7 +93,20 @@ pub struct EmbeddingConfig {
     /// (`~/.local/share/ethos/models/all-MiniLM-L6-v2.onnx`)
     #[serde(default)]
     pub onnx_model_path: String,
+    /// Expected SHA-256 (hex) of the ONNX model file, verified on load so a
+    /// truncated download fails fast. Unset skips the check.
+    #[serde(default)]
+    pub onnx_model_sha256: Option<String>,
     pub onnx_dimensions: u32,
+    /// Threads ONNX Runtime uses inside one operator. Kept low so local
+    /// embedding doesn't take every core of a shared machine; `0` lets ONNX
+    /// Runtime use all of them.
+    #[serde(default = "default_onnx_threads")]
+    pub onnx_intra_threads: usize,
+    /// Threads running independent graph branches in parallel; above 1 the
+    /// session switches to parallel execution. `0` is ONNX Runtime's default.
+    #[serde(default = "default_onnx_threads")]
+    pub onnx_inter_threads: usize,
     pub batch_size: u32,
     pub batch_timeout_seconds: u64,
     pub queue_capacity: u32,
@@ -48,6 +118,48 @@ pub struct EmbeddingConfig {
     pub reembed_batch_size: usize,
     #[serde(default = "default_reembed_enabled")]
     pub reembed_enabled: bool,
+    /// Embedding calls the re-embed worker keeps in flight at once.
+    /// `rate_limit_rpm` still applies to all of them together.
+    #[serde(default = "default_reembed_concurrency")]
+    pub reembed_concurrency: usize,
+    /// `/health` reports `degraded` once more than this many rows are waiting
+    /// for a vector, i.e. ingest is outpacing embedding. `0` never degrades.
+    #[serde(default)]
+    pub pending_degraded_threshold: u64,
+
+    /// L2-normalize embeddings before storage. Unset uses the backend
+    /// default: on for ONNX, off for Gemini.
+    #[serde(default)]
+    pub normalize: Option<bool>,
+
+    /// Gemini input is truncated to this many characters before embedding.
+    /// ONNX truncates at the tokenizer's max sequence length instead.
+    #[serde(default = "default_max_input_chars")]
+    pub max_input_chars: usize,
+
+    /// Cap on a single Gemini retry delay; backoff grows exponentially up to it
+    #[serde(default = "default_retry_max_delay_ms")]
+    pub retry_max_delay_ms: u64,
+    /// Randomize retry delays so concurrent callers don't retry in lockstep
+    #[serde(default = "default_retry_jitter")]
+    pub retry_jitter: bool,
+
+    /// Consecutive failed Gemini calls that open the circuit breaker,
+    /// making embeds fail fast (0 disables the breaker)
+    #[serde(default = "default_circuit_breaker_threshold")]
+    pub circuit_breaker_threshold: u32,
+    /// Failures further apart than this start a new count
+    #[serde(default = "default_circuit_breaker_window_seconds")]
+    pub circuit_breaker_window_seconds: u64,
+    /// How long the open circuit fails fast before probing Gemini again
+    #[serde(default = "default_circuit_breaker_cooldown_seconds")]
+    pub circuit_breaker_cooldown_seconds: u64,
+
+    /// Backend name per memory source (e.g. `file = "gemini"`); sources not
+    /// listed use `backend`. All backends must embed into the same space as
+    /// `backend`.
+    #[serde(default)]
+    pub source_backends: HashMap<String, String>,
 }
 
 fn default_reembed_interval() -> u64 {
@@ -59,6 +171,30 @@ fn default_reembed_batch_size() -> usize {
 fn default_reembed_enabled() -> bool {
     true
 }
+fn default_reembed_concurrency() -> usize {
+    4
+}
+fn default_onnx_threads() -> usize {
+    1
+}
+fn default_max_input_chars() -> usize {
+    crate::embeddings::DEFAULT_MAX_INPUT_CHARS
+}
+fn default_retry_max_delay_ms() -> u64 {
+    crate::embeddings::DEFAULT_RETRY_MAX_DELAY_MS
+}
+fn default_retry_jitter() -> bool {
+    true
+}
+fn default_circuit_breaker_threshold() -> u32 {
+    5
+}
+fn default_circuit_breaker_window_seconds() -> u64 {
+    60
+}
+fn default_circuit_breaker_cooldown_seconds() -> u64 {
+    30
+}
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ConsolidationConfig {
@@ -68,6 +204,49 @@ pub struct ConsolidationConfig {
     pub importance_threshold: f32,
     pub repetition_threshold: u32,
     pub retrieval_threshold: u32,
+    /// Candidate episodes whose stored vectors are at least this cosine-similar
+    /// are merged before extraction, keeping the most important one
+    #[serde(default = "default_dedup_similarity_threshold")]
+    pub dedup_similarity_threshold: f32,
+    /// Episodes fetched per candidate batch
+    #[serde(default = "default_max_candidates")]
+    pub max_candidates: usize,
+    /// Keep fetching batches until no candidates remain, instead of one batch per cycle
+    #[serde(default)]
+    pub drain_all: bool,
+    /// Cron expression (local time) for when cycles run, e.g. `"0 3 * * *"`
+    /// for 3am daily; validated at load. Unset runs every `interval_minutes`.
+    #[serde(default, deserialize_with = "deserialize_cron")]
+    pub cron: Option<String>,
+    /// Run a scheduled cycle even when the system isn't idle once this many
+    /// minutes have passed without a successful one. `0` always waits for idle.
+    #[serde(default)]
+    pub force_after_minutes: u64,
+}
+
+/// Parse a `[consolidation] cron` expression. Standard five-field
+/// expressions (minute hour day month weekday) get a leading `0` seconds
+/// field; six or seven fields are taken as seconds-first (and year-last).
+pub fn parse_cron_schedule(expr: &str) -> Result<cron::Schedule, String> {
+    let expr = expr.trim();
+    let full = if expr.split_whitespace().count() == 5 {
+        format!("0 {}", expr)
+    } else {
+        expr.to_string()
+    };
+    full.parse()
+        .map_err(|e| format!("invalid cron expression '{}': {}", expr, e))
+}
+
+fn deserialize_cron<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let expr = Option::<String>::deserialize(deserializer)?;
+    if let Some(expr) = &expr {
+        parse_cron_schedule(expr).map_err(serde::de::Error::custom)?;
+    }
+    Ok(expr)
 }
 
 impl Default for ConsolidationConfig {
@@ -79,21 +258,149 @@ impl Default for ConsolidationConfig {
             importance_threshold: 0.8,
             repetition_threshold: 3,
             retrieval_threshold: 5,
+            dedup_similarity_threshold: default_dedup_similarity_threshold(),
+            max_candidates: default_max_candidates(),
+            drain_all: false,
+            cron: None,
+            force_after_minutes: 0,
         }
     }
 }
 
+fn default_dedup_similarity_threshold() -> f32 {
+    0.95
+}
+
+fn default_max_candidates() -> usize {
+    100
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct RetrievalConfig {
+    /// Per-hop attenuation of `spreading_strength`: hop `n` spreads with
+    /// `spreading_strength * (1 - decay_factor)^n`
     pub decay_factor: f32,
     pub spreading_strength: f32,
     pub iterations: u32,
     pub anchor_top_k_episodes: u32,
     pub anchor_top_k_facts: u32,
+    /// Spreading searches drop anchors whose similarity score is below this
+    /// before spreading, so weak cosine hits neither seed the graph nor
+    /// appear unless spreading reaches them. `0` keeps every anchor.
+    #[serde(default)]
+    pub anchor_min_score: f32,
     pub weight_similarity: f32,
     pub weight_activation: f32,
     pub weight_structural: f32,
+    /// Weight of the anchor's retrieval count (log-scaled and normalised
+    /// against the other anchors) in the final score. `0` ignores it. The
+    /// `weight_*` terms are independent and need not sum to 1.
+    #[serde(default)]
+    pub weight_frequency: f32,
+    /// Weight of a read-time recency factor in the final score: 1 for a
+    /// memory created now, falling linearly to 0 at `recency_horizon_days`.
+    /// `0` ignores it. Unlike decay, nothing stored changes.
+    #[serde(default)]
+    pub weight_recency: f32,
+    /// Age in days at which the recency factor reaches 0
+    #[serde(default = "default_recency_horizon_days")]
+    pub recency_horizon_days: f64,
+    /// Spreading searches drop nodes whose combined `final_score` is below
+    /// this. Unlike a request's `min_score`, which filters anchors by cosine
+    /// similarity in every mode (and may be relaxed by `min_results`), it is
+    /// a server-wide floor on the spreading ranking and is never relaxed.
+    /// `0` keeps every node.
     pub confidence_gate: f32,
+    /// Minimum number of results to aim for by progressively relaxing
+    /// `min_score`; scope filters are never relaxed. `0` disables relaxation.
+    #[serde(default)]
+    pub min_results: u32,
+    /// MMR trade-off for `diversify` searches: 1.0 ranks purely by relevance,
+    /// lower values penalise results similar to ones already selected.
+    #[serde(default = "default_mmr_lambda")]
+    pub mmr_lambda: f64,
+    /// Vector distance used to rank anchors
+    #[serde(default)]
+    pub distance_metric: DistanceMetric,
+    /// Each retrieval of a fact closes this fraction of the gap between its
+    /// confidence and 1.0
+    #[serde(default = "default_retrieval_confidence_gain")]
+    pub retrieval_confidence_gain: f64,
+    /// Synonyms used by `expand` searches, keyed by lowercase word
+    #[serde(default)]
+    pub query_synonyms: HashMap<String, Vec<String>>,
+    /// Most query variants (including the original) an `expand` search embeds
+    #[serde(default = "default_max_query_variants")]
+    pub max_query_variants: usize,
+    /// Rescales returned `score`s for display; ranking is unaffected
+    #[serde(default)]
+    pub score_transform: ScoreTransform,
+    /// Give up on embedding a search query after this long and answer with a
+    /// degraded (empty) response. Ingest keeps the embedding client's own
+    /// longer timeout. `0` waits for the client.
+    #[serde(default)]
+    pub query_timeout_ms: u64,
+    /// Overall deadline for a search. When it passes during spreading or
+    /// re-ranking the cosine-ranked anchor page is returned, flagged
+    /// `degraded` and `timed_out`. `0` disables the deadline.
+    #[serde(default)]
+    pub max_search_ms: u64,
+    /// Whether searches that don't set `use_spreading` apply spreading
+    /// activation. A request's own value always wins.
+    #[serde(default)]
+    pub default_use_spreading: bool,
+}
+
+impl RetrievalConfig {
+    /// Resolve a request's `use_spreading` against `default_use_spreading`.
+    pub fn use_spreading(&self, requested: Option<bool>) -> bool {
+        requested.unwrap_or(self.default_use_spreading)
+    }
+}
+
+fn default_recency_horizon_days() -> f64 {
+    30.0
+}
+
+fn default_mmr_lambda() -> f64 {
+    0.5
+}
+
+fn default_retrieval_confidence_gain() -> f64 {
+    0.04
+}
+
+fn default_max_query_variants() -> usize {
+    4
+}
+
+/// pgvector distance operator for anchor search (`[retrieval] distance_metric`).
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DistanceMetric {
+    /// `<=>`, score = 1 - distance
+    #[default]
+    Cosine,
+    /// `<#>` (negative inner product), score = inner product
+    Dot,
+    /// `<->`, score = 1 / (1 + distance)
+    L2,
+}
+
+/// Display rescaling of search scores (`[retrieval] score_transform`).
+/// Cosine scores bunch up around 0.7–0.9; these spread them over 0–1.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScoreTransform {
+    /// Raw ranking scores
+    #[default]
+    None,
+    /// Maps 0.5–1.0 onto 0–1, clamping lower scores to 0
+    Linear,
+    /// Logistic curve centred on 0.8
+    Sigmoid,
+    /// Best result of the page → 1.0, worst → 0.0
+    Minmax,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -103,12 +410,74 @@ pub struct DecayConfig {
     pub frequency_weight: f64,
     pub emotional_weight: f64,
     pub prune_threshold: f64,
+    /// Coalesce retrieval (LTP) writes over this window and flush them as one
+    /// batched update per table. `0` records each retrieval immediately.
+    #[serde(default)]
+    pub retrieval_flush_ms: u64,
+    /// Episodes with `importance` at or above this are treated as pinned
+    #[serde(default = "default_pin_threshold")]
+    pub pin_threshold: f64,
+    /// Lowest salience a pinned memory decays to (never below `prune_threshold`)
+    #[serde(default = "default_min_salience_floor")]
+    pub min_salience_floor: f64,
+    /// `POST /admin/gc` hard-deletes rows pruned more than this many days ago
+    #[serde(default = "default_gc_retention_days")]
+    pub gc_retention_days: u32,
+    /// Append one JSONL record per pruned row (id, table, final salience,
+    /// reason) to `audit_path`
+    #[serde(default)]
+    pub audit_prunes: bool,
+    /// Prune audit file; `~` expands to the home directory
+    #[serde(default = "default_prune_audit_path")]
+    pub audit_path: String,
+    /// Most rows each table's sweep visits per run, in batches of 500 by id,
+    /// continuing where the previous sweep stopped. `0` sweeps every eligible row.
+    #[serde(default)]
+    pub max_rows: usize,
+}
+
+fn default_pin_threshold() -> f64 {
+    0.95
+}
+
+fn default_min_salience_floor() -> f64 {
+    0.2
+}
+
+fn default_gc_retention_days() -> u32 {
+    30
+}
+
+fn default_prune_audit_path() -> String {
+    "~/.local/share/ethos/decay-audit.jsonl".to_string()
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ConflictResolutionConfig {
     pub auto_supersede_confidence_delta: f64,
     pub review_inbox: String,
+    /// Each refinement of a fact closes this fraction of the gap between its
+    /// confidence and 1.0
+    #[serde(default = "default_refinement_confidence_gain")]
+    pub refinement_confidence_gain: f64,
+    /// Consolidation cycles after a conflict was flagged during which the
+    /// same contradiction only bumps `flag_count` instead of being flagged
+    /// again, even once resolved. Unresolved flags are never duplicated.
+    #[serde(default = "default_flag_cooldown_cycles")]
+    pub flag_cooldown_cycles: u32,
+    /// Minimum Levenshtein ratio (0–1) at which two fact objects count as
+    /// compatible refinements, for pairs where neither contains the other as
+    /// whole words. `0` keeps plain substring containment.
+    #[serde(default)]
+    pub object_similarity_threshold: f64,
+}
+
+fn default_flag_cooldown_cycles() -> u32 {
+    96
+}
+
+fn default_refinement_confidence_gain() -> f64 {
+    0.1
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -116,6 +485,25 @@ pub struct HttpConfig {
     pub enabled: bool,
     pub host: String,
     pub port: u16,
+    /// Bearer token required on all endpoints except `/health` and `/version`.
+    /// Unset leaves the API open.
+    #[serde(default)]
+    pub api_token: Option<String>,
+    /// Per-client limit on `/search` and `/ingest` (keyed by the bearer token
+    /// when it matches `api_token`, else client IP). `0` disables rate limiting.
+    #[serde(default)]
+    pub requests_per_minute: u32,
+    /// Largest accepted request body; larger bodies get 413. `0` removes the limit.
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+    /// Requests still running after this long get 408. `0` disables the timeout.
+    #[serde(default)]
+    pub request_timeout_seconds: u64,
+}
+
+/// axum's built-in body limit, so deployments without the key see no change
+fn default_max_body_bytes() -> usize {
+    2 * 1024 * 1024
 }
 
 impl Default for HttpConfig {
@@ -124,6 +512,94 @@ impl Default for HttpConfig {
             enabled: true,
             host: "127.0.0.1".to_string(),
             port: 8766,
+            api_token: None,
+            requests_per_minute: 0,
+            max_body_bytes: default_max_body_bytes(),
+            request_timeout_seconds: 0,
+        }
+    }
+}
+
+/// Storage of `memory_vectors.content`.
+///
+/// Compressed rows hold base64-encoded gzip and are flagged with
+/// `content_compressed`; they are decompressed on read. SQL text matching
+/// (`ILIKE`, trigram) cannot see inside compressed rows.
+#[derive(Debug, Deserialize, Clone)]
+pub struct StorageConfig {
+    #[serde(default)]
+    pub compress_content: bool,
+    /// Only content larger than this many bytes is compressed
+    #[serde(default = "default_compress_threshold_bytes")]
+    pub compress_threshold_bytes: usize,
+}
+
+fn default_compress_threshold_bytes() -> usize {
+    4096
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            compress_content: false,
+            compress_threshold_bytes: default_compress_threshold_bytes(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct LoggingConfig {
+    /// Append a JSONL record of every search (query, result ids, timing) to
+    /// `audit_path`, independent of tracing
+    #[serde(default)]
+    pub audit_searches: bool,
+    /// Search audit file; `~` expands to the home directory
+    #[serde(default = "default_audit_path")]
+    pub audit_path: String,
+}
+
+fn default_audit_path() -> String {
+    "~/.local/share/ethos/search-audit.jsonl".to_string()
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            audit_searches: false,
+            audit_path: default_audit_path(),
+        }
+    }
+}
+
+/// Background edge suggestion: links recently ingested vectors to their
+/// nearest neighbours in `memory_graph_links`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct LinkerConfig {
+    /// Minutes between edge suggestion passes (0 = off)
+    #[serde(default)]
+    pub suggest_interval_minutes: u64,
+    /// Minimum cosine similarity for a suggested edge
+    #[serde(default = "default_suggest_threshold")]
+    pub suggest_threshold: f64,
+    /// Most suggested edges per recently ingested vector
+    #[serde(default = "default_suggest_max_per_node")]
+    pub suggest_max_per_node: usize,
+}
+
+fn default_suggest_threshold() -> f64 {
+    0.8
+}
+
+fn default_suggest_max_per_node() -> usize {
+    3
+}
+
+impl Default for LinkerConfig {
+    fn default() -> Self {
+        Self {
+            suggest_interval_minutes: 0,
+            suggest_threshold: default_suggest_threshold(),
+            suggest_max_per_node: default_suggest_max_per_node(),
         }
     }
 }
@@ -136,3 +612,80 @@ impl EthosConfig {
         s.try_deserialize()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Load the example config with `edit` applied to its text
+    fn load_example(edit: impl FnOnce(String) -> String) -> Result<EthosConfig, ConfigError> {
+        let path =
+            std::env::temp_dir().join(format!("ethos-config-test-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            edit(include_str!("../../ethos.toml.example").to_string()),
+        )
+        .expect("write temp config");
+        let config = EthosConfig::load(path.to_str().unwrap());
+        std::fs::remove_file(&path).ok();
+        config
+    }
+
+    #[test]
+    fn test_consolidation_cron_validated_at_load() {
+        let with_cron = |expr: &str| {
+            let line = format!("[consolidation]\ncron = \"{}\"", expr);
+            load_example(|text| text.replacen("[consolidation]", &line, 1))
+        };
+
+        let config = with_cron("0 3 * * *").expect("five-field cron should load");
+        assert_eq!(config.consolidation.cron.as_deref(), Some("0 3 * * *"));
+        assert!(
+            with_cron("0 0 3 * * *").is_ok(),
+            "seconds-first cron should load"
+        );
+
+        let err = with_cron("every night").expect_err("invalid cron must be rejected");
+        assert!(
+            err.to_string().contains("invalid cron expression"),
+            "{}",
+            err
+        );
+        assert!(with_cron("0 25 * * *").is_err(), "hour 25 must be rejected");
+
+        let config = load_example(|text| text).expect("example config should load");
+        assert_eq!(config.consolidation.cron, None);
+    }
+
+    #[test]
+    fn test_default_use_spreading_applies_only_when_request_omits_it() {
+        let search = |body: serde_json::Value| match serde_json::from_value(body).unwrap() {
+            crate::ipc::EthosRequest::Search { use_spreading, .. } => use_spreading,
+            other => panic!("unexpected request variant: {other:?}"),
+        };
+        let omitted = search(serde_json::json!({ "action": "search", "query": "q" }));
+        let explicit_off = search(serde_json::json!({
+            "action": "search", "query": "q", "use_spreading": false
+        }));
+        let explicit_on = search(serde_json::json!({
+            "action": "search", "query": "q", "use_spreading": true
+        }));
+        assert_eq!(omitted, None);
+
+        let spreading_by_default = load_example(|text| {
+            text.replacen(
+                "default_use_spreading = false",
+                "default_use_spreading = true",
+                1,
+            )
+        })
+        .expect("config should load");
+        let retrieval = &spreading_by_default.retrieval;
+        assert!(retrieval.use_spreading(omitted));
+        assert!(!retrieval.use_spreading(explicit_off));
+
+        let example = load_example(|text| text).expect("example config should load");
+        assert!(!example.retrieval.use_spreading(omitted));
+        assert!(example.retrieval.use_spreading(explicit_on));
+    }
+}
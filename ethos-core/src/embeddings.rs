@@ -4,12 +4,16 @@
 //! - **Gemini** — cloud embeddings via the Gemini API (768-dim)
 //! - **ONNX** — local embeddings via `all-MiniLM-L6-v2` (384-dim)
 //! - **Gemini-fallback-ONNX** — Gemini with graceful degradation to `Ok(None)`
+//!
+//! [`SourceRoutedBackend`] picks one of several backends per memory source.
 
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio_retry::strategy::{jitter, ExponentialBackoff};
 use tokio_retry::Retry;
@@ -20,6 +24,15 @@ pub const GEMINI_DIMENSIONS: usize = 768;
 /// Default ONNX (all-MiniLM-L6-v2) embedding dimensions
 pub const ONNX_DIMENSIONS: usize = 384;
 
+/// Default character bound on embedding input (~2048 Gemini tokens)
+pub const DEFAULT_MAX_INPUT_CHARS: usize = 8000;
+
+/// Default cap on a single Gemini retry delay
+pub const DEFAULT_RETRY_MAX_DELAY_MS: u64 = 10_000;
+
+/// Max sequence length (tokens) for all-MiniLM-L6-v2
+pub const ONNX_MAX_SEQ_LEN: usize = 256;
+
 // ============================================================================
 // EmbeddingBackend trait
 // ============================================================================
@@ -38,11 +51,72 @@ pub trait EmbeddingBackend: Send + Sync {
         self.embed(text).await
     }
 
+    /// Embed a search query with an explicit task type (e.g. `SEMANTIC_SIMILARITY`
+    /// for symmetric comparison). Backends without task-type support ignore the
+    /// hint and call `embed_query()`.
+    async fn embed_query_as(
+        &self,
+        text: &str,
+        _task_type: TaskType,
+    ) -> Result<Option<Vec<f32>>, EmbeddingError> {
+        self.embed_query(text).await
+    }
+
+    /// Embed several texts, one result per input in order. Backends that can
+    /// run a single batched request (e.g. ONNX) override this; the default
+    /// calls `embed()` for each text, and a text that fails comes back `None`
+    /// so one bad input doesn't discard the rest of the batch.
+    async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Option<Vec<f32>>>, EmbeddingError> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for (index, text) in texts.iter().enumerate() {
+            embeddings.push(match self.embed(text).await {
+                Ok(embedding) => embedding,
+                Err(e) => {
+                    tracing::warn!(index, error = %e, "Failed to embed batch item");
+                    None
+                }
+            });
+        }
+        Ok(embeddings)
+    }
+
+    /// Embed a memory ingested from `source` (its `memory_vectors.source`).
+    /// Only [`SourceRoutedBackend`] looks at the source; the default calls
+    /// `embed()`.
+    async fn embed_for_source(
+        &self,
+        text: &str,
+        _source: Option<&str>,
+    ) -> Result<Option<Vec<f32>>, EmbeddingError> {
+        self.embed(text).await
+    }
+
+    /// [`embed_batch`](Self::embed_batch) for texts that share one source.
+    async fn embed_batch_for_source(
+        &self,
+        texts: &[&str],
+        _source: Option<&str>,
+    ) -> Result<Vec<Option<Vec<f32>>>, EmbeddingError> {
+        self.embed_batch(texts).await
+    }
+
+    /// Character bound callers should apply via [`prepare_text_for_embedding`].
+    /// `None` when the backend truncates internally (e.g. ONNX, by tokens).
+    fn max_input_chars(&self) -> Option<usize> {
+        None
+    }
+
     /// Returns the embedding dimension (e.g., 768 or 384).
     fn dimensions(&self) -> usize;
 
     /// Backend name for logging.
     fn name(&self) -> &str;
+
+    /// The vector space this backend embeds into. Vectors from different
+    /// spaces aren't comparable even when they have the same width.
+    fn embedding_space(&self) -> String {
+        self.name().to_string()
+    }
 }
 
 // ============================================================================
@@ -50,12 +124,15 @@ pub trait EmbeddingBackend: Send + Sync {
 // ============================================================================
 
 /// Task type for embedding API
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TaskType {
     #[default]
     RetrievalDocument,
     RetrievalQuery,
+    SemanticSimilarity,
+    Classification,
+    Clustering,
 }
 
 /// Embedding generation errors
@@ -76,17 +153,52 @@ pub enum EmbeddingError {
     #[error("Missing API key")]
     MissingApiKey,
 
-    #[error("All {attempts} retry attempts failed")]
-    RetryExhausted { attempts: usize },
+    #[error("All {attempts} retry attempts failed: {last_error}")]
+    RetryExhausted { attempts: usize, last_error: String },
+
+    #[error("Circuit open after repeated failures — retrying in {retry_in_ms}ms")]
+    CircuitOpen { retry_in_ms: u64 },
 
     #[error("ONNX model not found at {path} — run scripts/download-onnx-model.sh to fetch it")]
     ModelNotFound { path: String },
 
+    #[error(
+        "ONNX model at {path} is corrupt (SHA-256 {actual}, expected {expected}) — \
+         re-run scripts/download-onnx-model.sh"
+    )]
+    ModelCorrupt {
+        path: String,
+        expected: String,
+        actual: String,
+    },
+
     #[error("ONNX inference error: {0}")]
     OnnxInference(String),
 
     #[error("Tokenizer error: {0}")]
     Tokenizer(String),
+
+    #[error(
+        "Backend '{backend}' for source '{source_name}' produces {actual}-dim vectors, \
+         but the default backend produces {expected}-dim"
+    )]
+    MixedDimensions {
+        source_name: String,
+        backend: String,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error(
+        "Backend '{backend}' for source '{source_name}' embeds into '{actual}', \
+         but the default backend embeds into '{expected}'"
+    )]
+    MixedEmbeddingSpaces {
+        source_name: String,
+        backend: String,
+        expected: String,
+        actual: String,
+    },
 }
 
 // ============================================================================
@@ -101,6 +213,37 @@ pub struct EmbeddingConfig {
     pub dimensions: usize,
     pub max_retries: usize,
     pub retry_delay_ms: u64,
+    /// Upper bound on a single backoff delay
+    pub max_delay_ms: u64,
+    /// Randomize each backoff delay so concurrent callers don't retry in lockstep
+    pub jitter: bool,
+    /// L2-normalize returned vectors (see [`l2_normalize`])
+    pub normalize: bool,
+    /// Input longer than this many characters is truncated before embedding
+    pub max_input_chars: usize,
+    /// When to stop calling the API after repeated failures
+    pub breaker: CircuitBreakerConfig,
+}
+
+/// Circuit breaker settings for [`GeminiEmbeddingClient`].
+#[derive(Debug, Clone, Copy)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failed calls (after retries) that open the circuit; 0 disables
+    pub failure_threshold: u32,
+    /// Failures further apart than this start a new count
+    pub window: Duration,
+    /// How long an open circuit fails fast before letting one probe through
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            window: Duration::from_secs(60),
+            cooldown: Duration::from_secs(30),
+        }
+    }
 }
 
 impl EmbeddingConfig {
@@ -115,6 +258,11 @@ impl EmbeddingConfig {
             dimensions,
             max_retries: 3,
             retry_delay_ms: 1000,
+            max_delay_ms: DEFAULT_RETRY_MAX_DELAY_MS,
+            jitter: true,
+            normalize: false,
+            max_input_chars: DEFAULT_MAX_INPUT_CHARS,
+            breaker: CircuitBreakerConfig::default(),
         }
     }
 }
@@ -124,7 +272,15 @@ impl EmbeddingConfig {
 pub struct OnnxConfig {
     pub model_path: PathBuf,
     pub tokenizer_path: PathBuf,
+    /// Hex SHA-256 the model file must match (see [`crate::onnx_embedder::verify_model_sha256`])
+    pub expected_sha256: Option<String>,
     pub dimensions: usize,
+    /// L2-normalize the mean-pooled output (see [`l2_normalize`])
+    pub normalize: bool,
+    /// ONNX Runtime intra-op threads (`0` = runtime default, all cores)
+    pub intra_threads: usize,
+    /// ONNX Runtime inter-op threads; above 1 enables parallel execution
+    pub inter_threads: usize,
 }
 
 /// Configuration union for the backend factory.
@@ -143,6 +299,40 @@ pub fn create_backend(config: BackendConfig) -> Result<Box<dyn EmbeddingBackend>
     }
 }
 
+/// Truncate `text` to at most `max_chars` characters (on a char boundary).
+///
+/// Backends reject or silently clip over-long input in inconsistent ways, so
+/// callers bound it here first. Truncation is logged.
+pub fn prepare_text_for_embedding(text: &str, max_chars: Option<usize>) -> &str {
+    let Some(max_chars) = max_chars else {
+        return text;
+    };
+    match text.char_indices().nth(max_chars) {
+        Some((byte_idx, _)) => {
+            tracing::warn!(
+                max_chars,
+                original_chars = text.chars().count(),
+                "Truncating over-long input before embedding"
+            );
+            &text[..byte_idx]
+        }
+        None => text,
+    }
+}
+
+/// Scale `vector` to unit L2 length in place.
+///
+/// pgvector cosine distance assumes roughly unit-length vectors. An all-zero
+/// vector has no direction and is left unchanged.
+pub fn l2_normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for v in vector.iter_mut() {
+            *v /= norm;
+        }
+    }
+}
+
 // ============================================================================
 // Gemini API structs (private)
 // ============================================================================
@@ -189,16 +379,108 @@ struct GeminiErrorDetail {
     message: String,
 }
 
+// ============================================================================
+// CircuitBreaker
+// ============================================================================
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    last_failure_at: Option<Instant>,
+    /// Set while the circuit is open (or half-open, awaiting a probe result)
+    opened_at: Option<Instant>,
+}
+
+/// Closed → open after `failure_threshold` failures within `window`; open
+/// fails fast for `cooldown`, then admits one probe call (half-open). A
+/// successful probe closes the circuit, a failed one re-opens it.
+#[derive(Debug)]
+struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    /// Admit a call, or return how long until the next probe is allowed.
+    fn try_acquire(&self) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(opened_at) = state.opened_at else {
+            return Ok(());
+        };
+        let elapsed = opened_at.elapsed();
+        if elapsed < self.config.cooldown {
+            return Err(self.config.cooldown - elapsed);
+        }
+        // Half-open: this call is the probe. Restarting the cooldown keeps
+        // other callers failing fast until it reports back (or, if the
+        // probe is dropped, until the next probe is due).
+        state.opened_at = Some(Instant::now());
+        tracing::info!("Embedding circuit half-open — probing Gemini");
+        Ok(())
+    }
+
+    fn record_success(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.opened_at.is_some() {
+            tracing::info!("Embedding circuit closed — Gemini recovered");
+        }
+        *state = BreakerState::default();
+    }
+
+    fn record_failure(&self) {
+        if self.config.failure_threshold == 0 {
+            return;
+        }
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        if state.opened_at.is_some() {
+            state.opened_at = Some(now);
+            tracing::warn!("Embedding probe failed — circuit re-opened");
+            return;
+        }
+
+        let in_window = state
+            .last_failure_at
+            .is_some_and(|at| now.duration_since(at) <= self.config.window);
+        state.consecutive_failures = if in_window {
+            state.consecutive_failures + 1
+        } else {
+            1
+        };
+        state.last_failure_at = Some(now);
+
+        if state.consecutive_failures >= self.config.failure_threshold {
+            state.opened_at = Some(now);
+            tracing::warn!(
+                failures = state.consecutive_failures,
+                cooldown_ms = self.config.cooldown.as_millis() as u64,
+                "Embedding circuit opened — failing fast until cooldown elapses"
+            );
+        }
+    }
+}
+
 // ============================================================================
 // GeminiEmbeddingClient
 // ============================================================================
 
 /// Gemini embedding client — calls the Gemini Embeddings API.
+///
+/// Clones share one circuit breaker, so an outage seen by any clone makes
+/// all of them fail fast.
 #[derive(Debug, Clone)]
 pub struct GeminiEmbeddingClient {
     client: Client,
     config: EmbeddingConfig,
     base_url: String,
+    breaker: Arc<CircuitBreaker>,
 }
 
 impl GeminiEmbeddingClient {
@@ -211,6 +493,7 @@ impl GeminiEmbeddingClient {
 
         Ok(Self {
             client,
+            breaker: Arc::new(CircuitBreaker::new(config.breaker)),
             config,
             base_url: "https://generativelanguage.googleapis.com/v1beta".to_string(),
         })
@@ -229,6 +512,7 @@ impl GeminiEmbeddingClient {
 
         Ok(Self {
             client,
+            breaker: Arc::new(CircuitBreaker::new(config.breaker)),
             config,
             base_url,
         })
@@ -240,22 +524,34 @@ impl GeminiEmbeddingClient {
             .await
     }
 
-    /// Generate an embedding with a specific task type
+    /// Generate an embedding with a specific task type. Fails immediately
+    /// with [`EmbeddingError::CircuitOpen`] while the circuit breaker is open.
     pub async fn embed_with_task(
         &self,
         text: &str,
         task_type: TaskType,
     ) -> Result<Vec<f32>, EmbeddingError> {
+        if let Err(retry_in) = self.breaker.try_acquire() {
+            return Err(EmbeddingError::CircuitOpen {
+                retry_in_ms: retry_in.as_millis() as u64,
+            });
+        }
+
+        let with_jitter = self.config.jitter;
         let retry_strategy = ExponentialBackoff::from_millis(self.config.retry_delay_ms)
-            .max_delay(Duration::from_secs(10))
-            .map(jitter)
+            .max_delay(Duration::from_millis(self.config.max_delay_ms))
+            .map(move |delay| if with_jitter { jitter(delay) } else { delay })
             .take(self.config.max_retries);
 
         let result = Retry::spawn(retry_strategy, || self.embed_once(text, task_type)).await;
 
         match result {
-            Ok(vec) => Ok(vec),
+            Ok(vec) => {
+                self.breaker.record_success();
+                Ok(vec)
+            }
             Err(e) => {
+                self.breaker.record_failure();
                 tracing::error!(
                     attempts = self.config.max_retries,
                     error = %e,
@@ -263,6 +559,7 @@ impl GeminiEmbeddingClient {
                 );
                 Err(EmbeddingError::RetryExhausted {
                     attempts: self.config.max_retries,
+                    last_error: e.to_string(),
                 })
             }
         }
@@ -310,7 +607,7 @@ impl GeminiEmbeddingClient {
 
         let gemini_response: GeminiResponse = response.json().await?;
 
-        let values = gemini_response.embedding.values;
+        let mut values = gemini_response.embedding.values;
 
         if values.len() != self.config.dimensions {
             return Err(EmbeddingError::InvalidDimensions {
@@ -319,6 +616,10 @@ impl GeminiEmbeddingClient {
             });
         }
 
+        if self.config.normalize {
+            l2_normalize(&mut values);
+        }
+
         Ok(values)
     }
 }
@@ -330,9 +631,19 @@ impl EmbeddingBackend for GeminiEmbeddingClient {
     }
 
     async fn embed_query(&self, text: &str) -> Result<Option<Vec<f32>>, EmbeddingError> {
-        self.embed_with_task(text, TaskType::RetrievalQuery)
-            .await
-            .map(Some)
+        self.embed_query_as(text, TaskType::RetrievalQuery).await
+    }
+
+    async fn embed_query_as(
+        &self,
+        text: &str,
+        task_type: TaskType,
+    ) -> Result<Option<Vec<f32>>, EmbeddingError> {
+        self.embed_with_task(text, task_type).await.map(Some)
+    }
+
+    fn max_input_chars(&self) -> Option<usize> {
+        Some(self.config.max_input_chars)
     }
 
     fn dimensions(&self) -> usize {
@@ -342,6 +653,10 @@ impl EmbeddingBackend for GeminiEmbeddingClient {
     fn name(&self) -> &str {
         "gemini"
     }
+
+    fn embedding_space(&self) -> String {
+        format!("gemini:{}", self.config.model)
+    }
 }
 
 // ============================================================================
@@ -388,11 +703,15 @@ impl EmbeddingBackend for FallbackEmbeddingClient {
     }
 
     async fn embed_query(&self, text: &str) -> Result<Option<Vec<f32>>, EmbeddingError> {
-        match self
-            .inner
-            .embed_with_task(text, TaskType::RetrievalQuery)
-            .await
-        {
+        self.embed_query_as(text, TaskType::RetrievalQuery).await
+    }
+
+    async fn embed_query_as(
+        &self,
+        text: &str,
+        task_type: TaskType,
+    ) -> Result<Option<Vec<f32>>, EmbeddingError> {
+        match self.inner.embed_with_task(text, task_type).await {
             Ok(v) => Ok(Some(v)),
             Err(e) => {
                 tracing::warn!(
@@ -404,6 +723,10 @@ impl EmbeddingBackend for FallbackEmbeddingClient {
         }
     }
 
+    fn max_input_chars(&self) -> Option<usize> {
+        self.inner.max_input_chars()
+    }
+
     fn dimensions(&self) -> usize {
         self.inner.dimensions()
     }
@@ -411,6 +734,128 @@ impl EmbeddingBackend for FallbackEmbeddingClient {
     fn name(&self) -> &str {
         "gemini-fallback-onnx"
     }
+
+    /// Failures store no vector rather than an ONNX one, so every vector
+    /// this backend produces comes from Gemini.
+    fn embedding_space(&self) -> String {
+        self.inner.embedding_space()
+    }
+}
+
+// ============================================================================
+// SourceRoutedBackend
+// ============================================================================
+
+/// Dispatches memory embeddings to a backend chosen by the memory's source
+/// (e.g. `"file"` → Gemini, `"chat"` → ONNX). Sources without a route, and
+/// all queries, use the default backend. Every backend must embed into the
+/// default's vector space, since routed vectors share one vector column and
+/// are searched with default-backend queries; routes can only differ in how
+/// they fail (e.g. `gemini` vs `gemini-fallback-onnx`).
+pub struct SourceRoutedBackend {
+    default: Arc<dyn EmbeddingBackend>,
+    routes: HashMap<String, Arc<dyn EmbeddingBackend>>,
+}
+
+impl SourceRoutedBackend {
+    pub fn new(
+        default: Arc<dyn EmbeddingBackend>,
+        routes: HashMap<String, Arc<dyn EmbeddingBackend>>,
+    ) -> Result<Self, EmbeddingError> {
+        if let Some((source, backend)) = routes
+            .iter()
+            .find(|(_, backend)| backend.dimensions() != default.dimensions())
+        {
+            return Err(EmbeddingError::MixedDimensions {
+                source_name: source.clone(),
+                backend: backend.name().to_string(),
+                expected: default.dimensions(),
+                actual: backend.dimensions(),
+            });
+        }
+
+        let space = default.embedding_space();
+        if let Some((source, backend)) = routes
+            .iter()
+            .find(|(_, backend)| backend.embedding_space() != space)
+        {
+            return Err(EmbeddingError::MixedEmbeddingSpaces {
+                source_name: source.clone(),
+                backend: backend.name().to_string(),
+                expected: space,
+                actual: backend.embedding_space(),
+            });
+        }
+
+        Ok(Self { default, routes })
+    }
+
+    fn backend_for(&self, source: Option<&str>) -> &dyn EmbeddingBackend {
+        source
+            .and_then(|source| self.routes.get(source))
+            .unwrap_or(&self.default)
+            .as_ref()
+    }
+}
+
+#[async_trait]
+impl EmbeddingBackend for SourceRoutedBackend {
+    async fn embed(&self, text: &str) -> Result<Option<Vec<f32>>, EmbeddingError> {
+        self.default.embed(text).await
+    }
+
+    async fn embed_query(&self, text: &str) -> Result<Option<Vec<f32>>, EmbeddingError> {
+        self.default.embed_query(text).await
+    }
+
+    async fn embed_query_as(
+        &self,
+        text: &str,
+        task_type: TaskType,
+    ) -> Result<Option<Vec<f32>>, EmbeddingError> {
+        self.default.embed_query_as(text, task_type).await
+    }
+
+    async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Option<Vec<f32>>>, EmbeddingError> {
+        self.default.embed_batch(texts).await
+    }
+
+    async fn embed_for_source(
+        &self,
+        text: &str,
+        source: Option<&str>,
+    ) -> Result<Option<Vec<f32>>, EmbeddingError> {
+        self.backend_for(source).embed(text).await
+    }
+
+    async fn embed_batch_for_source(
+        &self,
+        texts: &[&str],
+        source: Option<&str>,
+    ) -> Result<Vec<Option<Vec<f32>>>, EmbeddingError> {
+        self.backend_for(source).embed_batch(texts).await
+    }
+
+    /// The tightest bound of any routed backend, so callers can truncate
+    /// before knowing which backend a text goes to.
+    fn max_input_chars(&self) -> Option<usize> {
+        std::iter::once(&self.default)
+            .chain(self.routes.values())
+            .filter_map(|backend| backend.max_input_chars())
+            .min()
+    }
+
+    fn dimensions(&self) -> usize {
+        self.default.dimensions()
+    }
+
+    fn name(&self) -> &str {
+        self.default.name()
+    }
+
+    fn embedding_space(&self) -> String {
+        self.default.embedding_space()
+    }
 }
 
 // ============================================================================
@@ -420,7 +865,7 @@ impl EmbeddingBackend for FallbackEmbeddingClient {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::matchers::{body_json, body_partial_json, header, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     fn test_config(api_key: &str) -> EmbeddingConfig {
@@ -430,6 +875,11 @@ mod tests {
             dimensions: GEMINI_DIMENSIONS,
             max_retries: 3,
             retry_delay_ms: 100,
+            max_delay_ms: 10_000,
+            jitter: true,
+            normalize: false,
+            max_input_chars: DEFAULT_MAX_INPUT_CHARS,
+            breaker: CircuitBreakerConfig::default(),
         }
     }
 
@@ -487,8 +937,15 @@ mod tests {
 
         assert!(result.is_err(), "Expected error on 500 response");
         match result {
-            Err(EmbeddingError::RetryExhausted { attempts }) => {
+            Err(EmbeddingError::RetryExhausted {
+                attempts,
+                last_error,
+            }) => {
                 assert_eq!(attempts, 3, "Expected 3 retry attempts");
+                assert!(
+                    last_error.contains("Internal server error"),
+                    "last error should be kept, got: {last_error}"
+                );
             }
             _ => panic!("Expected RetryExhausted error"),
         }
@@ -596,6 +1053,11 @@ mod tests {
             dimensions: GEMINI_DIMENSIONS,
             max_retries: 1,
             retry_delay_ms: 10,
+            max_delay_ms: 10_000,
+            jitter: true,
+            normalize: false,
+            max_input_chars: DEFAULT_MAX_INPUT_CHARS,
+            breaker: CircuitBreakerConfig::default(),
         };
         let fallback = FallbackEmbeddingClient::with_base_url(config, mock_server.uri()).unwrap();
 
@@ -630,4 +1092,349 @@ mod tests {
         assert!(result.is_some());
         assert_eq!(result.unwrap().len(), 768);
     }
+
+    fn magnitude(vector: &[f32]) -> f32 {
+        vector.iter().map(|x| x * x).sum::<f32>().sqrt()
+    }
+
+    #[test]
+    fn test_l2_normalize_produces_unit_length() {
+        let mut vector = vec![3.0, 4.0, 0.0, -12.0];
+        l2_normalize(&mut vector);
+
+        assert!((magnitude(&vector) - 1.0).abs() < 1e-6);
+        assert!((vector[0] - 3.0 / 13.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_l2_normalize_leaves_zero_vector_unchanged() {
+        let mut vector = vec![0.0f32; 384];
+        l2_normalize(&mut vector);
+
+        assert!(vector.iter().all(|v| *v == 0.0 && v.is_finite()));
+    }
+
+    #[tokio::test]
+    async fn test_gemini_normalize_returns_unit_vectors() {
+        let mock_server = MockServer::start().await;
+        let config = EmbeddingConfig {
+            normalize: true,
+            max_input_chars: DEFAULT_MAX_INPUT_CHARS,
+            ..test_config("test-api-key")
+        };
+        let client = GeminiEmbeddingClient::with_base_url(config, mock_server.uri()).unwrap();
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(mock_embedding_response()))
+            .mount(&mock_server)
+            .await;
+
+        let embedding = client.embed_raw("hello").await.unwrap();
+        assert!((magnitude(&embedding) - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_prepare_text_truncates_to_bound() {
+        let long = "é".repeat(10_000);
+        let prepared = prepare_text_for_embedding(&long, Some(DEFAULT_MAX_INPUT_CHARS));
+
+        assert_eq!(prepared.chars().count(), DEFAULT_MAX_INPUT_CHARS);
+        assert!(long.starts_with(prepared));
+    }
+
+    #[test]
+    fn test_prepare_text_leaves_short_input_untouched() {
+        let short = "hello world";
+        assert_eq!(prepare_text_for_embedding(short, Some(11)), short);
+        assert_eq!(prepare_text_for_embedding(short, Some(100)), short);
+        assert_eq!(prepare_text_for_embedding(short, None), short);
+    }
+
+    #[test]
+    fn test_gemini_backend_reports_configured_bound() {
+        let config = EmbeddingConfig {
+            max_input_chars: 42,
+            ..test_config("test-api-key")
+        };
+        let client = GeminiEmbeddingClient::new(config).unwrap();
+        assert_eq!(client.max_input_chars(), Some(42));
+    }
+
+    fn breaker_config(failure_threshold: u32, cooldown_ms: u64) -> EmbeddingConfig {
+        EmbeddingConfig {
+            max_retries: 0,
+            breaker: CircuitBreakerConfig {
+                failure_threshold,
+                window: Duration::from_secs(60),
+                cooldown: Duration::from_millis(cooldown_ms),
+            },
+            ..test_config("test-api-key")
+        }
+    }
+
+    async fn request_count(server: &MockServer) -> usize {
+        server.received_requests().await.unwrap_or_default().len()
+    }
+
+    #[tokio::test]
+    async fn test_breaker_opens_after_threshold_and_recovers_after_cooldown() {
+        let mock_server = MockServer::start().await;
+        let client =
+            GeminiEmbeddingClient::with_base_url(breaker_config(3, 200), mock_server.uri())
+                .unwrap();
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&mock_server)
+            .await;
+
+        for _ in 0..3 {
+            assert!(matches!(
+                client.embed_raw("hello").await,
+                Err(EmbeddingError::RetryExhausted { .. })
+            ));
+        }
+        assert_eq!(request_count(&mock_server).await, 3);
+
+        // Open: fails fast without touching the API, for every clone
+        let clone = client.clone();
+        for c in [&client, &clone] {
+            assert!(matches!(
+                c.embed_raw("hello").await,
+                Err(EmbeddingError::CircuitOpen { retry_in_ms }) if retry_in_ms <= 200
+            ));
+        }
+        assert_eq!(request_count(&mock_server).await, 3);
+
+        // Gemini recovers, but the circuit stays open until the cooldown
+        mock_server.reset().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(mock_embedding_response()))
+            .mount(&mock_server)
+            .await;
+        assert!(matches!(
+            client.embed_raw("hello").await,
+            Err(EmbeddingError::CircuitOpen { .. })
+        ));
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert!(client.embed_raw("hello").await.is_ok(), "probe succeeds");
+        assert!(clone.embed_raw("hello").await.is_ok(), "circuit closed");
+        assert_eq!(request_count(&mock_server).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_degrades_fast_while_breaker_open() {
+        let mock_server = MockServer::start().await;
+        let fallback =
+            FallbackEmbeddingClient::with_base_url(breaker_config(1, 60_000), mock_server.uri())
+                .unwrap();
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+
+        assert_eq!(fallback.embed("first").await.unwrap(), None);
+        assert_eq!(fallback.embed("second").await.unwrap(), None);
+        assert_eq!(fallback.embed_query("third").await.unwrap(), None);
+        assert_eq!(request_count(&mock_server).await, 1);
+    }
+
+    #[test]
+    fn test_breaker_failed_probe_reopens_and_window_resets_count() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            window: Duration::from_millis(20),
+            cooldown: Duration::from_millis(20),
+        });
+
+        // Failures further apart than the window never reach the threshold
+        breaker.record_failure();
+        std::thread::sleep(Duration::from_millis(40));
+        breaker.record_failure();
+        assert!(breaker.try_acquire().is_ok());
+
+        breaker.record_failure();
+        assert!(
+            breaker.try_acquire().is_err(),
+            "two failures in window open"
+        );
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(breaker.try_acquire().is_ok(), "half-open admits a probe");
+        assert!(breaker.try_acquire().is_err(), "only one probe at a time");
+        breaker.record_failure();
+        assert!(breaker.try_acquire().is_err(), "failed probe re-opens");
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(breaker.try_acquire().is_ok());
+        breaker.record_success();
+        assert!(breaker.try_acquire().is_ok());
+        assert!(
+            breaker.try_acquire().is_ok(),
+            "closed after successful probe"
+        );
+    }
+
+    #[test]
+    fn test_breaker_disabled_with_zero_threshold() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 0,
+            ..CircuitBreakerConfig::default()
+        });
+        for _ in 0..10 {
+            breaker.record_failure();
+        }
+        assert!(breaker.try_acquire().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_embed_query_as_sends_requested_task_type() {
+        let mock_server = MockServer::start().await;
+        let client =
+            GeminiEmbeddingClient::with_base_url(test_config("test-api-key"), mock_server.uri())
+                .unwrap();
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(
+                serde_json::json!({ "taskType": "SEMANTIC_SIMILARITY" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(mock_embedding_response()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(
+                serde_json::json!({ "taskType": "RETRIEVAL_QUERY" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(mock_embedding_response()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let similarity = client
+            .embed_query_as("hello", TaskType::SemanticSimilarity)
+            .await
+            .unwrap();
+        assert_eq!(similarity.map(|v| v.len()), Some(768));
+        assert!(client.embed_query("hello").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_source_routed_backend_dispatches_by_source() {
+        let file_server = MockServer::start().await;
+        let chat_server = MockServer::start().await;
+        for server in [&file_server, &chat_server] {
+            Mock::given(method("POST"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(mock_embedding_response()))
+                .expect(1)
+                .mount(server)
+                .await;
+        }
+
+        let file_backend: Arc<dyn EmbeddingBackend> = Arc::new(
+            GeminiEmbeddingClient::with_base_url(test_config("test-api-key"), file_server.uri())
+                .unwrap(),
+        );
+        let chat_backend: Arc<dyn EmbeddingBackend> = Arc::new(
+            GeminiEmbeddingClient::with_base_url(test_config("test-api-key"), chat_server.uri())
+                .unwrap(),
+        );
+        let routed = SourceRoutedBackend::new(
+            chat_backend.clone(),
+            HashMap::from([
+                ("file".to_string(), file_backend),
+                ("chat".to_string(), chat_backend),
+            ]),
+        )
+        .unwrap();
+
+        let file = routed
+            .embed_for_source("a long document", Some("file"))
+            .await;
+        let chat = routed.embed_for_source("a chat turn", Some("chat")).await;
+
+        assert_eq!(file.unwrap().map(|v| v.len()), Some(768));
+        assert_eq!(chat.unwrap().map(|v| v.len()), Some(768));
+        // Each mock server verifies on drop that it saw exactly one request
+    }
+
+    #[test]
+    fn test_source_routed_backend_rejects_mixed_dimensions() {
+        let default: Arc<dyn EmbeddingBackend> =
+            Arc::new(GeminiEmbeddingClient::new(test_config("test-api-key")).unwrap());
+        let narrow: Arc<dyn EmbeddingBackend> = Arc::new(
+            GeminiEmbeddingClient::new(EmbeddingConfig {
+                dimensions: ONNX_DIMENSIONS,
+                ..test_config("test-api-key")
+            })
+            .unwrap(),
+        );
+
+        let result =
+            SourceRoutedBackend::new(default, HashMap::from([("chat".to_string(), narrow)]));
+        assert!(matches!(
+            result,
+            Err(EmbeddingError::MixedDimensions {
+                expected: GEMINI_DIMENSIONS,
+                actual: ONNX_DIMENSIONS,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_source_routed_backend_rejects_mixed_embedding_spaces() {
+        let default: Arc<dyn EmbeddingBackend> =
+            Arc::new(GeminiEmbeddingClient::new(test_config("test-api-key")).unwrap());
+        let fallback: Arc<dyn EmbeddingBackend> =
+            Arc::new(FallbackEmbeddingClient::new(test_config("test-api-key")).unwrap());
+        let other_model: Arc<dyn EmbeddingBackend> = Arc::new(
+            GeminiEmbeddingClient::new(EmbeddingConfig {
+                model: "text-embedding-004".to_string(),
+                ..test_config("test-api-key")
+            })
+            .unwrap(),
+        );
+
+        // Same model, different failure handling: one space
+        assert!(SourceRoutedBackend::new(
+            default.clone(),
+            HashMap::from([("file".to_string(), fallback)])
+        )
+        .is_ok());
+
+        let result =
+            SourceRoutedBackend::new(default, HashMap::from([("chat".to_string(), other_model)]));
+        assert!(matches!(
+            result,
+            Err(EmbeddingError::MixedEmbeddingSpaces { ref actual, .. })
+                if actual == "gemini:text-embedding-004"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_default_embed_batch_keeps_items_around_a_failure() {
+        struct RejectsEmpty;
+
+        #[async_trait]
+        impl EmbeddingBackend for RejectsEmpty {
+            async fn embed(&self, text: &str) -> Result<Option<Vec<f32>>, EmbeddingError> {
+                if text.is_empty() {
+                    return Err(EmbeddingError::MissingEmbedding);
+                }
+                Ok(Some(vec![text.len() as f32]))
+            }
+            fn dimensions(&self) -> usize {
+                1
+            }
+            fn name(&self) -> &str {
+                "rejects-empty"
+            }
+        }
+
+        let embeddings = RejectsEmpty.embed_batch(&["a", "", "abc"]).await.unwrap();
+        assert_eq!(embeddings, vec![Some(vec![1.0]), None, Some(vec![3.0])]);
+    }
 }
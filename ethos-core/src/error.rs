@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -16,4 +17,84 @@ pub enum EthosError {
 
     #[error("Other error: {0}")]
     Other(String),
+
+    /// The request itself is invalid
+    #[error("{0}")]
+    BadRequest(String),
+
+    /// The addressed memory or fact does not exist
+    #[error("{0}")]
+    NotFound(String),
+
+    /// Content over `[service] max_content_bytes`
+    #[error("{0}")]
+    PayloadTooLarge(String),
+
+    /// A dependency (database, embedding backend) can't be reached; retrying
+    /// later may succeed
+    #[error("{0}")]
+    Unavailable(String),
+}
+
+/// What kind of failure an error response reports, so clients can tell a bad
+/// request from an outage without parsing the message. Sent as
+/// `EthosResponse::error_kind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    BadRequest,
+    NotFound,
+    PayloadTooLarge,
+    Unavailable,
+    Internal,
+}
+
+impl EthosError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::BadRequest(_) => ErrorKind::BadRequest,
+            Self::NotFound(_) => ErrorKind::NotFound,
+            Self::PayloadTooLarge(_) => ErrorKind::PayloadTooLarge,
+            Self::Unavailable(_) => ErrorKind::Unavailable,
+            Self::Database(e) => ErrorKind::of_sqlx(e),
+            Self::Config(_) | Self::Io(_) | Self::Ipc(_) | Self::Other(_) => ErrorKind::Internal,
+        }
+    }
+}
+
+impl ErrorKind {
+    /// Classify an error by the first cause in its chain that has a known
+    /// kind: an [`EthosError`], a connection-level `sqlx::Error`, or an
+    /// embedding backend that could not be reached. Anything else is
+    /// `Internal`.
+    pub fn of(error: &anyhow::Error) -> Self {
+        use crate::embeddings::EmbeddingError;
+
+        for cause in error.chain() {
+            if let Some(e) = cause.downcast_ref::<EthosError>() {
+                return e.kind();
+            }
+            if let Some(e) = cause.downcast_ref::<sqlx::Error>() {
+                return Self::of_sqlx(e);
+            }
+            if let Some(e) = cause.downcast_ref::<EmbeddingError>() {
+                return match e {
+                    EmbeddingError::Http(_)
+                    | EmbeddingError::RetryExhausted { .. }
+                    | EmbeddingError::CircuitOpen { .. } => Self::Unavailable,
+                    _ => Self::Internal,
+                };
+            }
+        }
+        Self::Internal
+    }
+
+    fn of_sqlx(error: &sqlx::Error) -> Self {
+        match error {
+            sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_) => {
+                Self::Unavailable
+            }
+            _ => Self::Internal,
+        }
+    }
 }
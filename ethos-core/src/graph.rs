@@ -3,13 +3,14 @@
 //! This module implements graph-based retrieval modeled on hippocampal activation:
 //! - Anchor nodes = top-K cosine matches (from Story 006)
 //! - Spreading = iterative activation propagation through `memory_graph_links`
-//! - Final score = weighted combination of similarity + activation + structural scores
+//! - Final score = weighted combination of similarity + activation + structural
+//!   (+ optional retrieval frequency) scores
 
 use crate::config::RetrievalConfig;
 use crate::error::EthosError;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// Maximum number of edges to load for spreading (bounds memory usage)
@@ -24,6 +25,17 @@ pub struct ActivationNode {
     pub spread_score: f32,
     pub structural_score: f32,
     pub final_score: f32,
+    /// Loaded graph edges pointing at this node (0 without spreading)
+    #[serde(default)]
+    pub in_degree: u32,
+    /// Retrieval count normalised against the other anchors, in `[0, 1]`
+    /// (0 for nodes reached only through spreading)
+    #[serde(default)]
+    pub frequency_score: f32,
+    /// Read-time freshness in `[0, 1]`: 1 when just created, 0 at
+    /// `recency_horizon_days` (0 for nodes reached only through spreading)
+    #[serde(default)]
+    pub recency_score: f32,
 }
 
 /// An edge in the memory graph
@@ -43,19 +55,71 @@ pub struct SpreadResult {
     pub edges_loaded: usize,
 }
 
+/// Activation carried across one edge in one spreading iteration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceEdge {
+    pub from_id: Uuid,
+    pub to_id: Uuid,
+    /// 1-based spreading iteration
+    pub iteration: u32,
+    /// Activation added to `to_id` (source activation * weight * spreading
+    /// strength, attenuated by `(1 - decay_factor)^iteration`)
+    pub contribution: f32,
+}
+
+/// A node reached from a seed memory by [`neighbors`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Neighbor {
+    pub id: Uuid,
+    pub node_type: String,
+    /// Hops from the seed along outgoing edges
+    pub depth: u32,
+    /// Node one hop closer to the seed, via the strongest such edge
+    pub via: Uuid,
+    /// Weight of the `via → id` edge
+    pub edge_weight: f32,
+    /// Activation received when spreading from the seed alone
+    pub activation: f32,
+}
+
 /// Core spreading activation algorithm (testable without database)
 ///
 /// # Arguments
 /// * `anchors` - Initial nodes from cosine search with their similarity scores
 /// * `edges` - Graph edges for propagation
-/// * `config` - Retrieval configuration (spreading_strength, iterations, weights)
+/// * `config` - Retrieval configuration (spreading_strength, decay_factor, iterations, weights)
+///
+/// Iteration `n` spreads with strength `spreading_strength * (1 - decay_factor)^n`,
+/// so farther hops contribute less; `decay_factor = 0` keeps strength constant.
 ///
 /// # Returns
-/// * `SpreadResult` - Nodes ranked by combined score
+/// * `SpreadResult` - Nodes ranked by combined score; nodes scoring below
+///   `config.confidence_gate` are left out
 pub fn spread_activation_core(
     anchors: &[ActivationNode],
     edges: &[GraphEdge],
     config: &RetrievalConfig,
+) -> SpreadResult {
+    spread(anchors, edges, config, None)
+}
+
+/// [`spread_activation_core`], also returning every edge traversal with the
+/// activation it contributed, in iteration order. Scores are identical.
+pub fn spread_activation_with_trace(
+    anchors: &[ActivationNode],
+    edges: &[GraphEdge],
+    config: &RetrievalConfig,
+) -> (SpreadResult, Vec<TraceEdge>) {
+    let mut trace = Vec::new();
+    let result = spread(anchors, edges, config, Some(&mut trace));
+    (result, trace)
+}
+
+fn spread(
+    anchors: &[ActivationNode],
+    edges: &[GraphEdge],
+    config: &RetrievalConfig,
+    mut trace: Option<&mut Vec<TraceEdge>>,
 ) -> SpreadResult {
     if anchors.is_empty() {
         return SpreadResult {
@@ -65,12 +129,19 @@ pub fn spread_activation_core(
         };
     }
 
-    // If no edges, return anchors with cosine scores only
+    // If no edges, return anchors with cosine (and frequency) scores only;
+    // the stable sort keeps anchor order unless frequency reorders them
     if edges.is_empty() {
-        let nodes: Vec<ActivationNode> = anchors
+        tracing::debug!(
+            anchors = anchors.len(),
+            "No graph edges around the spreading anchors; ranking by cosine only"
+        );
+        let mut nodes: Vec<ActivationNode> = anchors
             .iter()
             .map(|a| {
-                let final_score = config.weight_similarity * a.cosine_score;
+                let final_score = config.weight_similarity * a.cosine_score
+                    + config.weight_frequency * a.frequency_score
+                    + config.weight_recency * a.recency_score;
                 ActivationNode {
                     id: a.id,
                     node_type: a.node_type.clone(),
@@ -78,9 +149,14 @@ pub fn spread_activation_core(
                     spread_score: 0.0,
                     structural_score: 0.0,
                     final_score,
+                    in_degree: 0,
+                    frequency_score: a.frequency_score,
+                    recency_score: a.recency_score,
                 }
             })
             .collect();
+        apply_confidence_gate(&mut nodes, config.confidence_gate);
+        nodes.sort_by(rank_order);
 
         return SpreadResult {
             nodes,
@@ -109,16 +185,26 @@ pub fn spread_activation_core(
         adjacency.entry(edge.from_id).or_default().push(edge);
     }
 
-    // Iterative spreading activation
-    for _iteration in 0..config.iterations {
+    // Iterative spreading activation; each hop attenuates by (1 - decay_factor)
+    for iteration in 1..=config.iterations {
         let mut new_activation: HashMap<Uuid, f32> = HashMap::new();
+        let hop_strength =
+            config.spreading_strength * (1.0 - config.decay_factor).powi(iteration as i32);
 
         // For each active node
         for (node_id, &node_activation) in &activation {
             // Propagate to neighbors
             if let Some(neighbors) = adjacency.get(node_id) {
                 for edge in neighbors {
-                    let contribution = node_activation * edge.weight * config.spreading_strength;
+                    let contribution = node_activation * edge.weight * hop_strength;
+                    if let Some(trace) = trace.as_deref_mut() {
+                        trace.push(TraceEdge {
+                            from_id: *node_id,
+                            to_id: edge.to_id,
+                            iteration,
+                            contribution,
+                        });
+                    }
                     let current = new_activation.entry(edge.to_id).or_insert(0.0);
                     *current += contribution;
                 }
@@ -132,31 +218,36 @@ pub fn spread_activation_core(
         }
     }
 
-    // Calculate structural scores (in-degree centrality)
+    // Calculate structural scores (in-degree centrality, normalized by the
+    // highest in-degree so the most central node scores 1.0 at any graph size)
     let mut in_degree: HashMap<Uuid, f32> = HashMap::new();
-    let max_in_degree = edges.len() as f32;
 
     for edge in edges {
         let current = in_degree.entry(edge.to_id).or_insert(0.0);
         *current += 1.0;
     }
 
+    let max_in_degree = in_degree.values().copied().fold(0.0, f32::max).max(1.0);
+
     // Build final result nodes
     let mut nodes: Vec<ActivationNode> = Vec::new();
 
     for (id, node_type) in &node_types {
-        let cosine = anchors
+        let (cosine, frequency, recency) = anchors
             .iter()
             .find(|a| &a.id == id)
-            .map(|a| a.cosine_score)
-            .unwrap_or(0.0);
+            .map(|a| (a.cosine_score, a.frequency_score, a.recency_score))
+            .unwrap_or((0.0, 0.0, 0.0));
 
         let spread = activation.get(id).copied().unwrap_or(0.0);
-        let structural = in_degree.get(id).copied().unwrap_or(0.0) / max_in_degree;
+        let degree = in_degree.get(id).copied().unwrap_or(0.0);
+        let structural = degree / max_in_degree;
 
         let final_score = config.weight_similarity * cosine
             + config.weight_activation * spread
-            + config.weight_structural * structural;
+            + config.weight_structural * structural
+            + config.weight_frequency * frequency
+            + config.weight_recency * recency;
 
         nodes.push(ActivationNode {
             id: *id,
@@ -165,15 +256,16 @@ pub fn spread_activation_core(
             spread_score: spread,
             structural_score: structural,
             final_score,
+            in_degree: degree as u32,
+            frequency_score: frequency,
+            recency_score: recency,
         });
     }
 
+    apply_confidence_gate(&mut nodes, config.confidence_gate);
+
     // Sort by final score descending
-    nodes.sort_by(|a, b| {
-        b.final_score
-            .partial_cmp(&a.final_score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    nodes.sort_by(rank_order);
 
     SpreadResult {
         nodes,
@@ -182,6 +274,34 @@ pub fn spread_activation_core(
     }
 }
 
+/// Drop nodes whose combined `final_score` is below `gate`, so weak anchors
+/// and faintly activated neighbors don't pad the ranking. `0` keeps all.
+fn apply_confidence_gate(nodes: &mut Vec<ActivationNode>, gate: f32) {
+    if gate <= 0.0 {
+        return;
+    }
+    let before = nodes.len();
+    nodes.retain(|node| node.final_score >= gate);
+    if nodes.len() < before {
+        tracing::debug!(
+            dropped = before - nodes.len(),
+            confidence_gate = gate,
+            "Dropped spread nodes below the confidence gate"
+        );
+    }
+}
+
+/// Ranking order of spread results: `final_score` descending, then
+/// `cosine_score` descending, then `id`, so tied nodes come back in the same
+/// order whatever order the activation map yielded them in. Retrieval uses
+/// the same order when it re-ranks anchors.
+pub fn rank_order(a: &ActivationNode, b: &ActivationNode) -> std::cmp::Ordering {
+    let descending = |x: f32, y: f32| y.partial_cmp(&x).unwrap_or(std::cmp::Ordering::Equal);
+    descending(a.final_score, b.final_score)
+        .then_with(|| descending(a.cosine_score, b.cosine_score))
+        .then_with(|| a.id.cmp(&b.id))
+}
+
 /// Run spreading activation over the memory graph
 ///
 /// # Arguments
@@ -199,6 +319,7 @@ pub fn spread_activation_core(
 /// 3. Iterate: propagate activation through edges with decay
 /// 4. Calculate structural scores (in-degree centrality)
 /// 5. Combine: final_score = w_sim * cosine + w_act * spread + w_str * structural
+///    (+ w_freq * frequency)
 pub async fn spread_activation(
     pool: &PgPool,
     anchors: &[ActivationNode],
@@ -222,14 +343,184 @@ pub async fn spread_activation(
     Ok(spread_activation_core(anchors, &edges, config))
 }
 
+/// [`spread_activation`] with the edge traversal trace (see [`spread_activation_with_trace`])
+pub async fn spread_activation_traced(
+    pool: &PgPool,
+    anchors: &[ActivationNode],
+    config: &RetrievalConfig,
+) -> Result<(SpreadResult, Vec<TraceEdge>), EthosError> {
+    if anchors.is_empty() {
+        return Ok((
+            SpreadResult {
+                nodes: vec![],
+                iterations: 0,
+                edges_loaded: 0,
+            },
+            vec![],
+        ));
+    }
+
+    let anchor_ids: Vec<Uuid> = anchors.iter().map(|a| a.id).collect();
+    let edges = load_subgraph_edges(pool, &anchor_ids).await?;
+
+    Ok(spread_activation_with_trace(anchors, &edges, config))
+}
+
+/// Neighbors of `seed` up to `depth` hops along outgoing edges (testable
+/// without database).
+///
+/// Spreading runs from the seed alone (a single anchor with cosine 1.0) for
+/// `depth` iterations; neighbors are ranked by the activation they receive.
+/// Each records the strongest edge reaching it from the previous hop.
+pub fn neighbors_core(
+    seed: Uuid,
+    edges: &[GraphEdge],
+    depth: u32,
+    config: &RetrievalConfig,
+) -> Vec<Neighbor> {
+    let mut adjacency: HashMap<Uuid, Vec<&GraphEdge>> = HashMap::new();
+    for edge in edges {
+        adjacency.entry(edge.from_id).or_default().push(edge);
+    }
+
+    // Breadth-first: the first hop that reaches a node fixes its depth
+    let mut reached: HashMap<Uuid, Neighbor> = HashMap::new();
+    let mut frontier = vec![seed];
+    for hop in 1..=depth {
+        let mut next = Vec::new();
+        for node in &frontier {
+            for edge in adjacency.get(node).into_iter().flatten() {
+                if edge.to_id == seed {
+                    continue;
+                }
+                match reached.get_mut(&edge.to_id) {
+                    Some(n) if n.depth == hop && edge.weight > n.edge_weight => {
+                        n.via = *node;
+                        n.edge_weight = edge.weight;
+                    }
+                    Some(_) => {}
+                    None => {
+                        reached.insert(
+                            edge.to_id,
+                            Neighbor {
+                                id: edge.to_id,
+                                node_type: edge.to_type.clone(),
+                                depth: hop,
+                                via: *node,
+                                edge_weight: edge.weight,
+                                activation: 0.0,
+                            },
+                        );
+                        next.push(edge.to_id);
+                    }
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+
+    let seed_anchor = ActivationNode {
+        id: seed,
+        node_type: String::new(),
+        cosine_score: 1.0,
+        spread_score: 0.0,
+        structural_score: 0.0,
+        final_score: 1.0,
+        in_degree: 0,
+        frequency_score: 0.0,
+        recency_score: 0.0,
+    };
+    let spread_config = RetrievalConfig {
+        iterations: depth,
+        ..config.clone()
+    };
+    for node in spread_activation_core(&[seed_anchor], edges, &spread_config).nodes {
+        if let Some(neighbor) = reached.get_mut(&node.id) {
+            neighbor.activation = node.spread_score;
+        }
+    }
+
+    let mut neighbors: Vec<Neighbor> = reached.into_values().collect();
+    neighbors.sort_by(|a, b| {
+        b.activation
+            .total_cmp(&a.activation)
+            .then(b.edge_weight.total_cmp(&a.edge_weight))
+            .then(a.id.cmp(&b.id))
+    });
+    neighbors
+}
+
+/// Explore the memory graph around `seed` without a text query (see
+/// [`neighbors_core`]). Edges are loaded one hop at a time, at most
+/// 500 per hop.
+pub async fn neighbors(
+    pool: &PgPool,
+    seed: Uuid,
+    depth: u32,
+    config: &RetrievalConfig,
+) -> Result<Vec<Neighbor>, EthosError> {
+    let mut edges = Vec::new();
+    let mut seen = HashSet::from([seed]);
+    let mut frontier = vec![seed];
+
+    for _ in 0..depth {
+        if frontier.is_empty() {
+            break;
+        }
+        let loaded = load_outgoing_edges(pool, &frontier).await?;
+        frontier = loaded
+            .iter()
+            .filter(|edge| seen.insert(edge.to_id))
+            .map(|edge| edge.to_id)
+            .collect();
+        edges.extend(loaded);
+    }
+
+    Ok(neighbors_core(seed, &edges, depth, config))
+}
+
+/// Load edges leaving the given node IDs
+async fn load_outgoing_edges(
+    pool: &PgPool,
+    node_ids: &[Uuid],
+) -> Result<Vec<GraphEdge>, EthosError> {
+    let rows = sqlx::query_as::<_, (Uuid, Uuid, String, f32)>(
+        r#"
+        SELECT from_id, to_id, to_type, weight::real
+        FROM memory_graph_links
+        WHERE from_id = ANY($1)
+        ORDER BY weight DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(node_ids)
+    .bind(MAX_EDGES)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(from_id, to_id, to_type, weight)| GraphEdge {
+            from_id,
+            to_id,
+            to_type,
+            weight,
+        })
+        .collect())
+}
+
 /// Load edges from memory_graph_links for the given node IDs
 async fn load_subgraph_edges(
     pool: &PgPool,
     node_ids: &[Uuid],
 ) -> Result<Vec<GraphEdge>, EthosError> {
+    // `weight` is double precision; GraphEdge carries f32
     let rows = sqlx::query_as::<_, (Uuid, Uuid, String, f32)>(
         r#"
-        SELECT from_id, to_id, to_type, weight
+        SELECT from_id, to_id, to_type, weight::real
         FROM memory_graph_links
         WHERE from_id = ANY($1)
            OR to_id = ANY($1)
@@ -262,6 +553,7 @@ async fn load_subgraph_edges(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{DistanceMetric, ScoreTransform};
 
     fn test_config() -> RetrievalConfig {
         RetrievalConfig {
@@ -270,10 +562,24 @@ mod tests {
             iterations: 3,
             anchor_top_k_episodes: 10,
             anchor_top_k_facts: 10,
+            anchor_min_score: 0.0,
             weight_similarity: 0.5,
             weight_activation: 0.3,
             weight_structural: 0.2,
+            weight_frequency: 0.0,
+            weight_recency: 0.0,
+            recency_horizon_days: 30.0,
             confidence_gate: 0.12,
+            min_results: 0,
+            mmr_lambda: 0.5,
+            distance_metric: DistanceMetric::Cosine,
+            retrieval_confidence_gain: 0.04,
+            query_synonyms: Default::default(),
+            max_query_variants: 4,
+            score_transform: ScoreTransform::None,
+            query_timeout_ms: 0,
+            max_search_ms: 0,
+            default_use_spreading: false,
         }
     }
 
@@ -285,6 +591,9 @@ mod tests {
             spread_score: 0.0,
             structural_score: 0.0,
             final_score: 0.0,
+            in_degree: 0,
+            frequency_score: 0.0,
+            recency_score: 0.0,
         }
     }
 
@@ -348,6 +657,7 @@ mod tests {
     fn test_spread_decays_with_strength() {
         let mut config = test_config();
         config.spreading_strength = 0.5; // Lower spreading strength
+        config.decay_factor = 0.0;
 
         let anchor_id = Uuid::new_v4();
         let neighbor_id = Uuid::new_v4();
@@ -507,4 +817,307 @@ mod tests {
         let target_node = result.nodes.iter().find(|n| n.id == target).unwrap();
         assert!((target_node.structural_score - 1.0).abs() < 0.01);
     }
+
+    // ========================================================================
+    // TEST 10: Trace records each edge of a chain with decaying contribution
+    // ========================================================================
+    #[test]
+    fn test_trace_records_chain_edges_with_decreasing_contribution() {
+        let mut config = test_config();
+        config.iterations = 2;
+
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let c = Uuid::new_v4();
+
+        let anchors = vec![make_anchor(a, "episode", 1.0)];
+        let edges = vec![
+            make_edge(a, b, "episode", 0.8),
+            make_edge(b, c, "episode", 0.8),
+        ];
+
+        let (result, trace) = spread_activation_with_trace(&anchors, &edges, &config);
+
+        // Activation reaches C only in the second iteration, via B
+        let first_hop: Vec<&TraceEdge> = trace
+            .iter()
+            .filter(|t| t.from_id == a && t.to_id == b)
+            .collect();
+        let second_hop: Vec<&TraceEdge> = trace
+            .iter()
+            .filter(|t| t.from_id == b && t.to_id == c)
+            .collect();
+        assert_eq!(first_hop.len() + second_hop.len(), trace.len());
+        assert_eq!(second_hop.len(), 1);
+        assert_eq!(second_hop[0].iteration, 2);
+
+        // 1.0 * 0.8 * (0.85 * 0.85) = 0.578, then 0.578 * 0.8 * (0.85 * 0.85^2) = 0.28398
+        assert!((first_hop[0].contribution - 0.578).abs() < 0.001);
+        assert!((second_hop[0].contribution - 0.28398).abs() < 0.001);
+        assert!(second_hop[0].contribution < first_hop[0].contribution);
+
+        // Tracing does not change scoring
+        let untraced = spread_activation_core(&anchors, &edges, &config);
+        let score = |nodes: &[ActivationNode], id: Uuid| {
+            nodes.iter().find(|n| n.id == id).unwrap().final_score
+        };
+        assert_eq!(score(&result.nodes, c), score(&untraced.nodes, c));
+    }
+
+    // ========================================================================
+    // TEST 11: Structural score is relative to the most central node
+    // ========================================================================
+    #[test]
+    fn test_structural_score_independent_of_edge_count() {
+        let config = test_config();
+
+        // Hub with 3 incoming edges, alone or among 100 extra one-edge pairs
+        let structural_scores = |extra_pairs: usize| {
+            let hub = Uuid::new_v4();
+            let leaf = Uuid::new_v4();
+            let anchor = Uuid::new_v4();
+            let mut edges: Vec<GraphEdge> = (0..3)
+                .map(|_| make_edge(Uuid::new_v4(), hub, "fact", 0.5))
+                .collect();
+            edges.push(make_edge(anchor, leaf, "fact", 0.5));
+            for _ in 0..extra_pairs {
+                edges.push(make_edge(Uuid::new_v4(), Uuid::new_v4(), "fact", 0.5));
+            }
+
+            let result =
+                spread_activation_core(&[make_anchor(anchor, "episode", 0.5)], &edges, &config);
+            let structural = |id: Uuid| {
+                result
+                    .nodes
+                    .iter()
+                    .find(|n| n.id == id)
+                    .unwrap()
+                    .structural_score
+            };
+            (edges.len(), structural(hub), structural(leaf))
+        };
+
+        let (small_edges, small_hub, small_leaf) = structural_scores(0);
+        let (large_edges, large_hub, large_leaf) = structural_scores(100);
+        assert!(large_edges > small_edges * 10);
+
+        assert!((small_hub - 1.0).abs() < 1e-6);
+        assert!((large_hub - 1.0).abs() < 1e-6);
+        assert!((small_leaf - 1.0 / 3.0).abs() < 1e-6);
+        assert!((large_leaf - 1.0 / 3.0).abs() < 1e-6);
+    }
+
+    // ========================================================================
+    // TEST 12: neighbors_core records hop depth, strongest via edge, activation
+    // ========================================================================
+    #[test]
+    fn test_neighbors_core_depth_and_via() {
+        let config = test_config();
+        let seed = Uuid::new_v4();
+        let near = Uuid::new_v4();
+        let weak = Uuid::new_v4();
+        let far = Uuid::new_v4();
+        let edges = vec![
+            make_edge(seed, near, "fact", 0.8),
+            make_edge(seed, weak, "episode", 0.2),
+            make_edge(near, far, "fact", 0.6),
+            make_edge(weak, far, "fact", 0.9),
+            make_edge(far, seed, "episode", 1.0),
+        ];
+
+        let one_hop = neighbors_core(seed, &edges, 1, &config);
+        let ids: Vec<Uuid> = one_hop.iter().map(|n| n.id).collect();
+        assert_eq!(ids, vec![near, weak], "ranked by activation, no seed");
+        assert!((one_hop[0].edge_weight - 0.8).abs() < 1e-6);
+        let first_hop_strength = config.spreading_strength * (1.0 - config.decay_factor);
+        assert!((one_hop[0].activation - 0.8 * first_hop_strength).abs() < 1e-6);
+        assert_eq!(one_hop[1].node_type, "episode");
+
+        let two_hops = neighbors_core(seed, &edges, 2, &config);
+        let far_node = two_hops.iter().find(|n| n.id == far).expect("far reached");
+        assert_eq!(far_node.depth, 2);
+        assert_eq!(far_node.via, weak, "strongest edge into the second hop");
+        assert!((far_node.edge_weight - 0.9).abs() < 1e-6);
+        assert!(far_node.activation > 0.0);
+        assert!(two_hops.iter().all(|n| n.id != seed));
+    }
+
+    // ========================================================================
+    // TEST 13: decay_factor attenuates each successive hop of a chain
+    // ========================================================================
+    #[test]
+    fn test_decay_factor_attenuates_per_hop() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let c = Uuid::new_v4();
+        let d = Uuid::new_v4();
+
+        // Unit weights so each hop's contribution reflects only the strength
+        let anchors = vec![make_anchor(a, "episode", 1.0)];
+        let edges = vec![
+            make_edge(a, b, "episode", 1.0),
+            make_edge(b, c, "episode", 1.0),
+            make_edge(c, d, "episode", 1.0),
+        ];
+        let hop_contributions = |decay_factor: f32| {
+            let config = RetrievalConfig {
+                decay_factor,
+                spreading_strength: 1.0,
+                iterations: 3,
+                ..test_config()
+            };
+            let (_, trace) = spread_activation_with_trace(&anchors, &edges, &config);
+            [(a, b, 1), (b, c, 2), (c, d, 3)].map(|(from, to, iteration)| {
+                trace
+                    .iter()
+                    .find(|t| t.from_id == from && t.to_id == to && t.iteration == iteration)
+                    .expect("hop traced")
+                    .contribution
+            })
+        };
+
+        let decayed = hop_contributions(0.15);
+        assert!(decayed[0] > decayed[1]);
+        assert!(decayed[1] > decayed[2]);
+        assert!((decayed[0] - 0.85).abs() < 1e-6);
+
+        // Without decay every hop carries the full activation, as before
+        assert_eq!(hop_contributions(0.0), [1.0, 1.0, 1.0]);
+    }
+
+    // ========================================================================
+    // TEST 14: an empty graph keeps the anchors in their given order
+    // ========================================================================
+    #[test]
+    fn test_spread_empty_graph_preserves_anchor_order() {
+        let config = test_config();
+        let anchors: Vec<ActivationNode> = [0.9, 0.7, 0.4]
+            .into_iter()
+            .map(|cosine| make_anchor(Uuid::new_v4(), "episode", cosine))
+            .collect();
+
+        let result = spread_activation_core(&anchors, &[], &config);
+
+        let ids: Vec<Uuid> = result.nodes.iter().map(|n| n.id).collect();
+        let anchor_ids: Vec<Uuid> = anchors.iter().map(|a| a.id).collect();
+        assert_eq!(ids, anchor_ids);
+        assert_eq!(result.edges_loaded, 0);
+        assert_eq!(result.iterations, 0);
+    }
+
+    // ========================================================================
+    // TEST 15: the frequency term breaks a similarity tie
+    // ========================================================================
+    #[test]
+    fn test_frequency_weight_breaks_similarity_tie() {
+        let mut config = test_config();
+        config.weight_frequency = 0.2;
+        let rare = make_anchor(Uuid::new_v4(), "episode", 0.8);
+        let frequent = ActivationNode {
+            frequency_score: 1.0,
+            ..make_anchor(Uuid::new_v4(), "episode", 0.8)
+        };
+
+        let result = spread_activation_core(&[rare.clone(), frequent.clone()], &[], &config);
+        assert_eq!(result.nodes[0].id, frequent.id);
+        assert!((result.nodes[0].final_score - 0.6).abs() < 1e-6); // 0.8 * 0.5 + 0.2
+
+        let edges = vec![make_edge(rare.id, frequent.id, "episode", 0.5)];
+        let result = spread_activation_core(&[rare, frequent.clone()], &edges, &config);
+        let node = result.nodes.iter().find(|n| n.id == frequent.id).unwrap();
+        assert_eq!(node.frequency_score, 1.0);
+    }
+
+    // ========================================================================
+    // TEST 16: equal-score nodes come back in a deterministic order
+    // ========================================================================
+    #[test]
+    fn test_equal_scores_break_ties_by_cosine_then_id() {
+        let config = test_config();
+
+        // A symmetric cycle: every node gets the same activation and in-degree
+        let mut anchors: Vec<ActivationNode> = (0..5)
+            .map(|_| make_anchor(Uuid::new_v4(), "episode", 0.5))
+            .collect();
+        let edges: Vec<GraphEdge> = (0..anchors.len())
+            .map(|i| {
+                let next = &anchors[(i + 1) % anchors.len()];
+                make_edge(anchors[i].id, next.id, "episode", 0.5)
+            })
+            .collect();
+        let mut sorted_ids: Vec<Uuid> = anchors.iter().map(|a| a.id).collect();
+        sorted_ids.sort();
+
+        for _ in 0..10 {
+            anchors.rotate_left(1);
+            let result = spread_activation_core(&anchors, &edges, &config);
+            let ids: Vec<Uuid> = result.nodes.iter().map(|n| n.id).collect();
+            assert!(result
+                .nodes
+                .windows(2)
+                .all(|w| w[0].final_score == w[1].final_score));
+            assert_eq!(ids, sorted_ids, "ties are ordered by id");
+        }
+
+        // Equal final scores, different similarity: the closer match first
+        let mut config = test_config();
+        config.weight_frequency = 0.25;
+        let similar = make_anchor(Uuid::max(), "episode", 0.5);
+        let frequent = ActivationNode {
+            frequency_score: 1.0,
+            ..make_anchor(Uuid::nil(), "episode", 0.0)
+        };
+        let result = spread_activation_core(&[frequent, similar.clone()], &[], &config);
+        assert_eq!(result.nodes[0].final_score, result.nodes[1].final_score);
+        assert_eq!(result.nodes[0].id, similar.id);
+    }
+
+    // ========================================================================
+    // TEST 17: nodes scoring below confidence_gate are dropped
+    // ========================================================================
+    #[test]
+    fn test_confidence_gate_drops_sub_gate_nodes() {
+        let config = test_config();
+        let strong = make_anchor(Uuid::new_v4(), "episode", 0.9);
+        let weak: Vec<ActivationNode> = (0..2)
+            .map(|_| make_anchor(Uuid::new_v4(), "episode", 0.1))
+            .collect();
+        let (hub, faint) = (Uuid::new_v4(), Uuid::new_v4());
+
+        // Without edges a weak anchor scores 0.5 * 0.1 = 0.05
+        let result = spread_activation_core(&[strong.clone(), weak[0].clone()], &[], &config);
+        let ids: Vec<Uuid> = result.nodes.iter().map(|n| n.id).collect();
+        assert_eq!(ids, vec![strong.id]);
+
+        // Every anchor feeds the hub; the faint node gets a trickle from the
+        // strong anchor and a third of the hub's in-degree (about 0.07)
+        let mut edges: Vec<GraphEdge> = [&strong, &weak[0], &weak[1]]
+            .iter()
+            .map(|a| make_edge(a.id, hub, "fact", 1.0))
+            .collect();
+        edges.push(make_edge(strong.id, faint, "fact", 0.01));
+        let mut anchors = vec![strong.clone()];
+        anchors.extend(weak.iter().cloned());
+
+        let result = spread_activation_core(&anchors, &edges, &config);
+        let mut ids: Vec<Uuid> = result.nodes.iter().map(|n| n.id).collect();
+        ids.sort();
+        let mut expected = vec![strong.id, hub];
+        expected.sort();
+        assert_eq!(ids, expected);
+        assert!(result
+            .nodes
+            .iter()
+            .all(|n| n.final_score >= config.confidence_gate));
+
+        // A gate of 0 keeps every node
+        let ungated = RetrievalConfig {
+            confidence_gate: 0.0,
+            ..test_config()
+        };
+        let result = spread_activation_core(&anchors, &edges, &ungated);
+        assert_eq!(result.nodes.len(), 5);
+        let faint_node = result.nodes.iter().find(|n| n.id == faint).unwrap();
+        assert!(faint_node.final_score < config.confidence_gate);
+    }
 }
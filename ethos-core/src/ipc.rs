@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::error::ErrorKind;
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "action", rename_all = "snake_case")]
 pub enum EthosRequest {
@@ -8,17 +10,67 @@ pub enum EthosRequest {
     Ingest {
         payload: serde_json::Value,
     },
+    /// Ingest many payloads in one transaction; failures are reported per item
+    IngestBatch {
+        payloads: Vec<serde_json::Value>,
+    },
     Search {
         query: String,
         limit: Option<u32>,
         #[serde(default)]
-        use_spreading: bool,
+        offset: Option<u32>,
+        /// Unset uses `[retrieval] default_use_spreading`
+        #[serde(default)]
+        use_spreading: Option<bool>,
+        #[serde(default)]
+        diversify: bool,
+        /// Collapse chunks of one ingested document into its best-scoring chunk
+        #[serde(default, alias = "groupByDocument")]
+        group_by_document: bool,
+        /// Attach an `explanation` of each result's ranking
+        #[serde(default)]
+        explain: bool,
+        /// Include the spreading-activation edge trace in the response
+        #[serde(default)]
+        trace: bool,
+        /// Also search synonym and plural/singular variants of the query
+        #[serde(default)]
+        expand: bool,
+        /// Embed the query with this task type instead of `RETRIEVAL_QUERY`
+        /// (ignored by backends without task types, e.g. ONNX)
+        #[serde(default, alias = "taskType")]
+        task_type: Option<crate::embeddings::TaskType>,
+        /// Compare only the first N vector dimensions (Matryoshka truncation)
+        #[serde(default, alias = "searchDimensions")]
+        search_dimensions: Option<u32>,
+        /// Attach the facts consolidated from each episode result
+        #[serde(default, alias = "includeRelatedFacts")]
+        include_related_facts: bool,
         #[serde(default, alias = "resourceId")]
         resource_id: Option<String>,
         #[serde(default, alias = "threadId")]
         thread_id: Option<String>,
         #[serde(default, alias = "agentId")]
         agent_id: Option<String>,
+        #[serde(default, alias = "minScore")]
+        min_score: Option<f64>,
+        #[serde(default, alias = "sourceFilter")]
+        source_filter: Option<Vec<String>>,
+        #[serde(default, alias = "metadataContains")]
+        metadata_contains: Option<serde_json::Value>,
+        /// Only memories created at or after this instant
+        #[serde(default, alias = "createdAfter")]
+        created_after: Option<chrono::DateTime<chrono::Utc>>,
+        /// Only memories created at or before this instant
+        #[serde(default, alias = "createdBefore")]
+        created_before: Option<chrono::DateTime<chrono::Utc>>,
+        /// Only memories whose source episode or fact shares one of these
+        /// topics; with an empty `query`, lists them by salience instead
+        #[serde(default)]
+        topics: Option<Vec<String>>,
+        /// Admin/forensics: also return pruned memories, tagged `pruned: true`
+        #[serde(default, alias = "includePruned")]
+        include_pruned: bool,
     },
     Get {
         id: uuid::Uuid,
@@ -27,9 +79,165 @@ pub enum EthosRequest {
         session: Option<String>,
         reason: Option<String>,
     },
+    /// Run a decay sweep now; `dry_run` reports without writing
+    Decay {
+        #[serde(default)]
+        dry_run: bool,
+    },
     Embed {
         id: uuid::Uuid,
     },
+    /// Dry-run view of promotion criteria for unconsolidated episodes
+    ConsolidationCandidates {
+        session: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Facts the next consolidation batch would extract, without writing
+    ConsolidationPreview,
+    /// The background loop's most recent consolidation reports, newest first
+    ConsolidationHistory,
+    /// Active (unpruned, unsuperseded) facts, optionally for one subject or
+    /// only those flagged for review
+    ListFacts {
+        #[serde(default)]
+        subject: Option<String>,
+        #[serde(default)]
+        flagged: bool,
+        #[serde(default)]
+        limit: Option<u32>,
+    },
+    /// The episodes a fact was consolidated from, oldest first
+    FactSources {
+        #[serde(alias = "factId")]
+        fact_id: uuid::Uuid,
+    },
+    /// Manually correct a fact; clears its review flag
+    EditFact {
+        #[serde(alias = "factId")]
+        fact_id: uuid::Uuid,
+        edit: FactEdit,
+    },
+    ResolveConflict {
+        #[serde(alias = "factId")]
+        fact_id: uuid::Uuid,
+        /// Serialized as `resolution` because `action` is the request tag
+        #[serde(rename = "resolution")]
+        action: ConflictAction,
+    },
+    /// Forget a memory: soft-delete (`pruned`) unless `hard`, and drop its graph edges
+    Delete {
+        id: uuid::Uuid,
+        #[serde(default)]
+        hard: bool,
+    },
+    /// Sessions with their event counts and consolidation progress, most
+    /// recently active first
+    ListSessions {
+        #[serde(default, alias = "agentId")]
+        agent_id: Option<String>,
+        #[serde(default, alias = "activeSince")]
+        active_since: Option<chrono::DateTime<chrono::Utc>>,
+        #[serde(default)]
+        limit: Option<u32>,
+    },
+    /// Memories changed at or after `since`, least recently changed first,
+    /// independent of similarity; pass the previous page's `next_cursor` to continue
+    ListMemories {
+        #[serde(default)]
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        #[serde(default)]
+        cursor: Option<String>,
+        #[serde(default)]
+        limit: Option<u32>,
+    },
+    /// Hard-delete rows pruned more than `retention_days` ago (default
+    /// `[decay] gc_retention_days`) and the graph edges pointing at them
+    Gc {
+        #[serde(default)]
+        retention_days: Option<u32>,
+    },
+    /// Pin (or unpin) a memory so decay never drops it below `min_salience_floor`
+    PinMemory {
+        id: uuid::Uuid,
+        pinned: bool,
+    },
+    /// Memories linked to `id` up to `depth` hops, ranked by the activation
+    /// spreading from `id` alone gives them
+    Neighbors {
+        id: uuid::Uuid,
+        #[serde(default)]
+        depth: Option<u32>,
+        #[serde(default)]
+        limit: Option<u32>,
+    },
+    /// Swap the active embedding backend ("gemini" | "onnx" | "gemini-fallback-onnx")
+    /// without restarting; the current backend stays in place if the new one
+    /// cannot be constructed
+    SetBackend {
+        backend: String,
+    },
+}
+
+/// Resolution applied to a fact that was flagged for review.
+///
+/// The review inbox actions map onto this: `keep-old` discards the new fact,
+/// `keep-new` supersedes the old fact with the new one, `keep-both` keeps both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictAction {
+    /// Keep the fact and clear its review flag
+    Keep,
+    /// Prune the fact
+    Discard,
+    /// Mark the fact as superseded by another fact
+    SupersedeWith(uuid::Uuid),
+}
+
+/// Fields of a fact to overwrite by hand; unset fields are left alone.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FactEdit {
+    #[serde(default)]
+    pub statement: Option<String>,
+    #[serde(default)]
+    pub object: Option<String>,
+    /// Clamped to `[0, 1]`
+    #[serde(default)]
+    pub confidence: Option<f64>,
+    #[serde(default)]
+    pub topics: Option<Vec<String>>,
+}
+
+impl FactEdit {
+    /// Trim the text fields and clamp `confidence`, or explain why the edit
+    /// is invalid (nothing to change, blank text, non-finite confidence).
+    pub fn validate(self) -> Result<Self, String> {
+        if self == Self::default() {
+            return Err("at least one of statement, object, confidence, topics is required".into());
+        }
+        let text = |field: &str, value: Option<String>| match value {
+            Some(v) if v.trim().is_empty() => Err(format!("{} must not be empty", field)),
+            v => Ok(v.map(|v| v.trim().to_string())),
+        };
+        let confidence = match self.confidence {
+            Some(c) if !c.is_finite() => return Err("confidence must be a number".into()),
+            c => c.map(|c| c.clamp(0.0, 1.0)),
+        };
+        let topics = self.topics.map(|topics| {
+            topics
+                .into_iter()
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect()
+        });
+
+        Ok(Self {
+            statement: text("statement", self.statement)?,
+            object: text("object", self.object)?,
+            confidence,
+            topics,
+        })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -38,6 +246,9 @@ pub struct EthosResponse {
     pub status: String,
     pub data: Option<serde_json::Value>,
     pub error: Option<String>,
+    /// Classification of `error`; absent on success
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<ErrorKind>,
     pub version: String,
 }
 
@@ -47,19 +258,31 @@ impl EthosResponse {
             status: "ok".to_string(),
             data: Some(data),
             error: None,
+            error_kind: None,
             version: "0.1.0".to_string(),
         }
     }
 
     pub fn err(msg: impl Into<String>) -> Self {
+        Self::err_kind(ErrorKind::Internal, msg)
+    }
+
+    pub fn err_kind(kind: ErrorKind, msg: impl Into<String>) -> Self {
         Self {
             status: "error".to_string(),
             data: None,
             error: Some(msg.into()),
+            error_kind: Some(kind),
             version: "0.1.0".to_string(),
         }
     }
 
+    /// Error response for a failed subsystem call, classified by
+    /// [`ErrorKind::of`]. The message includes the error's causes.
+    pub fn from_error(error: &anyhow::Error) -> Self {
+        Self::err_kind(ErrorKind::of(error), format!("{:#}", error))
+    }
+
     pub fn pong() -> Self {
         Self::ok(serde_json::json!({"pong": true}))
     }
@@ -67,7 +290,7 @@ impl EthosResponse {
 
 #[cfg(test)]
 mod tests {
-    use super::EthosRequest;
+    use super::{ConflictAction, EthosRequest, FactEdit};
 
     #[test]
     fn test_search_request_deserializes_scope_filters_in_snake_and_camel_case() {
@@ -120,4 +343,62 @@ mod tests {
             other => panic!("unexpected request variant: {other:?}"),
         }
     }
+
+    #[test]
+    fn test_resolve_conflict_request_deserializes_each_action() {
+        let other_id = uuid::Uuid::new_v4();
+        let cases = [
+            (serde_json::json!("keep"), ConflictAction::Keep),
+            (serde_json::json!("discard"), ConflictAction::Discard),
+            (
+                serde_json::json!({ "supersede_with": other_id }),
+                ConflictAction::SupersedeWith(other_id),
+            ),
+        ];
+
+        for (resolution, expected) in cases {
+            let fact_id = uuid::Uuid::new_v4();
+            let json = serde_json::json!({
+                "action": "resolve_conflict",
+                "fact_id": fact_id,
+                "resolution": resolution,
+            });
+
+            match serde_json::from_value(json).expect("resolve_conflict should deserialize") {
+                EthosRequest::ResolveConflict {
+                    fact_id: parsed_id,
+                    action,
+                } => {
+                    assert_eq!(parsed_id, fact_id);
+                    assert_eq!(action, expected);
+                }
+                other => panic!("unexpected request variant: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_fact_edit_validates_and_rejects_unknown_fields() {
+        let edit: FactEdit = serde_json::from_value(serde_json::json!({
+            "statement": "  Team uses Postgres  ",
+            "confidence": 1.7,
+            "topics": ["db", " "]
+        }))
+        .expect("known fields deserialize");
+        let edit = edit.validate().expect("valid edit");
+        assert_eq!(edit.statement.as_deref(), Some("Team uses Postgres"));
+        assert_eq!(edit.confidence, Some(1.0));
+        assert_eq!(edit.topics, Some(vec!["db".to_string()]));
+        assert_eq!(edit.object, None);
+
+        let unknown = serde_json::from_value::<FactEdit>(serde_json::json!({ "subject": "x" }));
+        assert!(unknown.unwrap_err().to_string().contains("unknown field"));
+
+        assert!(FactEdit::default().validate().is_err());
+        let blank = FactEdit {
+            object: Some("  ".into()),
+            ..Default::default()
+        };
+        assert_eq!(blank.validate().unwrap_err(), "object must not be empty");
+    }
 }
@@ -1,3 +1,4 @@
+pub mod compression;
 pub mod config;
 pub mod db;
 pub mod embeddings;
@@ -12,6 +13,6 @@ pub use embeddings::{
     create_backend, BackendConfig, EmbeddingBackend, EmbeddingConfig, EmbeddingError,
     FallbackEmbeddingClient, GeminiEmbeddingClient, OnnxConfig, GEMINI_DIMENSIONS, ONNX_DIMENSIONS,
 };
-pub use error::EthosError;
+pub use error::{ErrorKind, EthosError};
 pub use graph::{ActivationNode, SpreadResult};
 pub use onnx_embedder::OnnxEmbeddingClient;
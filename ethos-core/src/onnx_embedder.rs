@@ -6,16 +6,20 @@
 use async_trait::async_trait;
 use ort::session::Session;
 use ort::value::Tensor;
-use std::path::PathBuf;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
-use crate::embeddings::{EmbeddingBackend, EmbeddingError, OnnxConfig};
+use crate::embeddings::{
+    l2_normalize, EmbeddingBackend, EmbeddingError, OnnxConfig, ONNX_MAX_SEQ_LEN,
+};
 
 /// Local ONNX embedding client using `all-MiniLM-L6-v2`.
 pub struct OnnxEmbeddingClient {
     session: Arc<Mutex<Session>>,
     tokenizer: Arc<tokenizers::Tokenizer>,
     dimensions: usize,
+    normalize: bool,
 }
 
 impl std::fmt::Debug for OnnxEmbeddingClient {
@@ -30,7 +34,9 @@ impl OnnxEmbeddingClient {
     /// Create a new ONNX embedding client.
     ///
     /// Loads the ONNX model and tokenizer from the paths specified in `config`.
-    /// Returns `EmbeddingError::ModelNotFound` if either file is missing.
+    /// Returns `EmbeddingError::ModelNotFound` if either file is missing, and
+    /// `EmbeddingError::ModelCorrupt` if the model doesn't match
+    /// `expected_sha256`.
     pub fn new(config: OnnxConfig) -> Result<Self, EmbeddingError> {
         if !config.model_path.exists() {
             return Err(EmbeddingError::ModelNotFound {
@@ -42,19 +48,33 @@ impl OnnxEmbeddingClient {
                 path: config.tokenizer_path.display().to_string(),
             });
         }
+        if let Some(expected) = &config.expected_sha256 {
+            verify_model_sha256(&config.model_path, expected)?;
+        }
 
         let session = Session::builder()
-            .and_then(|b| b.with_intra_threads(1))
+            .and_then(|b| b.with_intra_threads(config.intra_threads))
+            .and_then(|b| b.with_parallel_execution(config.inter_threads > 1))
+            .and_then(|b| b.with_inter_threads(config.inter_threads))
             .and_then(|b| b.commit_from_file(&config.model_path))
             .map_err(|e| EmbeddingError::OnnxInference(e.to_string()))?;
 
-        let tokenizer = tokenizers::Tokenizer::from_file(&config.tokenizer_path)
+        let mut tokenizer = tokenizers::Tokenizer::from_file(&config.tokenizer_path)
+            .map_err(|e| EmbeddingError::Tokenizer(e.to_string()))?;
+
+        // Long input would overflow the model's position embeddings; keep the head
+        tokenizer
+            .with_truncation(Some(tokenizers::TruncationParams {
+                max_length: ONNX_MAX_SEQ_LEN,
+                ..Default::default()
+            }))
             .map_err(|e| EmbeddingError::Tokenizer(e.to_string()))?;
 
         Ok(Self {
             session: Arc::new(Mutex::new(session)),
             tokenizer: Arc::new(tokenizer),
             dimensions: config.dimensions,
+            normalize: config.normalize,
         })
     }
 }
@@ -66,13 +86,14 @@ impl EmbeddingBackend for OnnxEmbeddingClient {
         let session = Arc::clone(&self.session);
         let tokenizer = Arc::clone(&self.tokenizer);
         let dimensions = self.dimensions;
+        let normalize = self.normalize;
         let text = text.to_string();
 
         let result = tokio::task::spawn_blocking(move || {
             let mut session_guard = session.lock().map_err(|e| {
                 EmbeddingError::OnnxInference(format!("session lock poisoned: {e}"))
             })?;
-            embed_sync(&mut session_guard, &tokenizer, &text, dimensions)
+            embed_sync(&mut session_guard, &tokenizer, &text, dimensions, normalize)
         })
         .await
         .map_err(|e| EmbeddingError::OnnxInference(format!("spawn_blocking join error: {e}")))?;
@@ -80,6 +101,32 @@ impl EmbeddingBackend for OnnxEmbeddingClient {
         result.map(Some)
     }
 
+    async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Option<Vec<f32>>>, EmbeddingError> {
+        let session = Arc::clone(&self.session);
+        let tokenizer = Arc::clone(&self.tokenizer);
+        let dimensions = self.dimensions;
+        let normalize = self.normalize;
+        let texts: Vec<String> = texts.iter().map(|t| t.to_string()).collect();
+
+        let result = tokio::task::spawn_blocking(move || {
+            let mut session_guard = session.lock().map_err(|e| {
+                EmbeddingError::OnnxInference(format!("session lock poisoned: {e}"))
+            })?;
+            let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+            embed_batch_sync(
+                &mut session_guard,
+                &tokenizer,
+                &texts,
+                dimensions,
+                normalize,
+            )
+        })
+        .await
+        .map_err(|e| EmbeddingError::OnnxInference(format!("spawn_blocking join error: {e}")))?;
+
+        result.map(|embeddings| embeddings.into_iter().map(Some).collect())
+    }
+
     fn dimensions(&self) -> usize {
         self.dimensions
     }
@@ -89,30 +136,60 @@ impl EmbeddingBackend for OnnxEmbeddingClient {
     }
 }
 
-/// Run ONNX inference synchronously.
+/// Run ONNX inference synchronously for a single text.
 fn embed_sync(
     session: &mut Session,
     tokenizer: &tokenizers::Tokenizer,
     text: &str,
     expected_dims: usize,
+    normalize: bool,
 ) -> Result<Vec<f32>, EmbeddingError> {
-    // 1. Tokenize
-    let encoding = tokenizer
-        .encode(text, true)
+    let mut embeddings = embed_batch_sync(session, tokenizer, &[text], expected_dims, normalize)?;
+    Ok(embeddings.remove(0))
+}
+
+/// Run ONNX inference synchronously over a batch of texts in one forward pass.
+///
+/// Encodings are right-padded to the longest in the batch; padding is masked
+/// out of the mean-pool, so each vector matches an individual embed.
+fn embed_batch_sync(
+    session: &mut Session,
+    tokenizer: &tokenizers::Tokenizer,
+    texts: &[&str],
+    expected_dims: usize,
+    normalize: bool,
+) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+    if texts.is_empty() {
+        return Ok(vec![]);
+    }
+
+    // 1. Tokenize and pad to a common length
+    let encodings = tokenizer
+        .encode_batch(texts.to_vec(), true)
         .map_err(|e| EmbeddingError::Tokenizer(e.to_string()))?;
 
-    let input_ids: Vec<i64> = encoding.get_ids().iter().map(|&id| id as i64).collect();
-    let attention_mask: Vec<i64> = encoding
-        .get_attention_mask()
-        .iter()
-        .map(|&m| m as i64)
-        .collect();
-    let token_type_ids: Vec<i64> = encoding.get_type_ids().iter().map(|&t| t as i64).collect();
+    let batch_size = encodings.len();
+    let seq_len = encodings.iter().map(|e| e.len()).max().unwrap_or(0);
 
-    let seq_len = input_ids.len();
-    let shape = vec![1i64, seq_len as i64];
+    let mut input_ids = vec![0i64; batch_size * seq_len];
+    let mut attention_mask = vec![0i64; batch_size * seq_len];
+    let mut token_type_ids = vec![0i64; batch_size * seq_len];
+    for (row, encoding) in encodings.iter().enumerate() {
+        let offset = row * seq_len;
+        for (tok_idx, &id) in encoding.get_ids().iter().enumerate() {
+            input_ids[offset + tok_idx] = id as i64;
+        }
+        for (tok_idx, &m) in encoding.get_attention_mask().iter().enumerate() {
+            attention_mask[offset + tok_idx] = m as i64;
+        }
+        for (tok_idx, &t) in encoding.get_type_ids().iter().enumerate() {
+            token_type_ids[offset + tok_idx] = t as i64;
+        }
+    }
+
+    let shape = vec![batch_size as i64, seq_len as i64];
 
-    // 2. Build input tensors via Tensor::from_array (batch_size=1)
+    // 2. Build input tensors via Tensor::from_array
     let input_ids_tensor = Tensor::from_array((shape.clone(), input_ids))
         .map_err(|e| EmbeddingError::OnnxInference(e.to_string()))?;
     let attention_mask_tensor = Tensor::from_array((shape.clone(), attention_mask.clone()))
@@ -138,55 +215,66 @@ fn embed_sync(
         .try_extract_tensor::<f32>()
         .map_err(|e| EmbeddingError::OnnxInference(e.to_string()))?;
 
-    // Expected shape: [1, seq_len, hidden_dim]
+    // Expected shape: [batch_size, seq_len, hidden_dim]
     if out_shape.len() != 3 {
         return Err(EmbeddingError::OnnxInference(format!(
             "Expected 3D output, got {}D",
             out_shape.len()
         )));
     }
+    if out_shape[0] as usize != batch_size {
+        return Err(EmbeddingError::OnnxInference(format!(
+            "Expected batch of {batch_size}, got {}",
+            out_shape[0]
+        )));
+    }
     let out_seq_len = out_shape[1] as usize;
     let hidden_dim = out_shape[2] as usize;
 
-    // 5. Mean-pool over sequence length, masked by attention_mask
-    let mut pooled = vec![0.0f32; hidden_dim];
-    let mask_sum: f32 = attention_mask.iter().map(|&m| m as f32).sum();
-
-    for tok_idx in 0..out_seq_len {
-        let mask_val = if tok_idx < attention_mask.len() {
-            attention_mask[tok_idx] as f32
-        } else {
-            0.0
-        };
-        if mask_val > 0.0 {
-            let offset = tok_idx * hidden_dim; // flat index into [1, seq_len, hidden_dim]
-            for dim in 0..hidden_dim {
-                pooled[dim] += data[offset + dim] * mask_val;
+    let mut embeddings = Vec::with_capacity(batch_size);
+    for row in 0..batch_size {
+        let mask = &attention_mask[row * seq_len..(row + 1) * seq_len];
+
+        // 5. Mean-pool over sequence length, masked by attention_mask
+        let mut pooled = vec![0.0f32; hidden_dim];
+        let mask_sum: f32 = mask.iter().map(|&m| m as f32).sum();
+
+        for tok_idx in 0..out_seq_len {
+            let mask_val = if tok_idx < mask.len() {
+                mask[tok_idx] as f32
+            } else {
+                0.0
+            };
+            if mask_val > 0.0 {
+                // flat index into [batch_size, seq_len, hidden_dim]
+                let offset = (row * out_seq_len + tok_idx) * hidden_dim;
+                for dim in 0..hidden_dim {
+                    pooled[dim] += data[offset + dim] * mask_val;
+                }
             }
         }
-    }
-    if mask_sum > 0.0 {
-        for v in &mut pooled {
-            *v /= mask_sum;
+        if mask_sum > 0.0 {
+            for v in &mut pooled {
+                *v /= mask_sum;
+            }
         }
-    }
 
-    // 6. L2 normalize
-    let norm: f32 = pooled.iter().map(|x| x * x).sum::<f32>().sqrt();
-    if norm > 0.0 {
-        for v in &mut pooled {
-            *v /= norm;
+        // 6. L2 normalize
+        if normalize {
+            l2_normalize(&mut pooled);
         }
-    }
 
-    if pooled.len() != expected_dims {
-        return Err(EmbeddingError::InvalidDimensions {
-            expected: expected_dims,
-            actual: pooled.len(),
-        });
+        if pooled.len() != expected_dims {
+            return Err(EmbeddingError::InvalidDimensions {
+                expected: expected_dims,
+                actual: pooled.len(),
+            });
+        }
+
+        embeddings.push(pooled);
     }
 
-    Ok(pooled)
+    Ok(embeddings)
 }
 
 /// Resolve the default model directory.
@@ -200,6 +288,34 @@ pub fn default_model_dir() -> PathBuf {
     data_home.join("ethos/models")
 }
 
+/// Check that the file at `path` hashes to `expected` (hex SHA-256, any case).
+pub fn verify_model_sha256(path: &Path, expected: &str) -> Result<(), EmbeddingError> {
+    let mut file = std::fs::File::open(path).map_err(|_| EmbeddingError::ModelNotFound {
+        path: path.display().to_string(),
+    })?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).map_err(|e| EmbeddingError::ModelCorrupt {
+        path: path.display().to_string(),
+        expected: expected.to_string(),
+        actual: format!("unreadable: {e}"),
+    })?;
+    let actual: String = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+
+    if actual.eq_ignore_ascii_case(expected.trim()) {
+        Ok(())
+    } else {
+        Err(EmbeddingError::ModelCorrupt {
+            path: path.display().to_string(),
+            expected: expected.trim().to_lowercase(),
+            actual,
+        })
+    }
+}
+
 /// Resolve paths for the ONNX model and tokenizer.
 ///
 /// If `onnx_model_path` from config is empty, uses the default location.
@@ -236,7 +352,11 @@ mod tests {
         let config = OnnxConfig {
             model_path: PathBuf::from("/nonexistent/model.onnx"),
             tokenizer_path: PathBuf::from("/nonexistent/tokenizer.json"),
+            expected_sha256: None,
             dimensions: ONNX_DIMENSIONS,
+            normalize: true,
+            intra_threads: 1,
+            inter_threads: 1,
         };
 
         let result = OnnxEmbeddingClient::new(config);
@@ -249,6 +369,109 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    #[ignore = "requires the ONNX model (scripts/download-onnx-model.sh)"]
+    async fn test_embed_batch_matches_individual_embeds() {
+        let (model_path, tokenizer_path) = resolve_onnx_paths("");
+        let client = OnnxEmbeddingClient::new(OnnxConfig {
+            model_path,
+            tokenizer_path,
+            expected_sha256: None,
+            dimensions: ONNX_DIMENSIONS,
+            normalize: true,
+            intra_threads: 1,
+            inter_threads: 1,
+        })
+        .expect("ONNX model installed");
+
+        // Different lengths so the shorter inputs are padded in the batch
+        let texts = [
+            "Rust",
+            "The quick brown fox jumps over the lazy dog",
+            "Spreading activation retrieves memories associated with the anchors of a query",
+        ];
+        let batched = client.embed_batch(&texts).await.unwrap();
+        assert_eq!(batched.len(), texts.len());
+
+        for (text, batch_vec) in texts.iter().zip(batched) {
+            let batch_vec = batch_vec.expect("batched embedding");
+            let single_vec = client.embed(text).await.unwrap().expect("embedding");
+            assert_eq!(batch_vec.len(), single_vec.len());
+            for (b, s) in batch_vec.iter().zip(&single_vec) {
+                assert!((b - s).abs() < 1e-4, "{text:?}: batched {b} vs single {s}");
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires the ONNX model (scripts/download-onnx-model.sh)"]
+    async fn test_thread_counts_build_session_and_embed_identically() {
+        let client = |intra_threads, inter_threads| {
+            let (model_path, tokenizer_path) = resolve_onnx_paths("");
+            OnnxEmbeddingClient::new(OnnxConfig {
+                model_path,
+                tokenizer_path,
+                expected_sha256: None,
+                dimensions: ONNX_DIMENSIONS,
+                normalize: true,
+                intra_threads,
+                inter_threads,
+            })
+            .expect("session builds with explicit thread counts")
+        };
+        let single = client(1, 1);
+        let threaded = client(2, 2);
+
+        let text = "Spreading activation retrieves memories associated with a query";
+        let expected = single.embed(text).await.unwrap().expect("embedding");
+        let actual = threaded.embed(text).await.unwrap().expect("embedding");
+        assert_eq!(actual.len(), ONNX_DIMENSIONS);
+        for (a, e) in actual.iter().zip(&expected) {
+            assert!((a - e).abs() < 1e-4, "threaded {a} vs single-threaded {e}");
+        }
+    }
+
+    #[test]
+    fn test_tampered_model_returns_corrupt_error() {
+        let dir = std::env::temp_dir().join(format!("ethos-onnx-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let model_path = dir.join("model.onnx");
+        let tokenizer_path = dir.join("model-tokenizer.json");
+        std::fs::write(&model_path, b"model bytes").unwrap();
+        std::fs::write(&tokenizer_path, b"{}").unwrap();
+        let actual: String = Sha256::digest(b"model bytes")
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        assert!(verify_model_sha256(&model_path, &actual.to_uppercase()).is_ok());
+
+        // A truncated download no longer matches the recorded hash
+        std::fs::write(&model_path, b"model by").unwrap();
+        let result = OnnxEmbeddingClient::new(OnnxConfig {
+            model_path: model_path.clone(),
+            tokenizer_path,
+            expected_sha256: Some(actual.clone()),
+            dimensions: ONNX_DIMENSIONS,
+            normalize: true,
+            intra_threads: 1,
+            inter_threads: 1,
+        });
+        std::fs::remove_dir_all(&dir).ok();
+
+        match result.unwrap_err() {
+            EmbeddingError::ModelCorrupt {
+                path,
+                expected,
+                actual: found,
+            } => {
+                assert_eq!(path, model_path.display().to_string());
+                assert_eq!(expected, actual);
+                assert_ne!(found, actual);
+            }
+            other => panic!("Expected ModelCorrupt, got: {other:?}"),
+        }
+    }
+
     #[test]
     fn test_default_model_dir_contains_ethos() {
         let dir = default_model_dir();
@@ -0,0 +1,29 @@
+//! Embeds build info reported by `GET /version`: the git commit
+//! (`ETHOS_GIT_SHA`) and build time (`ETHOS_BUILD_TIMESTAMP`). Either can be
+//! set in the environment instead, e.g. for builds outside a git checkout.
+
+use std::process::Command;
+
+fn main() {
+    let git_sha = std::env::var("ETHOS_GIT_SHA")
+        .ok()
+        .or_else(|| {
+            Command::new("git")
+                .args(["rev-parse", "--short=12", "HEAD"])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let build_timestamp = std::env::var("ETHOS_BUILD_TIMESTAMP")
+        .unwrap_or_else(|_| chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+
+    println!("cargo:rustc-env=ETHOS_GIT_SHA={git_sha}");
+    println!("cargo:rustc-env=ETHOS_BUILD_TIMESTAMP={build_timestamp}");
+    println!("cargo:rerun-if-env-changed=ETHOS_GIT_SHA");
+    println!("cargo:rerun-if-env-changed=ETHOS_BUILD_TIMESTAMP");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/refs");
+}
@@ -0,0 +1,177 @@
+//! Audit logs: one JSONL record per search (`[logging] audit_searches`) and
+//! one per row pruned by decay (`[decay] audit_prunes`)
+//!
+//! The search log answers "why didn't my memory surface" after the fact: each
+//! record holds the query, its paging/spreading options, and the ids it
+//! returned. Records are queued on a bounded channel and appended by a
+//! background task, so a slow disk never delays the search response; when the
+//! queue is full the record is dropped with a warning.
+//!
+//! The prune log answers "why did my memory vanish": each decay sweep appends
+//! the rows it pruned with their final salience and the reason.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Records waiting to be written before new ones are dropped.
+const AUDIT_QUEUE_CAPACITY: usize = 1024;
+
+/// One audited search, serialized as a single JSON line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchAuditRecord {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub query: String,
+    pub limit: Option<u32>,
+    pub use_spreading: bool,
+    pub result_ids: Vec<Uuid>,
+    pub took_ms: u64,
+}
+
+impl SearchAuditRecord {
+    /// Record for a search response; ids are read from `results[].id`.
+    pub fn from_response(
+        query: String,
+        limit: Option<u32>,
+        use_spreading: bool,
+        response: &serde_json::Value,
+        took_ms: u64,
+    ) -> Self {
+        let result_ids = response["results"]
+            .as_array()
+            .map(|results| {
+                results
+                    .iter()
+                    .filter_map(|r| r["id"].as_str())
+                    .filter_map(|id| Uuid::parse_str(id).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            timestamp: chrono::Utc::now(),
+            query,
+            limit,
+            use_spreading,
+            result_ids,
+            took_ms,
+        }
+    }
+}
+
+/// Why decay pruned a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PruneReason {
+    /// Salience (confidence, for facts) decayed below `prune_threshold`
+    Threshold,
+    /// `expires_at` passed
+    Expired,
+}
+
+/// One row pruned by a decay sweep, serialized as a single JSON line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PruneAuditRecord {
+    pub id: Uuid,
+    /// `memory_vectors`, `episodic_traces` or `semantic_facts`
+    pub table: String,
+    /// Salience (confidence, for facts) when the row was pruned
+    pub final_salience: f64,
+    pub reason: PruneReason,
+    pub swept_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl PruneAuditRecord {
+    pub fn new(id: Uuid, table: &str, final_salience: f64, reason: PruneReason) -> Self {
+        Self {
+            id,
+            table: table.to_string(),
+            final_salience,
+            reason,
+            swept_at: chrono::Utc::now(),
+        }
+    }
+}
+
+/// Append prune records to `path` (parent directories are created first).
+/// Sweeps run in the background, so records are written inline.
+pub async fn append_prune_records(
+    path: &Path,
+    records: &[PruneAuditRecord],
+) -> std::io::Result<()> {
+    let mut lines = String::new();
+    for record in records {
+        lines.push_str(&serde_json::to_string(record)?);
+        lines.push('\n');
+    }
+    append_line(path, &lines).await
+}
+
+/// Handle for queueing audit records to the writer task.
+pub struct SearchAudit {
+    tx: mpsc::Sender<SearchAuditRecord>,
+}
+
+impl SearchAudit {
+    /// Start a writer task appending records to `path` (parent directories
+    /// are created on first write).
+    pub fn spawn(path: PathBuf) -> Self {
+        let (tx, rx) = mpsc::channel(AUDIT_QUEUE_CAPACITY);
+        tokio::spawn(run_audit_writer(path, rx));
+        Self { tx }
+    }
+
+    /// Queue a record without waiting for the write.
+    pub fn record(&self, record: SearchAuditRecord) {
+        if let Err(e) = self.tx.try_send(record) {
+            tracing::warn!(error = %e, "Dropping search audit record");
+        }
+    }
+}
+
+static SEARCH_AUDIT: OnceLock<SearchAudit> = OnceLock::new();
+
+/// Install the process-wide search audit log. Returns false if one is
+/// already installed.
+pub fn install_search_audit(audit: SearchAudit) -> bool {
+    SEARCH_AUDIT.set(audit).is_ok()
+}
+
+/// The installed audit log, if `audit_searches` is enabled.
+pub fn search_audit() -> Option<&'static SearchAudit> {
+    SEARCH_AUDIT.get()
+}
+
+/// Append queued records to `path` until every sender is dropped.
+async fn run_audit_writer(path: PathBuf, mut rx: mpsc::Receiver<SearchAuditRecord>) {
+    while let Some(record) = rx.recv().await {
+        let mut line = match serde_json::to_string(&record) {
+            Ok(line) => line,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to encode search audit record");
+                continue;
+            }
+        };
+        line.push('\n');
+        if let Err(e) = append_line(&path, &line).await {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to write search audit record");
+        }
+    }
+}
+
+async fn append_line(path: &Path, line: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(line.as_bytes()).await?;
+    file.flush().await
+}
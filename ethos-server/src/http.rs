@@ -8,27 +8,70 @@
 //! machinery, which improves coverage accuracy under tarpaulin.
 //!
 //! Endpoints:
-//! - GET  /health      — health check with DB status
-//! - GET  /version     — server version info
+//! - GET  /health      — health check with DB status and embedding backlog
+//! - GET  /version     — server version, build and embedding backend info
+//! - GET  /stats       — memory counts and age/salience summary
+//! - GET  /metrics     — Prometheus text-format counters and gauges
 //! - POST /search      — semantic memory search
+//! - GET  /search/stream — search as Server-Sent Events (anchors, results, done)
 //! - POST /ingest      — ingest content into memory
+//! - POST /ingest/batch — ingest an array of memories in one transaction
 //! - POST /consolidate — trigger consolidation cycle
+//! - POST /decay       — run a decay sweep now (`{"dry_run": true}` to preview)
+//! - GET  /consolidate/candidates — promotion criteria per unconsolidated episode
+//! - POST /consolidate/preview — facts the next cycle would extract, nothing written
+//! - GET  /consolidate/history — the background loop's recent cycle reports, newest first
+//! - GET  /facts       — active facts (`?subject=`, `?flagged=true`, `?limit=`)
+//! - GET  /facts/:id/sources — the episodes a fact was consolidated from
+//! - PATCH /facts/:id — correct a fact's statement, object, confidence or topics
+//! - POST /facts/:id/resolve — resolve a fact flagged for review
+//! - GET  /sessions    — sessions with event and consolidation counts (`?agent=`, `?active_since=`)
+//! - GET  /memory      — memories created since a time, oldest first (`?since=`, `?cursor=`, `?limit=`)
+//! - DELETE /memory/:id — forget a memory (soft by default, `?hard=true` to remove)
+//! - GET  /memory/:id/neighbors — graph neighbors of a memory (`?depth=`, `?limit=`)
+//! - POST /admin/backend — switch the embedding backend without restarting
+//! - POST /admin/gc    — hard-delete rows pruned longer than `[decay] gc_retention_days`
+//! - GET  /reembed/status — NULL-vector backfill progress (`done` once search is fully restored)
+//! - GET  /export      — every non-pruned memory with its vector, streamed as JSONL
+//! - POST /import      — restore JSONL from `/export`; lines without a vector are embedded
+//!
+//! When `[http] api_token` is set, every endpoint except `/health` and
+//! `/version` requires `Authorization: Bearer <token>`. Bodies over
+//! `[http] max_body_bytes` get 413; requests over `request_timeout_seconds` get 408.
+//!
+//! Endpoint errors are [`ApiError`]s: the JSON body carries the message and a
+//! stable `code` (`bad_request`, `not_found`, `payload_too_large`,
+//! `unavailable`, `internal`).
 
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
-use axum::extract::State;
-use axum::http::StatusCode;
-use axum::response::IntoResponse;
-use axum::routing::{get, post};
+use axum::body::Body;
+use axum::extract::{ConnectInfo, DefaultBodyLimit, Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, patch, post};
 use axum::{Json, Router};
-use ethos_core::ipc::{EthosRequest, EthosResponse};
-use ethos_core::EthosConfig;
+use ethos_core::embeddings::{EmbeddingBackend, TaskType};
+use ethos_core::ipc::{ConflictAction, EthosRequest, EthosResponse, FactEdit};
+use ethos_core::{ErrorKind, EthosConfig};
+use futures::TryStreamExt;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use subtle::ConstantTimeEq;
 use tokio::net::TcpListener;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc};
+use tower_http::timeout::TimeoutLayer;
+
+use crate::metrics::{metrics, MemoryGauges};
+use crate::subsystems::embedder;
+use crate::subsystems::retrieve::{self, SearchFilters, SearchOptions};
 
 /// Shared state for all HTTP handlers
 #[derive(Clone)]
@@ -38,14 +81,217 @@ pub struct HttpState {
 }
 
 /// Build the Axum router with all endpoints
+///
+/// `/health` and `/version` are always public; everything else sits behind
+/// [`require_bearer_token`] when `[http] api_token` is set. `/search` and
+/// `/ingest` are additionally rate limited when `[http] requests_per_minute`
+/// is non-zero.
 pub fn build_router(state: Arc<HttpState>) -> Router {
-    Router::new()
-        .route("/health", get(health_handler))
-        .route("/version", get(version_handler))
+    // Endpoints that spend an embedding call per request
+    let mut limited = Router::new()
         .route("/search", post(search_handler))
+        .route("/search/stream", get(search_stream_handler))
         .route("/ingest", post(ingest_handler))
+        .route("/ingest/batch", post(ingest_batch_handler));
+    if state.config.http.requests_per_minute > 0 {
+        let limiter = Arc::new(RateLimiter::new(
+            state.config.http.requests_per_minute,
+            state.config.http.api_token.clone(),
+        ));
+        limited = limited.route_layer(middleware::from_fn_with_state(limiter, rate_limit));
+    }
+
+    let protected = Router::new()
+        .route("/stats", get(stats_handler))
+        .route("/metrics", get(metrics_handler))
         .route("/consolidate", post(consolidate_handler))
-        .with_state(state)
+        .route("/consolidate/candidates", get(candidates_handler))
+        .route("/consolidate/preview", post(preview_handler))
+        .route("/consolidate/history", get(history_handler))
+        .route("/decay", post(decay_handler))
+        .route("/facts", get(facts_handler))
+        .route("/facts/:id", patch(edit_fact_handler))
+        .route("/facts/:id/sources", get(fact_sources_handler))
+        .route("/facts/:id/resolve", post(resolve_handler))
+        .route("/sessions", get(sessions_handler))
+        .route("/memory", get(memories_handler))
+        .route("/memory/:id", delete(delete_handler))
+        .route("/memory/:id/neighbors", get(neighbors_handler))
+        .route("/admin/backend", post(set_backend_handler))
+        .route("/admin/gc", post(gc_handler))
+        .route("/reembed/status", get(reembed_status_handler))
+        .route("/export", get(export_handler))
+        .route("/import", post(import_handler))
+        .merge(limited)
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_bearer_token,
+        ));
+
+    let body_limit = match state.config.http.max_body_bytes {
+        0 => DefaultBodyLimit::disable(),
+        max => DefaultBodyLimit::max(max),
+    };
+    let timeout_seconds = state.config.http.request_timeout_seconds;
+
+    let router = Router::new()
+        .route("/health", get(health_handler))
+        .route("/version", get(version_handler))
+        .merge(protected)
+        .layer(body_limit)
+        .with_state(state);
+
+    if timeout_seconds > 0 {
+        router.layer(TimeoutLayer::with_status_code(
+            StatusCode::REQUEST_TIMEOUT,
+            Duration::from_secs(timeout_seconds),
+        ))
+    } else {
+        router
+    }
+}
+
+/// Reject requests lacking `Authorization: Bearer <api_token>` with 401.
+/// Passes everything through when no token is configured.
+async fn require_bearer_token(
+    State(state): State<Arc<HttpState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let expected = match state.config.http.api_token.as_deref() {
+        Some(token) if !token.is_empty() => token,
+        _ => return next.run(request).await,
+    };
+
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    if provided.is_some_and(|token| token_matches(token, expected)) {
+        next.run(request).await
+    } else {
+        (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({
+                "error": "missing or invalid bearer token",
+                "status": "error",
+            })),
+        )
+            .into_response()
+    }
+}
+
+/// Compare a presented token against the configured one in constant time,
+/// so response timing doesn't leak how much of a guess was right
+pub(crate) fn token_matches(provided: &str, expected: &str) -> bool {
+    provided.as_bytes().ct_eq(expected.as_bytes()).into()
+}
+
+/// Buckets kept before idle (fully refilled) ones are evicted
+const RATE_LIMIT_MAX_BUCKETS: usize = 10_000;
+
+/// Per-client token buckets for `[http] requests_per_minute`.
+///
+/// Each client may burst up to the full per-minute allowance, which then
+/// refills continuously.
+struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    /// `[http] api_token`; only a bearer token matching it gets its own bucket
+    api_token: Option<String>,
+    buckets: std::sync::Mutex<HashMap<String, TokenBucket>>,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    fn new(requests_per_minute: u32, api_token: Option<String>) -> Self {
+        let capacity = requests_per_minute.max(1) as f64;
+        Self {
+            capacity,
+            refill_per_sec: capacity / 60.0,
+            api_token: api_token.filter(|token| !token.is_empty()),
+            buckets: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Bucket key for a request: the bearer token once it matches the
+    /// configured one, otherwise the client IP. Keying by an unchecked token
+    /// would hand every made-up token a fresh bucket.
+    fn key(&self, request: &Request) -> String {
+        let token = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        match (token, self.api_token.as_deref()) {
+            (Some(token), Some(expected)) if token_matches(token, expected) => {
+                format!("token:{}", token)
+            }
+            _ => request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|info| format!("ip:{}", info.0.ip()))
+                .unwrap_or_else(|| "unknown".to_string()),
+        }
+    }
+
+    /// Take one token for `key`. On exhaustion returns the seconds until a
+    /// token is available.
+    fn check(&self, key: &str) -> std::result::Result<(), u64> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        if buckets.len() >= RATE_LIMIT_MAX_BUCKETS && !buckets.contains_key(key) {
+            let (capacity, rate) = (self.capacity, self.refill_per_sec);
+            buckets.retain(|_, b| {
+                b.tokens + now.duration_since(b.updated).as_secs_f64() * rate < capacity
+            });
+        }
+
+        let bucket = buckets.entry(key.to_string()).or_insert(TokenBucket {
+            tokens: self.capacity,
+            updated: now,
+        });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - bucket.tokens) / self.refill_per_sec).ceil() as u64)
+        }
+    }
+}
+
+/// Rate-limit by the validated bearer token, otherwise by client IP.
+/// Exceeding the limit returns 429 with `Retry-After`.
+async fn rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let key = limiter.key(&request);
+
+    match limiter.check(&key) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after.max(1).to_string())],
+            Json(serde_json::json!({
+                "error": "rate limit exceeded",
+                "status": "error",
+            })),
+        )
+            .into_response(),
+    }
 }
 
 /// Start the HTTP server on the configured address.
@@ -62,12 +308,16 @@ pub async fn start_http_server(
     let listener = TcpListener::bind(&addr).await?;
     tracing::info!("Ethos HTTP API listening on http://{}", addr);
 
-    axum::serve(listener, app)
-        .with_graceful_shutdown(async move {
-            let _ = shutdown.recv().await;
-            tracing::info!("HTTP server shutting down...");
-        })
-        .await?;
+    // Connect info gives the rate limiter a per-client IP key
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async move {
+        let _ = shutdown.recv().await;
+        tracing::info!("HTTP server shutting down...");
+    })
+    .await?;
 
     Ok(())
 }
@@ -80,10 +330,64 @@ pub async fn start_http_server(
 pub struct SearchRequest {
     pub query: Option<String>,
     pub limit: Option<u32>,
+    /// Number of ranked results to skip (for "show more" paging)
+    #[serde(default)]
+    pub offset: Option<u32>,
+    /// Apply spreading activation; omitted uses `[retrieval] default_use_spreading`
+    #[serde(default)]
+    pub use_spreading: Option<bool>,
+    /// Re-rank with Maximal Marginal Relevance to drop near-duplicates
+    #[serde(default)]
+    pub diversify: bool,
+    /// Return one result per chunked document (its best-scoring chunk, with
+    /// `chunk_count`) instead of several chunks of the same document
+    #[serde(default, alias = "groupByDocument")]
+    pub group_by_document: bool,
+    /// Attach an `explanation` of each result's ranking
+    #[serde(default)]
+    pub explain: bool,
+    /// Include the spreading-activation edge trace in the response
     #[serde(default)]
-    pub use_spreading: bool,
-    /// Minimum score threshold (informational; filtering happens in retrieval)
+    pub trace: bool,
+    /// Also search synonym and plural/singular variants of the query; widens
+    /// recall at the cost of one embedding call per variant
+    #[serde(default)]
+    pub expand: bool,
+    /// Query embedding task type (e.g. `SEMANTIC_SIMILARITY`); backends
+    /// without task types ignore it
+    #[serde(default, alias = "taskType")]
+    pub task_type: Option<TaskType>,
+    /// Compare only the first N dimensions of the query and stored vectors
+    /// (Matryoshka truncation); must not exceed the stored dimension
+    #[serde(default, alias = "searchDimensions")]
+    pub search_dimensions: Option<u32>,
+    /// Attach to each episode result the active facts consolidated from it,
+    /// as `metadata.related_facts`
+    #[serde(default, alias = "includeRelatedFacts")]
+    pub include_related_facts: bool,
+    /// Minimum cosine score; weaker matches are dropped unless `min_results` relaxes it
+    #[serde(alias = "minScore")]
     pub min_score: Option<f64>,
+    /// Only return rows whose `source` is in this list (e.g. `["user"]`)
+    #[serde(default, alias = "sourceFilter")]
+    pub source_filter: Option<Vec<String>>,
+    /// Only return rows whose metadata contains this JSON object
+    #[serde(default, alias = "metadataContains")]
+    pub metadata_contains: Option<serde_json::Value>,
+    /// Only return memories created at or after this RFC 3339 instant
+    #[serde(default, alias = "createdAfter")]
+    pub created_after: Option<Timestamp>,
+    /// Only return memories created at or before this RFC 3339 instant
+    #[serde(default, alias = "createdBefore")]
+    pub created_before: Option<Timestamp>,
+    /// Only return memories whose episode or fact shares one of these topics.
+    /// With no `query`, lists those memories by salience.
+    #[serde(default)]
+    pub topics: Option<Vec<String>>,
+    /// Also return pruned ("forgotten") memories, tagged `pruned: true` in
+    /// their metadata. For forensics and debugging.
+    #[serde(default, alias = "includePruned")]
+    pub include_pruned: bool,
     #[serde(rename = "resourceId", alias = "resource_id")]
     pub resource_id: Option<String>,
     #[serde(rename = "threadId", alias = "thread_id")]
@@ -98,6 +402,173 @@ pub struct ConsolidateRequest {
     pub reason: Option<String>,
 }
 
+/// Body of `POST /admin/backend`.
+#[derive(Debug, Deserialize)]
+pub struct SetBackendRequest {
+    /// "gemini" | "onnx" | "gemini-fallback-onnx"
+    pub backend: String,
+}
+
+/// Optional body of `POST /admin/gc`.
+#[derive(Debug, Deserialize, Default)]
+pub struct GcRequest {
+    /// Overrides `[decay] gc_retention_days`
+    #[serde(default)]
+    pub retention_days: Option<u32>,
+}
+
+/// Optional body of `POST /decay`.
+#[derive(Debug, Deserialize, Default)]
+pub struct DecayRequest {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Query string of `GET /search/stream`.
+#[derive(Debug, Deserialize, Default)]
+pub struct SearchStreamQuery {
+    pub query: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    #[serde(default)]
+    pub use_spreading: Option<bool>,
+    #[serde(default)]
+    pub diversify: bool,
+    #[serde(default, alias = "groupByDocument")]
+    pub group_by_document: bool,
+    #[serde(default)]
+    pub explain: bool,
+    #[serde(default)]
+    pub trace: bool,
+    #[serde(default)]
+    pub expand: bool,
+    #[serde(default, alias = "taskType")]
+    pub task_type: Option<TaskType>,
+    #[serde(default, alias = "searchDimensions")]
+    pub search_dimensions: Option<u32>,
+    #[serde(default, alias = "includeRelatedFacts")]
+    pub include_related_facts: bool,
+    #[serde(alias = "minScore")]
+    pub min_score: Option<f64>,
+    #[serde(alias = "resourceId")]
+    pub resource_id: Option<String>,
+    #[serde(alias = "threadId")]
+    pub thread_id: Option<String>,
+    #[serde(alias = "agentId")]
+    pub agent_id: Option<String>,
+    #[serde(alias = "createdAfter")]
+    pub created_after: Option<Timestamp>,
+    #[serde(alias = "createdBefore")]
+    pub created_before: Option<Timestamp>,
+}
+
+/// One event of `GET /search/stream`.
+///
+/// A successful stream is `anchors` (cosine-only page), `results` (final
+/// ranking, same shape as `POST /search`), then `done`. Failures send `error`
+/// followed by `done`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchStreamEvent {
+    Anchors(serde_json::Value),
+    Results(serde_json::Value),
+    Error(String),
+    Done { took_ms: u64 },
+}
+
+impl SearchStreamEvent {
+    fn into_sse(self) -> Event {
+        let (name, data) = match self {
+            SearchStreamEvent::Anchors(data) => ("anchors", data),
+            SearchStreamEvent::Results(data) => ("results", data),
+            SearchStreamEvent::Error(error) => ("error", serde_json::json!({ "error": error })),
+            SearchStreamEvent::Done { took_ms } => {
+                ("done", serde_json::json!({ "took_ms": took_ms }))
+            }
+        };
+        Event::default().event(name).data(data.to_string())
+    }
+}
+
+/// Query string of `GET /consolidate/candidates`.
+#[derive(Debug, Deserialize, Default)]
+pub struct CandidatesQuery {
+    /// Session UUID or session key
+    pub session: Option<String>,
+    pub limit: Option<u32>,
+}
+
+/// Query string of `GET /facts`.
+#[derive(Debug, Deserialize, Default)]
+pub struct FactsQuery {
+    pub subject: Option<String>,
+    /// Only facts flagged for review
+    #[serde(default)]
+    pub flagged: bool,
+    pub limit: Option<u32>,
+}
+
+/// Query string of `GET /sessions`.
+#[derive(Debug, Deserialize, Default)]
+pub struct SessionsQuery {
+    /// Only sessions of this agent
+    pub agent: Option<String>,
+    /// Only sessions active at or after this instant (RFC 3339)
+    pub active_since: Option<chrono::DateTime<chrono::Utc>>,
+    pub limit: Option<u32>,
+}
+
+/// Query string of `GET /memory`.
+#[derive(Debug, Deserialize, Default)]
+pub struct MemoriesQuery {
+    /// Only memories changed at or after this instant (RFC 3339)
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+/// Query string of `GET /memory/:id/neighbors`.
+#[derive(Debug, Deserialize, Default)]
+pub struct NeighborsQuery {
+    /// Hops to explore (default 1, max 3)
+    pub depth: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+/// Query string of `DELETE /memory/:id`.
+#[derive(Debug, Deserialize, Default)]
+pub struct DeleteQuery {
+    /// Remove the row instead of marking it pruned
+    #[serde(default)]
+    pub hard: bool,
+}
+
+/// Body of `POST /facts/:id/resolve`.
+///
+/// `action` is `keep`, `discard`, or `supersede_with`; the latter requires `otherId`.
+#[derive(Debug, Deserialize)]
+pub struct ResolveRequest {
+    pub action: String,
+    #[serde(rename = "otherId", alias = "other_id")]
+    pub other_id: Option<uuid::Uuid>,
+}
+
+impl ResolveRequest {
+    /// Convert the request body into a `ConflictAction`, or a validation error.
+    pub fn into_action(self) -> std::result::Result<ConflictAction, String> {
+        match (self.action.as_str(), self.other_id) {
+            ("keep", _) => Ok(ConflictAction::Keep),
+            ("discard", _) => Ok(ConflictAction::Discard),
+            ("supersede_with", Some(other_id)) => Ok(ConflictAction::SupersedeWith(other_id)),
+            ("supersede_with", None) => Err("otherId is required for supersede_with".to_string()),
+            (other, _) => Err(format!(
+                "unknown action '{}': expected keep, discard, or supersede_with",
+                other
+            )),
+        }
+    }
+}
+
 /// Standard HTTP error response
 #[derive(Debug, Serialize)]
 pub struct ErrorResponse {
@@ -119,7 +590,11 @@ impl ErrorResponse {
 // ============================================================================
 
 /// Inner health check — queries DB and returns (status_code, json_body).
-pub async fn health_inner(pool: &PgPool, socket_path: &str) -> (StatusCode, serde_json::Value) {
+pub async fn health_inner(
+    pool: &PgPool,
+    socket_path: &str,
+    pending_degraded_threshold: u64,
+) -> (StatusCode, serde_json::Value) {
     let pg_ver = match ethos_core::db::health_check(pool).await {
         Ok(v) => v,
         Err(e) => {
@@ -138,54 +613,336 @@ pub async fn health_inner(pool: &PgPool, socket_path: &str) -> (StatusCode, serd
         Err(e) => format!("unavailable: {}", e),
     };
 
+    // Rows waiting for a vector: a growing count means ingest is outpacing
+    // embedding, which leaves new memories invisible to search
+    let pending = match crate::subsystems::reembed::count_pending(pool).await {
+        Ok(n) => Some(n),
+        Err(e) => {
+            tracing::warn!("Health check could not count pending embeddings: {}", e);
+            None
+        }
+    };
+    let backlogged = pending_degraded_threshold > 0
+        && pending.is_some_and(|n| n.max(0) as u64 > pending_degraded_threshold);
+
     (
         StatusCode::OK,
         serde_json::json!({
-            "status": "healthy",
+            "status": if backlogged { "degraded" } else { "healthy" },
             "version": env!("CARGO_PKG_VERSION"),
             "postgresql": pg_ver,
             "pgvector": pgvector_ver,
             "socket": socket_path,
+            "pending_embeddings": pending,
         }),
     )
 }
 
-/// Inner version — returns version info (pure, no IO).
-pub fn version_inner() -> serde_json::Value {
+/// Inner version — returns version, build and embedding backend info. The
+/// backend is the active one, so a runtime swap shows up here; `[embedding]`
+/// is reported if no backend can be built. `git_sha` and `build_timestamp`
+/// are embedded by `build.rs`.
+pub fn version_inner(config: &EthosConfig) -> serde_json::Value {
+    let (backend, dimensions) = match embedder::active_backend(config) {
+        Ok(active) => (active.name().to_string(), active.dimensions()),
+        Err(_) => (
+            config.embedding.backend.clone(),
+            embedder::configured_dimensions(config),
+        ),
+    };
     serde_json::json!({
         "version": env!("CARGO_PKG_VERSION"),
         "protocol": "ethos/1",
+        "git_sha": env!("ETHOS_GIT_SHA"),
+        "build_timestamp": env!("ETHOS_BUILD_TIMESTAMP"),
+        "backend": backend,
+        "embedding_dimensions": dimensions,
     })
 }
 
-/// Inner search — validates query and calls the IPC router.
-pub async fn search_inner(
+/// Inner re-embed status — progress of the NULL-vector backfill worker.
+pub fn reembed_status_inner(config: &EthosConfig) -> serde_json::Value {
+    let progress = crate::subsystems::reembed::reembed_progress();
+    serde_json::json!({
+        "enabled": config.embedding.reembed_enabled,
+        "total_null": progress.total_null,
+        "embedded_so_far": progress.embedded_so_far,
+        "done": progress.done,
+    })
+}
+
+/// Inner import — restores JSONL lines produced by `GET /export`, read from
+/// `jsonl` a line at a time.
+pub async fn import_inner(
     pool: &PgPool,
     config: &EthosConfig,
-    req: SearchRequest,
-) -> (StatusCode, serde_json::Value) {
-    let query = match req.query {
-        Some(q) if !q.trim().is_empty() => q,
-        _ => {
-            return (
-                StatusCode::BAD_REQUEST,
-                serde_json::json!({
-                    "error": "query field is required",
-                    "status": "error",
-                }),
-            );
+    jsonl: impl tokio::io::AsyncRead + Unpin,
+) -> serde_json::Value {
+    let report = crate::subsystems::export::import_memories(pool, jsonl, Some(config)).await;
+    serde_json::to_value(report).unwrap_or_default()
+}
+
+/// Inner stats — per-table memory counts plus age and salience summaries.
+pub async fn stats_inner(pool: &PgPool) -> Result<serde_json::Value, ApiError> {
+    Ok(collect_stats(pool).await?)
+}
+
+/// Inner metrics — Prometheus text with counters and live row-count gauges.
+///
+/// Gauges are left out (with a warning) if the database can't be queried, so
+/// the counters stay scrapeable during an outage.
+pub async fn metrics_inner(pool: &PgPool) -> (StatusCode, String) {
+    let gauges = sqlx::query_as::<_, (i64, i64)>(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM memory_vectors WHERE NOT pruned),
+            (SELECT COUNT(*) FROM semantic_facts WHERE NOT pruned)
+        "#,
+    )
+    .fetch_one(pool)
+    .await;
+
+    let gauges = match gauges {
+        Ok((memory_vectors, semantic_facts)) => Some(MemoryGauges {
+            memory_vectors,
+            semantic_facts,
+        }),
+        Err(e) => {
+            tracing::warn!(error = %e, "Skipping /metrics gauges");
+            None
         }
     };
 
+    (StatusCode::OK, metrics().render(gauges))
+}
+
+type Timestamp = chrono::DateTime<chrono::Utc>;
+
+/// One aggregate query per table: three round-trips in total.
+async fn collect_stats(pool: &PgPool) -> std::result::Result<serde_json::Value, sqlx::Error> {
+    let (vectors_total, vectors_null, vectors_pruned, vectors_oldest, vectors_newest): (
+        i64,
+        i64,
+        i64,
+        Option<Timestamp>,
+        Option<Timestamp>,
+    ) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*),
+               COUNT(*) FILTER (WHERE vector IS NULL),
+               COUNT(*) FILTER (WHERE pruned),
+               MIN(created_at),
+               MAX(created_at)
+        FROM memory_vectors
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let (
+        episodes_total,
+        episodes_consolidated,
+        episodes_pruned,
+        episodes_salience_sum,
+        episodes_oldest,
+        episodes_newest,
+    ): (
+        i64,
+        i64,
+        i64,
+        Option<f64>,
+        Option<Timestamp>,
+        Option<Timestamp>,
+    ) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*),
+               COUNT(*) FILTER (WHERE consolidated_at IS NOT NULL),
+               COUNT(*) FILTER (WHERE pruned),
+               SUM(salience),
+               MIN(created_at),
+               MAX(created_at)
+        FROM episodic_traces
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let (
+        facts_total,
+        facts_flagged,
+        facts_superseded,
+        facts_salience_sum,
+        facts_oldest,
+        facts_newest,
+    ): (
+        i64,
+        i64,
+        i64,
+        Option<f64>,
+        Option<Timestamp>,
+        Option<Timestamp>,
+    ) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*),
+               COUNT(*) FILTER (WHERE flagged_for_review),
+               COUNT(*) FILTER (WHERE superseded_by IS NOT NULL),
+               SUM(salience),
+               MIN(created_at),
+               MAX(created_at)
+        FROM semantic_facts
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let oldest = [vectors_oldest, episodes_oldest, facts_oldest]
+        .into_iter()
+        .flatten()
+        .min();
+    let newest = [vectors_newest, episodes_newest, facts_newest]
+        .into_iter()
+        .flatten()
+        .max();
+
+    // Salience lives on episodes and facts; average across both
+    let salience_rows = episodes_total + facts_total;
+    let avg_salience = (salience_rows > 0).then(|| {
+        (episodes_salience_sum.unwrap_or(0.0) + facts_salience_sum.unwrap_or(0.0))
+            / salience_rows as f64
+    });
+
+    Ok(serde_json::json!({
+        "memory_vectors": {
+            "total": vectors_total,
+            "null_vector": vectors_null,
+            "pruned": vectors_pruned,
+        },
+        "episodic_traces": {
+            "total": episodes_total,
+            "consolidated": episodes_consolidated,
+            "pruned": episodes_pruned,
+        },
+        "semantic_facts": {
+            "total": facts_total,
+            "flagged": facts_flagged,
+            "superseded": facts_superseded,
+        },
+        "oldest_created_at": oldest,
+        "newest_created_at": newest,
+        "avg_salience": avg_salience,
+    }))
+}
+
+/// Inner search stream — runs the search in two stages on a background task,
+/// sending the anchor page before spreading activation and re-ranking finish.
+///
+/// The query must already be validated as non-empty.
+pub fn search_stream_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    backend: Arc<dyn EmbeddingBackend>,
+    req: SearchStreamQuery,
+) -> mpsc::Receiver<SearchStreamEvent> {
+    let (tx, rx) = mpsc::channel(4);
+    let pool = pool.clone();
+    let retrieval = config.retrieval.clone();
+
+    tokio::spawn(async move {
+        let start = Instant::now();
+        let options = SearchOptions {
+            limit: req.limit,
+            offset: req.offset,
+            use_spreading: retrieval.use_spreading(req.use_spreading),
+            diversify: req.diversify,
+            group_by_document: req.group_by_document,
+            explain: req.explain,
+            trace: req.trace,
+            expand: req.expand,
+            task_type: req.task_type,
+            search_dimensions: req.search_dimensions,
+            include_related_facts: req.include_related_facts,
+        };
+        let filters = SearchFilters {
+            resource_id: req.resource_id,
+            thread_id: req.thread_id,
+            agent_id: req.agent_id,
+            min_score: req.min_score,
+            created_after: req.created_after,
+            created_before: req.created_before,
+            ..Default::default()
+        };
+        let query = req.query.unwrap_or_default();
+
+        let outcome = async {
+            let stage = retrieve::search_anchors(
+                query,
+                options,
+                filters,
+                &pool,
+                backend.as_ref(),
+                &retrieval,
+            )
+            .await?;
+            // A closed channel means the client went away; stop early
+            if tx
+                .send(SearchStreamEvent::Anchors(stage.preview(&retrieval)))
+                .await
+                .is_err()
+            {
+                return Ok(None);
+            }
+            retrieve::finish_search(stage, &pool, &retrieval)
+                .await
+                .map(Some)
+        }
+        .await;
+
+        let event = match outcome {
+            Ok(Some(results)) => SearchStreamEvent::Results(results),
+            Ok(None) => return,
+            Err(e) => SearchStreamEvent::Error(e.to_string()),
+        };
+        if tx.send(event).await.is_ok() {
+            let took_ms = start.elapsed().as_millis() as u64;
+            let _ = tx.send(SearchStreamEvent::Done { took_ms }).await;
+        }
+    });
+
+    rx
+}
+
+/// Inner search — runs the query through the IPC router, which validates it.
+pub async fn search_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    req: SearchRequest,
+) -> Result<serde_json::Value, ApiError> {
     let start = Instant::now();
 
+    // The router rejects an empty query unless a topic filter is set
     let ipc_request = EthosRequest::Search {
-        query: query.clone(),
+        query: req.query.unwrap_or_default(),
         limit: req.limit,
+        offset: req.offset,
         use_spreading: req.use_spreading,
+        diversify: req.diversify,
+        group_by_document: req.group_by_document,
+        explain: req.explain,
+        trace: req.trace,
+        expand: req.expand,
+        task_type: req.task_type,
+        search_dimensions: req.search_dimensions,
+        include_related_facts: req.include_related_facts,
         resource_id: req.resource_id,
         thread_id: req.thread_id,
         agent_id: req.agent_id,
+        min_score: req.min_score,
+        source_filter: req.source_filter,
+        metadata_contains: req.metadata_contains,
+        created_after: req.created_after,
+        created_before: req.created_before,
+        topics: req.topics,
+        include_pruned: req.include_pruned,
     };
 
     let response =
@@ -193,44 +950,40 @@ pub async fn search_inner(
 
     let took_ms = start.elapsed().as_millis() as u64;
 
-    match response_to_http(response) {
-        Ok(mut data) => {
-            if let Some(obj) = data.as_object_mut() {
-                obj.insert("took_ms".to_string(), serde_json::json!(took_ms));
-            }
-            (StatusCode::OK, data)
-        }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            serde_json::json!({
-                "error": e,
-                "status": "error",
-            }),
-        ),
+    let mut data = response_to_http(response)?;
+    if let Some(obj) = data.as_object_mut() {
+        obj.insert("took_ms".to_string(), serde_json::json!(took_ms));
     }
+    Ok(data)
 }
 
-/// Inner ingest — calls the IPC router with the ingest payload.
+/// Inner ingest — calls the IPC router with the ingest payload. Content over
+/// `[service] max_content_bytes` gets 413.
 pub async fn ingest_inner(
     pool: &PgPool,
     config: &EthosConfig,
     payload: serde_json::Value,
-) -> (StatusCode, serde_json::Value) {
+) -> Result<serde_json::Value, ApiError> {
     let ipc_request = EthosRequest::Ingest { payload };
 
     let response =
         crate::router::handle_request_with_config(ipc_request, pool, Some(config.clone())).await;
 
-    match response_to_http(response) {
-        Ok(data) => (StatusCode::OK, data),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            serde_json::json!({
-                "error": e,
-                "status": "error",
-            }),
-        ),
-    }
+    response_to_http(response)
+}
+
+/// Inner batch ingest — calls the IPC router with every payload at once.
+pub async fn ingest_batch_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    payloads: Vec<serde_json::Value>,
+) -> Result<serde_json::Value, ApiError> {
+    let ipc_request = EthosRequest::IngestBatch { payloads };
+
+    let response =
+        crate::router::handle_request_with_config(ipc_request, pool, Some(config.clone())).await;
+
+    response_to_http(response)
 }
 
 /// Inner consolidate — calls the IPC router with the consolidation request.
@@ -238,7 +991,7 @@ pub async fn consolidate_inner(
     pool: &PgPool,
     config: &EthosConfig,
     req: ConsolidateRequest,
-) -> (StatusCode, serde_json::Value) {
+) -> Result<serde_json::Value, ApiError> {
     let ipc_request = EthosRequest::Consolidate {
         session: req.session,
         reason: req.reason,
@@ -247,16 +1000,264 @@ pub async fn consolidate_inner(
     let response =
         crate::router::handle_request_with_config(ipc_request, pool, Some(config.clone())).await;
 
-    match response_to_http(response) {
-        Ok(data) => (StatusCode::OK, data),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            serde_json::json!({
-                "error": e,
-                "status": "error",
-            }),
-        ),
-    }
+    response_to_http(response)
+}
+
+/// Inner decay — runs a decay sweep via the IPC router and returns its report.
+pub async fn decay_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    req: DecayRequest,
+) -> Result<serde_json::Value, ApiError> {
+    let ipc_request = EthosRequest::Decay {
+        dry_run: req.dry_run,
+    };
+
+    let response =
+        crate::router::handle_request_with_config(ipc_request, pool, Some(config.clone())).await;
+
+    response_to_http(response)
+}
+
+/// Inner candidates — lists promotion diagnostics via the IPC router.
+pub async fn candidates_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    query: CandidatesQuery,
+) -> Result<serde_json::Value, ApiError> {
+    let ipc_request = EthosRequest::ConsolidationCandidates {
+        session: query.session,
+        limit: query.limit,
+    };
+
+    let response =
+        crate::router::handle_request_with_config(ipc_request, pool, Some(config.clone())).await;
+
+    response_to_http(response)
+}
+
+/// Inner preview — extracts the next batch's facts via the IPC router without
+/// writing them.
+pub async fn preview_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+) -> Result<serde_json::Value, ApiError> {
+    let response = crate::router::handle_request_with_config(
+        EthosRequest::ConsolidationPreview,
+        pool,
+        Some(config.clone()),
+    )
+    .await;
+
+    response_to_http(response)
+}
+
+/// Inner history — the background loop's recent consolidation reports.
+pub async fn history_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+) -> Result<serde_json::Value, ApiError> {
+    let response = crate::router::handle_request_with_config(
+        EthosRequest::ConsolidationHistory,
+        pool,
+        Some(config.clone()),
+    )
+    .await;
+
+    response_to_http(response)
+}
+
+/// Inner fact sources — traces a fact back to its episodes via the IPC
+/// router; unknown ids return 404.
+pub async fn fact_sources_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    fact_id: uuid::Uuid,
+) -> Result<serde_json::Value, ApiError> {
+    let response = crate::router::handle_request_with_config(
+        EthosRequest::FactSources { fact_id },
+        pool,
+        Some(config.clone()),
+    )
+    .await;
+
+    response_to_http(response)
+}
+
+/// Inner fact edit — validates the edit (unknown fields, blank text and
+/// empty edits get 400) and calls the IPC router; unknown ids return 404.
+pub async fn edit_fact_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    fact_id: uuid::Uuid,
+    body: serde_json::Value,
+) -> Result<serde_json::Value, ApiError> {
+    let edit = serde_json::from_value::<FactEdit>(body)
+        .map_err(|e| e.to_string())
+        .and_then(FactEdit::validate)
+        .map_err(ApiError::BadRequest)?;
+
+    let response = crate::router::handle_request_with_config(
+        EthosRequest::EditFact { fact_id, edit },
+        pool,
+        Some(config.clone()),
+    )
+    .await;
+
+    response_to_http(response)
+}
+
+/// Inner resolve — validates the action and calls the IPC router.
+pub async fn resolve_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    fact_id: uuid::Uuid,
+    req: ResolveRequest,
+) -> Result<serde_json::Value, ApiError> {
+    let action = req.into_action().map_err(ApiError::BadRequest)?;
+
+    let ipc_request = EthosRequest::ResolveConflict { fact_id, action };
+
+    let response =
+        crate::router::handle_request_with_config(ipc_request, pool, Some(config.clone())).await;
+
+    response_to_http(response)
+}
+
+/// Inner neighbors — explores the memory graph around `id` via the IPC
+/// router; unknown ids return 404.
+pub async fn neighbors_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    id: uuid::Uuid,
+    query: NeighborsQuery,
+) -> Result<serde_json::Value, ApiError> {
+    let ipc_request = EthosRequest::Neighbors {
+        id,
+        depth: query.depth,
+        limit: query.limit,
+    };
+
+    let response =
+        crate::router::handle_request_with_config(ipc_request, pool, Some(config.clone())).await;
+
+    response_to_http(response)
+}
+
+/// Inner delete — forgets a memory via the IPC router; unknown ids return 404.
+pub async fn delete_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    id: uuid::Uuid,
+    query: DeleteQuery,
+) -> Result<serde_json::Value, ApiError> {
+    let ipc_request = EthosRequest::Delete {
+        id,
+        hard: query.hard,
+    };
+
+    let response =
+        crate::router::handle_request_with_config(ipc_request, pool, Some(config.clone())).await;
+
+    response_to_http(response)
+}
+
+/// Inner set-backend — swaps the embedding backend via the IPC router. Unknown
+/// or unconstructible backends return 400 and leave the current one active.
+pub async fn set_backend_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    req: SetBackendRequest,
+) -> Result<serde_json::Value, ApiError> {
+    let ipc_request = EthosRequest::SetBackend {
+        backend: req.backend,
+    };
+
+    let response =
+        crate::router::handle_request_with_config(ipc_request, pool, Some(config.clone())).await;
+
+    // Any failure to switch is a problem with the requested backend
+    response_to_http(response).map_err(|e| ApiError::BadRequest(e.to_string()))
+}
+
+/// Inner facts — lists active facts via the IPC router.
+pub async fn facts_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    query: FactsQuery,
+) -> Result<serde_json::Value, ApiError> {
+    let ipc_request = EthosRequest::ListFacts {
+        subject: query.subject,
+        flagged: query.flagged,
+        limit: query.limit,
+    };
+
+    let response =
+        crate::router::handle_request_with_config(ipc_request, pool, Some(config.clone())).await;
+
+    response_to_http(response)
+}
+
+/// Inner sessions — lists sessions and their activity via the IPC router.
+pub async fn sessions_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    query: SessionsQuery,
+) -> Result<serde_json::Value, ApiError> {
+    let ipc_request = EthosRequest::ListSessions {
+        agent_id: query.agent,
+        active_since: query.active_since,
+        limit: query.limit,
+    };
+
+    let response =
+        crate::router::handle_request_with_config(ipc_request, pool, Some(config.clone())).await;
+
+    response_to_http(response)
+}
+
+/// Inner memories — pages through memories in creation order via the IPC
+/// router. Malformed cursors return 400.
+pub async fn memories_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    query: MemoriesQuery,
+) -> Result<serde_json::Value, ApiError> {
+    if let Some(Err(e)) = query
+        .cursor
+        .as_deref()
+        .map(crate::subsystems::changefeed::Cursor::parse)
+    {
+        return Err(ApiError::BadRequest(e.to_string()));
+    }
+
+    let ipc_request = EthosRequest::ListMemories {
+        since: query.since,
+        cursor: query.cursor,
+        limit: query.limit,
+    };
+
+    let response =
+        crate::router::handle_request_with_config(ipc_request, pool, Some(config.clone())).await;
+
+    response_to_http(response)
+}
+
+/// Inner gc — deletes long-pruned rows via the IPC router and reports counts
+/// per table.
+pub async fn gc_inner(
+    pool: &PgPool,
+    config: &EthosConfig,
+    req: GcRequest,
+) -> Result<serde_json::Value, ApiError> {
+    let ipc_request = EthosRequest::Gc {
+        retention_days: req.retention_days,
+    };
+
+    let response =
+        crate::router::handle_request_with_config(ipc_request, pool, Some(config.clone())).await;
+
+    response_to_http(response)
 }
 
 // ============================================================================
@@ -264,50 +1265,354 @@ pub async fn consolidate_inner(
 // ============================================================================
 
 pub async fn health_handler(State(state): State<Arc<HttpState>>) -> impl IntoResponse {
-    let (status, body) = health_inner(&state.pool, &state.config.service.socket_path).await;
+    let (status, body) = health_inner(
+        &state.pool,
+        &state.config.service.socket_path,
+        state.config.embedding.pending_degraded_threshold,
+    )
+    .await;
     (status, Json(body))
 }
 
-pub async fn version_handler() -> impl IntoResponse {
-    (StatusCode::OK, Json(version_inner()))
+pub async fn version_handler(State(state): State<Arc<HttpState>>) -> impl IntoResponse {
+    (StatusCode::OK, Json(version_inner(&state.config)))
+}
+
+pub async fn reembed_status_handler(State(state): State<Arc<HttpState>>) -> impl IntoResponse {
+    (StatusCode::OK, Json(reembed_status_inner(&state.config)))
+}
+
+/// Stream the export as `application/x-ndjson`. A database error mid-export
+/// aborts the body, so a truncated transfer means an incomplete backup.
+pub async fn export_handler(State(state): State<Arc<HttpState>>) -> Response {
+    let rx = crate::subsystems::export::export_memories(&state.pool);
+    let stream = futures::stream::unfold(rx, |mut rx| async move {
+        let line = rx.recv().await?;
+        Some((line.map_err(|e| std::io::Error::other(e.to_string())), rx))
+    });
+    (
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(stream),
+    )
+        .into_response()
+}
+
+/// Streams the body rather than buffering it, so `[http] max_body_bytes`
+/// bounds each line instead of the whole backup.
+pub async fn import_handler(State(state): State<Arc<HttpState>>, body: Body) -> impl IntoResponse {
+    let stream = body.into_data_stream().map_err(std::io::Error::other);
+    let reader = tokio_util::io::StreamReader::new(stream);
+    Json(import_inner(&state.pool, &state.config, reader).await)
+}
+
+pub async fn stats_handler(State(state): State<Arc<HttpState>>) -> impl IntoResponse {
+    stats_inner(&state.pool).await.map(Json)
+}
+
+pub async fn metrics_handler(State(state): State<Arc<HttpState>>) -> impl IntoResponse {
+    let (status, body) = metrics_inner(&state.pool).await;
+    (
+        status,
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        body,
+    )
 }
 
+/// A search cut short by `[retrieval] max_search_ms` answers 504 with its
+/// partial page as the body.
 pub async fn search_handler(
     State(state): State<Arc<HttpState>>,
     Json(req): Json<SearchRequest>,
-) -> impl IntoResponse {
-    let (status, body) = search_inner(&state.pool, &state.config, req).await;
-    (status, Json(body))
+) -> Response {
+    match search_inner(&state.pool, &state.config, req).await {
+        Ok(body) if body["timed_out"] == true => {
+            (StatusCode::GATEWAY_TIMEOUT, Json(body)).into_response()
+        }
+        Ok(body) => Json(body).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn search_stream_handler(
+    State(state): State<Arc<HttpState>>,
+    Query(req): Query<SearchStreamQuery>,
+) -> Response {
+    if req
+        .query
+        .as_deref()
+        .map(str::trim)
+        .unwrap_or_default()
+        .is_empty()
+    {
+        return ApiError::BadRequest("query must not be empty".to_string()).into_response();
+    }
+
+    let backend = match embedder::active_backend(&state.config) {
+        Ok(backend) => backend,
+        Err(e) => {
+            return ApiError::Internal(format!("Embedder init failed: {}", e)).into_response();
+        }
+    };
+
+    let rx = search_stream_inner(&state.pool, &state.config, backend, req);
+    let stream = futures::stream::unfold(rx, |mut rx| async move {
+        let event = rx.recv().await?;
+        Some((Ok::<_, Infallible>(event.into_sse()), rx))
+    });
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
 }
 
 pub async fn ingest_handler(
     State(state): State<Arc<HttpState>>,
     Json(payload): Json<serde_json::Value>,
 ) -> impl IntoResponse {
-    let (status, body) = ingest_inner(&state.pool, &state.config, payload).await;
-    (status, Json(body))
+    ingest_inner(&state.pool, &state.config, payload)
+        .await
+        .map(Json)
+}
+
+pub async fn ingest_batch_handler(
+    State(state): State<Arc<HttpState>>,
+    Json(payloads): Json<Vec<serde_json::Value>>,
+) -> impl IntoResponse {
+    ingest_batch_inner(&state.pool, &state.config, payloads)
+        .await
+        .map(Json)
 }
 
 pub async fn consolidate_handler(
     State(state): State<Arc<HttpState>>,
     Json(req): Json<ConsolidateRequest>,
 ) -> impl IntoResponse {
-    let (status, body) = consolidate_inner(&state.pool, &state.config, req).await;
-    (status, Json(body))
+    consolidate_inner(&state.pool, &state.config, req)
+        .await
+        .map(Json)
+}
+
+pub async fn decay_handler(
+    State(state): State<Arc<HttpState>>,
+    req: Option<Json<DecayRequest>>,
+) -> impl IntoResponse {
+    let req = req.map(|Json(req)| req).unwrap_or_default();
+    decay_inner(&state.pool, &state.config, req).await.map(Json)
+}
+
+pub async fn candidates_handler(
+    State(state): State<Arc<HttpState>>,
+    Query(query): Query<CandidatesQuery>,
+) -> impl IntoResponse {
+    candidates_inner(&state.pool, &state.config, query)
+        .await
+        .map(Json)
+}
+
+pub async fn preview_handler(State(state): State<Arc<HttpState>>) -> impl IntoResponse {
+    preview_inner(&state.pool, &state.config).await.map(Json)
+}
+
+pub async fn history_handler(State(state): State<Arc<HttpState>>) -> impl IntoResponse {
+    history_inner(&state.pool, &state.config).await.map(Json)
+}
+
+pub async fn facts_handler(
+    State(state): State<Arc<HttpState>>,
+    Query(query): Query<FactsQuery>,
+) -> impl IntoResponse {
+    facts_inner(&state.pool, &state.config, query)
+        .await
+        .map(Json)
+}
+
+pub async fn sessions_handler(
+    State(state): State<Arc<HttpState>>,
+    Query(query): Query<SessionsQuery>,
+) -> impl IntoResponse {
+    sessions_inner(&state.pool, &state.config, query)
+        .await
+        .map(Json)
+}
+
+pub async fn memories_handler(
+    State(state): State<Arc<HttpState>>,
+    Query(query): Query<MemoriesQuery>,
+) -> impl IntoResponse {
+    memories_inner(&state.pool, &state.config, query)
+        .await
+        .map(Json)
+}
+
+pub async fn fact_sources_handler(
+    State(state): State<Arc<HttpState>>,
+    Path(fact_id): Path<uuid::Uuid>,
+) -> impl IntoResponse {
+    fact_sources_inner(&state.pool, &state.config, fact_id)
+        .await
+        .map(Json)
+}
+
+pub async fn edit_fact_handler(
+    State(state): State<Arc<HttpState>>,
+    Path(fact_id): Path<uuid::Uuid>,
+    Json(body): Json<serde_json::Value>,
+) -> impl IntoResponse {
+    edit_fact_inner(&state.pool, &state.config, fact_id, body)
+        .await
+        .map(Json)
+}
+
+pub async fn resolve_handler(
+    State(state): State<Arc<HttpState>>,
+    Path(fact_id): Path<uuid::Uuid>,
+    Json(req): Json<ResolveRequest>,
+) -> impl IntoResponse {
+    resolve_inner(&state.pool, &state.config, fact_id, req)
+        .await
+        .map(Json)
+}
+
+pub async fn delete_handler(
+    State(state): State<Arc<HttpState>>,
+    Path(id): Path<uuid::Uuid>,
+    Query(query): Query<DeleteQuery>,
+) -> impl IntoResponse {
+    delete_inner(&state.pool, &state.config, id, query)
+        .await
+        .map(Json)
+}
+
+pub async fn neighbors_handler(
+    State(state): State<Arc<HttpState>>,
+    Path(id): Path<uuid::Uuid>,
+    Query(query): Query<NeighborsQuery>,
+) -> impl IntoResponse {
+    neighbors_inner(&state.pool, &state.config, id, query)
+        .await
+        .map(Json)
+}
+
+pub async fn set_backend_handler(
+    State(state): State<Arc<HttpState>>,
+    Json(req): Json<SetBackendRequest>,
+) -> impl IntoResponse {
+    set_backend_inner(&state.pool, &state.config, req)
+        .await
+        .map(Json)
+}
+
+pub async fn gc_handler(
+    State(state): State<Arc<HttpState>>,
+    req: Option<Json<GcRequest>>,
+) -> impl IntoResponse {
+    let req = req.map(|Json(req)| req).unwrap_or_default();
+    gc_inner(&state.pool, &state.config, req).await.map(Json)
 }
 
 // ============================================================================
 // Helpers
 // ============================================================================
 
-/// Convert an IPC `EthosResponse` into an HTTP body value, or an error string.
-pub fn response_to_http(response: EthosResponse) -> std::result::Result<serde_json::Value, String> {
+/// Error returned by the inner functions. Each variant maps to an HTTP status
+/// and a stable `code` in the JSON body, so clients can tell a bad request
+/// from an outage without parsing the message:
+///
+/// ```json
+/// {"error": "query field is required", "code": "bad_request", "status": "error"}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ApiError {
+    /// The request itself is invalid (400, `bad_request`)
+    #[error("{0}")]
+    BadRequest(String),
+    /// The addressed memory does not exist (404, `not_found`)
+    #[error("{0}")]
+    NotFound(String),
+    /// Content over `[service] max_content_bytes` (413, `payload_too_large`)
+    #[error("{0}")]
+    PayloadTooLarge(String),
+    /// The database or embedding backend can't be reached; retrying later
+    /// may succeed (503, `unavailable`)
+    #[error("{0}")]
+    Unavailable(String),
+    /// Anything else (500, `internal`)
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl ApiError {
+    /// The variant for an IPC error of `kind`
+    pub fn from_kind(kind: ErrorKind, message: String) -> Self {
+        match kind {
+            ErrorKind::BadRequest => Self::BadRequest(message),
+            ErrorKind::NotFound => Self::NotFound(message),
+            ErrorKind::PayloadTooLarge => Self::PayloadTooLarge(message),
+            ErrorKind::Unavailable => Self::Unavailable(message),
+            ErrorKind::Internal => Self::Internal(message),
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Stable machine-readable error code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::BadRequest(_) => "bad_request",
+            Self::NotFound(_) => "not_found",
+            Self::PayloadTooLarge(_) => "payload_too_large",
+            Self::Unavailable(_) => "unavailable",
+            Self::Internal(_) => "internal",
+        }
+    }
+
+    pub fn body(&self) -> serde_json::Value {
+        serde_json::json!({
+            "error": self.to_string(),
+            "code": self.code(),
+            "status": "error",
+        })
+    }
+}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(e: sqlx::Error) -> Self {
+        match e {
+            sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_) => {
+                Self::Unavailable(e.to_string())
+            }
+            _ => Self::Internal(e.to_string()),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status(), Json(self.body())).into_response()
+    }
+}
+
+/// Convert an IPC `EthosResponse` into an HTTP body value, or the
+/// [`ApiError`] for its `error_kind` (`Internal` when unset).
+pub fn response_to_http(
+    response: EthosResponse,
+) -> std::result::Result<serde_json::Value, ApiError> {
     if response.status == "ok" {
         Ok(response.data.unwrap_or(serde_json::json!({})))
     } else {
-        Err(response
-            .error
-            .unwrap_or_else(|| "unknown error".to_string()))
+        Err(ApiError::from_kind(
+            response.error_kind.unwrap_or(ErrorKind::Internal),
+            response
+                .error
+                .unwrap_or_else(|| "unknown error".to_string()),
+        ))
     }
 }
 
@@ -328,14 +1633,43 @@ mod tests {
         Some((pool, config))
     }
 
+    /// Status and body the handler would send for an inner function's result
+    fn into_parts(result: Result<serde_json::Value, ApiError>) -> (StatusCode, serde_json::Value) {
+        match result {
+            Ok(body) => (StatusCode::OK, body),
+            Err(e) => (e.status(), e.body()),
+        }
+    }
+
+    /// The example config, loadable without ethos.toml
+    fn example_config() -> EthosConfig {
+        let path =
+            std::env::temp_dir().join(format!("ethos-http-test-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(&path, include_str!("../../ethos.toml.example")).expect("write temp config");
+        let config = EthosConfig::load(path.to_str().unwrap()).expect("example config should load");
+        std::fs::remove_file(&path).ok();
+        config
+    }
+
     // ========================================================================
-    // TEST 1: version_inner is pure and returns correct fields
+    // TEST 1: version_inner returns correct fields
     // ========================================================================
     #[test]
     fn test_version_inner_pure() {
-        let v = version_inner();
+        let config = example_config();
+        let v = version_inner(&config);
         assert!(v["version"].is_string(), "version must be string");
         assert_eq!(v["protocol"], "ethos/1", "protocol must be ethos/1");
+        assert!(v["git_sha"].is_string(), "git_sha must be string");
+        assert!(
+            v["build_timestamp"].is_string(),
+            "build_timestamp must be string"
+        );
+        assert_eq!(v["backend"], config.embedding.backend.as_str());
+        assert_eq!(
+            v["embedding_dimensions"], config.embedding.gemini_dimensions,
+            "example config uses the gemini backend"
+        );
     }
 
     // ========================================================================
@@ -358,7 +1692,10 @@ mod tests {
         let resp = EthosResponse::err("something went wrong");
         let result = response_to_http(resp);
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "something went wrong");
+        assert_eq!(
+            result.unwrap_err(),
+            ApiError::Internal("something went wrong".to_string())
+        );
     }
 
     // ========================================================================
@@ -382,7 +1719,10 @@ mod tests {
         resp.status = "error".to_string();
         let result = response_to_http(resp);
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "unknown error");
+        assert_eq!(
+            result.unwrap_err(),
+            ApiError::Internal("unknown error".to_string())
+        );
     }
 
     // ========================================================================
@@ -430,7 +1770,7 @@ mod tests {
             }
         };
 
-        let (status, body) = health_inner(&pool, "/tmp/ethos.sock").await;
+        let (status, body) = health_inner(&pool, "/tmp/ethos.sock", 0).await;
         assert_eq!(status, StatusCode::OK, "Health should return 200");
         assert_eq!(body["status"], "healthy");
         assert!(body["postgresql"].is_string());
@@ -453,14 +1793,29 @@ mod tests {
         let req = SearchRequest {
             query: Some("".to_string()),
             limit: None,
-            use_spreading: false,
+            offset: None,
+            use_spreading: Some(false),
+            diversify: false,
+            group_by_document: false,
+            explain: false,
+            trace: false,
+            expand: false,
+            task_type: None,
+            search_dimensions: None,
+            include_related_facts: false,
             min_score: None,
+            source_filter: None,
+            metadata_contains: None,
+            created_after: None,
+            created_before: None,
+            topics: None,
+            include_pruned: false,
             resource_id: None,
             thread_id: None,
             agent_id: None,
         };
 
-        let (status, body) = search_inner(&pool, &config, req).await;
+        let (status, body) = into_parts(search_inner(&pool, &config, req).await);
         assert_eq!(status, StatusCode::BAD_REQUEST);
         assert_eq!(body["status"], "error");
         assert!(body["error"].is_string());
@@ -482,14 +1837,29 @@ mod tests {
         let req = SearchRequest {
             query: None,
             limit: Some(5),
-            use_spreading: false,
+            offset: None,
+            use_spreading: Some(false),
+            diversify: false,
+            group_by_document: false,
+            explain: false,
+            trace: false,
+            expand: false,
+            task_type: None,
+            search_dimensions: None,
+            include_related_facts: false,
             min_score: None,
+            source_filter: None,
+            metadata_contains: None,
+            created_after: None,
+            created_before: None,
+            topics: None,
+            include_pruned: false,
             resource_id: None,
             thread_id: None,
             agent_id: None,
         };
 
-        let (status, body) = search_inner(&pool, &config, req).await;
+        let (status, body) = into_parts(search_inner(&pool, &config, req).await);
         assert_eq!(status, StatusCode::BAD_REQUEST);
         assert_eq!(body["status"], "error");
     }
@@ -510,14 +1880,29 @@ mod tests {
         let req = SearchRequest {
             query: Some("   ".to_string()),
             limit: None,
-            use_spreading: false,
+            offset: None,
+            use_spreading: Some(false),
+            diversify: false,
+            group_by_document: false,
+            explain: false,
+            trace: false,
+            expand: false,
+            task_type: None,
+            search_dimensions: None,
+            include_related_facts: false,
             min_score: None,
+            source_filter: None,
+            metadata_contains: None,
+            created_after: None,
+            created_before: None,
+            topics: None,
+            include_pruned: false,
             resource_id: None,
             thread_id: None,
             agent_id: None,
         };
 
-        let (status, body) = search_inner(&pool, &config, req).await;
+        let (status, body) = into_parts(search_inner(&pool, &config, req).await);
         assert_eq!(status, StatusCode::BAD_REQUEST);
         assert_eq!(body["status"], "error");
     }
@@ -538,17 +1923,34 @@ mod tests {
         let req = SearchRequest {
             query: Some("semantic memory search".to_string()),
             limit: Some(3),
-            use_spreading: false,
+            offset: None,
+            use_spreading: Some(false),
+            diversify: false,
+            group_by_document: false,
+            explain: false,
+            trace: false,
+            expand: false,
+            task_type: None,
+            search_dimensions: None,
+            include_related_facts: false,
             min_score: None,
+            source_filter: None,
+            metadata_contains: None,
+            created_after: None,
+            created_before: None,
+            topics: None,
+            include_pruned: false,
             resource_id: None,
             thread_id: None,
             agent_id: None,
         };
 
-        let (status, body) = search_inner(&pool, &config, req).await;
-        // 200 (results or empty) or 500 (embedding unavailable)
+        let (status, body) = into_parts(search_inner(&pool, &config, req).await);
+        // 200 (results or empty) or 500/503 (embedding unavailable)
         assert!(
-            status == StatusCode::OK || status == StatusCode::INTERNAL_SERVER_ERROR,
+            status == StatusCode::OK
+                || status == StatusCode::INTERNAL_SERVER_ERROR
+                || status == StatusCode::SERVICE_UNAVAILABLE,
             "Unexpected status: {}",
             status
         );
@@ -581,14 +1983,29 @@ mod tests {
         let req = SearchRequest {
             query: Some("embedder init failure test".to_string()),
             limit: Some(3),
-            use_spreading: false,
+            offset: None,
+            use_spreading: Some(false),
+            diversify: false,
+            group_by_document: false,
+            explain: false,
+            trace: false,
+            expand: false,
+            task_type: None,
+            search_dimensions: None,
+            include_related_facts: false,
             min_score: None,
+            source_filter: None,
+            metadata_contains: None,
+            created_after: None,
+            created_before: None,
+            topics: None,
+            include_pruned: false,
             resource_id: None,
             thread_id: None,
             agent_id: None,
         };
 
-        let (status, body) = search_inner(&pool, &config, req).await;
+        let (status, body) = into_parts(search_inner(&pool, &config, req).await);
         assert_eq!(
             status,
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -622,9 +2039,9 @@ mod tests {
             // no "content" field — should cause an error
         });
 
-        let (status, body) = ingest_inner(&pool, &config, payload).await;
-        // Should return 500 with error info
-        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        let (status, body) = into_parts(ingest_inner(&pool, &config, payload).await);
+        // Should return 400 with error info
+        assert_eq!(status, StatusCode::BAD_REQUEST);
         assert!(body["error"].is_string(), "Should have error message");
     }
 
@@ -659,7 +2076,7 @@ mod tests {
             }
         });
 
-        let (status, body) = ingest_inner(&pool, &config, payload).await;
+        let (status, body) = into_parts(ingest_inner(&pool, &config, payload).await);
         assert_eq!(
             status,
             StatusCode::OK,
@@ -695,9 +2112,11 @@ mod tests {
             reason: Some("test trigger".to_string()),
         };
 
-        let (status, body) = consolidate_inner(&pool, &config, req).await;
+        let (status, body) = into_parts(consolidate_inner(&pool, &config, req).await);
         assert!(
-            status == StatusCode::OK || status == StatusCode::INTERNAL_SERVER_ERROR,
+            status == StatusCode::OK
+                || status == StatusCode::INTERNAL_SERVER_ERROR
+                || status == StatusCode::SERVICE_UNAVAILABLE,
             "Unexpected status: {}",
             status
         );
@@ -723,11 +2142,414 @@ mod tests {
             }
         };
 
-        let (status, body) = health_inner(&pool, "/tmp/test.sock").await;
+        let (status, body) = health_inner(&pool, "/tmp/test.sock", 0).await;
         if status == StatusCode::OK {
             let version = body["version"].as_str().unwrap_or("");
             assert!(!version.is_empty(), "Version should not be empty");
             assert_eq!(version, env!("CARGO_PKG_VERSION"));
         }
     }
+
+    // ========================================================================
+    // TEST 15: ResolveRequest maps onto ConflictAction and validates input
+    // ========================================================================
+    #[test]
+    fn test_resolve_request_into_action() {
+        let other_id = uuid::Uuid::new_v4();
+
+        let req: ResolveRequest = serde_json::from_value(serde_json::json!({
+            "action": "supersede_with",
+            "otherId": other_id,
+        }))
+        .expect("camelCase payload should deserialize");
+        assert_eq!(
+            req.into_action(),
+            Ok(ConflictAction::SupersedeWith(other_id))
+        );
+
+        let req: ResolveRequest = serde_json::from_value(serde_json::json!({
+            "action": "keep",
+        }))
+        .expect("payload without otherId should deserialize");
+        assert_eq!(req.into_action(), Ok(ConflictAction::Keep));
+
+        let missing_other = ResolveRequest {
+            action: "supersede_with".to_string(),
+            other_id: None,
+        };
+        assert!(missing_other.into_action().is_err());
+
+        let unknown = ResolveRequest {
+            action: "keep-both".to_string(),
+            other_id: None,
+        };
+        assert!(unknown.into_action().is_err());
+    }
+
+    // ========================================================================
+    // TEST 16: stats_inner — reports numeric counts for each table
+    // ========================================================================
+    #[tokio::test]
+    async fn test_stats_inner_counts() {
+        let (pool, _config) = match make_state().await {
+            Some(s) => s,
+            None => {
+                eprintln!("Skipping test_stats_inner_counts: DB unavailable");
+                return;
+            }
+        };
+
+        let vector_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO memory_vectors (content, source, pruned) VALUES ('stats test', 'test-stats', true) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .expect("insert memory vector");
+        let fact_id: uuid::Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO semantic_facts (kind, statement, subject, predicate, object, flagged_for_review, salience)
+            VALUES ('fact', 'stats test', 'StatsTest', 'value', 'x', true, 0.5)
+            RETURNING id
+            "#,
+        )
+        .fetch_one(&pool)
+        .await
+        .expect("insert fact");
+
+        let (status, body) = into_parts(stats_inner(&pool).await);
+
+        sqlx::query("DELETE FROM memory_vectors WHERE id = $1")
+            .bind(vector_id)
+            .execute(&pool)
+            .await
+            .ok();
+        sqlx::query("DELETE FROM semantic_facts WHERE id = $1")
+            .bind(fact_id)
+            .execute(&pool)
+            .await
+            .ok();
+
+        assert_eq!(status, StatusCode::OK, "body: {}", body);
+        for (table, keys) in [
+            ("memory_vectors", ["total", "null_vector", "pruned"]),
+            ("episodic_traces", ["total", "consolidated", "pruned"]),
+            ("semantic_facts", ["total", "flagged", "superseded"]),
+        ] {
+            for key in keys {
+                assert!(
+                    body[table][key].is_u64(),
+                    "{}.{} should be a count",
+                    table,
+                    key
+                );
+            }
+        }
+        assert!(body["memory_vectors"]["null_vector"].as_u64().unwrap() >= 1);
+        assert!(body["memory_vectors"]["pruned"].as_u64().unwrap() >= 1);
+        assert!(body["semantic_facts"]["flagged"].as_u64().unwrap() >= 1);
+        assert!(body["avg_salience"].is_f64());
+        assert!(body["oldest_created_at"].is_string());
+        assert!(body["newest_created_at"].is_string());
+    }
+
+    // ========================================================================
+    // TEST 17: search_stream_inner — anchors, then results, then done
+    // ========================================================================
+    #[tokio::test]
+    async fn test_search_stream_emits_stages() {
+        use ethos_core::embeddings::{
+            CircuitBreakerConfig, EmbeddingConfig, GeminiEmbeddingClient, DEFAULT_MAX_INPUT_CHARS,
+            GEMINI_DIMENSIONS,
+        };
+        use pgvector::Vector;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let (pool, config) = match make_state().await {
+            Some(s) => s,
+            None => {
+                eprintln!("Skipping test_search_stream_emits_stages: DB or config unavailable");
+                return;
+            }
+        };
+
+        let values: Vec<f32> = (0..768).map(|i| (i as f32) / 768.0).collect();
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "embedding": { "values": values } })),
+            )
+            .mount(&mock_server)
+            .await;
+        let backend = GeminiEmbeddingClient::with_base_url(
+            EmbeddingConfig {
+                api_key: "test-api-key".to_string(),
+                model: "gemini-embedding-001".to_string(),
+                dimensions: GEMINI_DIMENSIONS,
+                max_retries: 1,
+                retry_delay_ms: 10,
+                max_delay_ms: 10_000,
+                jitter: true,
+                normalize: false,
+                max_input_chars: DEFAULT_MAX_INPUT_CHARS,
+                breaker: CircuitBreakerConfig::default(),
+            },
+            mock_server.uri(),
+        )
+        .expect("Failed to create test client");
+
+        let agent = format!("stream-test-{}", uuid::Uuid::new_v4());
+        let id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO memory_vectors (content, source, vector, metadata) VALUES ('stream test', 'test', $1, $2) RETURNING id",
+        )
+        .bind(Vector::from(values))
+        .bind(serde_json::json!({ "agent_id": agent.clone() }))
+        .fetch_one(&pool)
+        .await
+        .expect("insert memory vector");
+
+        let mut rx = search_stream_inner(
+            &pool,
+            &config,
+            Arc::new(backend),
+            SearchStreamQuery {
+                query: Some("stream test".to_string()),
+                use_spreading: Some(true),
+                agent_id: Some(agent),
+                ..Default::default()
+            },
+        );
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+
+        sqlx::query("DELETE FROM memory_vectors WHERE id = $1")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .ok();
+
+        assert_eq!(events.len(), 3, "events: {:?}", events);
+        match &events[0] {
+            SearchStreamEvent::Anchors(data) => {
+                assert_eq!(data["results"][0]["id"], id.to_string())
+            }
+            other => panic!("expected anchors first, got {:?}", other),
+        }
+        match &events[1] {
+            SearchStreamEvent::Results(data) => {
+                assert_eq!(data["results"][0]["id"], id.to_string());
+                assert!(data["has_more"].is_boolean());
+            }
+            other => panic!("expected results second, got {:?}", other),
+        }
+        assert!(matches!(events[2], SearchStreamEvent::Done { .. }));
+    }
+
+    // ========================================================================
+    // TEST 18: decay_inner — dry run reports a stale row, real run prunes it
+    // ========================================================================
+    #[tokio::test]
+    async fn test_decay_inner_prunes_stale_row() {
+        let (pool, config) = match make_state().await {
+            Some(s) => s,
+            None => {
+                eprintln!("Skipping test_decay_inner_prunes_stale_row: DB or config unavailable");
+                return;
+            }
+        };
+
+        let id: uuid::Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO memory_vectors (content, source, importance, created_at, last_accessed)
+            VALUES ('stale decay test', 'test-decay-http', 0.06, NOW() - INTERVAL '120 days', NOW() - INTERVAL '120 days')
+            RETURNING id
+            "#,
+        )
+        .fetch_one(&pool)
+        .await
+        .expect("insert stale row");
+
+        let pruned = |pool: PgPool| async move {
+            sqlx::query_scalar::<_, bool>("SELECT pruned FROM memory_vectors WHERE id = $1")
+                .bind(id)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+        };
+
+        let (status, report) =
+            into_parts(decay_inner(&pool, &config, DecayRequest { dry_run: true }).await);
+        assert_eq!(status, StatusCode::OK, "body: {}", report);
+        assert_eq!(report["dry_run"], true);
+        assert!(report["would_prune_ids"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!(id)));
+        assert!(!pruned(pool.clone()).await, "dry run must not write");
+
+        let (status, report) =
+            into_parts(decay_inner(&pool, &config, DecayRequest::default()).await);
+        assert_eq!(status, StatusCode::OK, "body: {}", report);
+        assert!(report["vectors_pruned"].as_u64().unwrap() >= 1);
+        assert!(pruned(pool.clone()).await);
+
+        sqlx::query("DELETE FROM memory_vectors WHERE id = $1")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .ok();
+    }
+
+    // ========================================================================
+    // TEST 19: each ApiError variant maps to its status and stable code
+    // ========================================================================
+    #[tokio::test]
+    async fn test_api_error_status_and_code() {
+        let cases = [
+            (
+                ApiError::BadRequest("bad".to_string()),
+                StatusCode::BAD_REQUEST,
+                "bad_request",
+            ),
+            (
+                ApiError::NotFound("gone".to_string()),
+                StatusCode::NOT_FOUND,
+                "not_found",
+            ),
+            (
+                ApiError::PayloadTooLarge("big".to_string()),
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "payload_too_large",
+            ),
+            (
+                ApiError::Unavailable("down".to_string()),
+                StatusCode::SERVICE_UNAVAILABLE,
+                "unavailable",
+            ),
+            (
+                ApiError::Internal("oops".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal",
+            ),
+        ];
+
+        for (error, status, code) in cases {
+            let message = error.to_string();
+            let response = error.into_response();
+            assert_eq!(response.status(), status, "{}", code);
+
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("body");
+            let body: serde_json::Value = serde_json::from_slice(&bytes).expect("json body");
+            assert_eq!(body["code"], code);
+            assert_eq!(body["error"], message);
+            assert_eq!(body["status"], "error");
+        }
+    }
+
+    // ========================================================================
+    // TEST 20: IPC errors map onto ApiError variants by kind, not by message
+    // ========================================================================
+    #[test]
+    fn test_api_error_from_ipc_error_kind() {
+        use ethos_core::embeddings::EmbeddingError;
+        use ethos_core::EthosError;
+
+        let code = |response: EthosResponse| response_to_http(response).unwrap_err().code();
+
+        let id = uuid::Uuid::new_v4();
+        assert_eq!(
+            code(EthosResponse::err_kind(
+                ErrorKind::NotFound,
+                format!("Memory {} not found", id)
+            )),
+            "not_found"
+        );
+        assert_eq!(
+            code(EthosResponse::from_error(
+                &EthosError::PayloadTooLarge("content exceeds 5 bytes".to_string()).into()
+            )),
+            "payload_too_large"
+        );
+        assert_eq!(
+            code(EthosResponse::from_error(
+                &EthosError::BadRequest("Fact cannot supersede itself".to_string()).into()
+            )),
+            "bad_request"
+        );
+        assert_eq!(
+            code(EthosResponse::from_error(&sqlx::Error::PoolTimedOut.into())),
+            "unavailable"
+        );
+
+        let circuit_open = anyhow::Error::new(EmbeddingError::CircuitOpen { retry_in_ms: 500 })
+            .context("Failed to embed query");
+        let response = EthosResponse::from_error(&circuit_open);
+        assert!(response
+            .error
+            .as_deref()
+            .unwrap()
+            .starts_with("Failed to embed query: Circuit open"));
+        assert_eq!(code(response), "unavailable");
+
+        // Untyped errors are internal whatever their message says
+        assert_eq!(
+            code(EthosResponse::from_error(&anyhow::anyhow!(
+                "Memory {} not found",
+                id
+            ))),
+            "internal"
+        );
+        assert_eq!(
+            ApiError::from(sqlx::Error::PoolTimedOut).code(),
+            "unavailable"
+        );
+        assert_eq!(ApiError::from(sqlx::Error::RowNotFound).code(), "internal");
+    }
+
+    // ========================================================================
+    // TEST 21: health_inner reports the NULL-vector backlog and degrades past
+    // the threshold
+    // ========================================================================
+    #[tokio::test]
+    async fn test_health_inner_reports_pending_embeddings() {
+        let (pool, _config) = match make_state().await {
+            Some(s) => s,
+            None => {
+                eprintln!("Skipping test_health_inner_reports_pending_embeddings: DB unavailable");
+                return;
+            }
+        };
+
+        let source = format!("health-backlog-{}", uuid::Uuid::new_v4());
+        for n in 0..3 {
+            sqlx::query("INSERT INTO memory_vectors (content, source) VALUES ($1, $2)")
+                .bind(format!("awaiting a vector {}", n))
+                .bind(&source)
+                .execute(&pool)
+                .await
+                .expect("insert NULL-vector row");
+        }
+
+        let (off_status, off) = health_inner(&pool, "/tmp/ethos.sock", 0).await;
+        let (_, under) = health_inner(&pool, "/tmp/ethos.sock", u64::MAX).await;
+        let (over_status, over) = health_inner(&pool, "/tmp/ethos.sock", 2).await;
+
+        sqlx::query("DELETE FROM memory_vectors WHERE source = $1")
+            .bind(&source)
+            .execute(&pool)
+            .await
+            .ok();
+
+        assert_eq!(off_status, StatusCode::OK);
+        assert!(off["pending_embeddings"].as_i64().unwrap() >= 3, "{}", off);
+        assert_eq!(off["status"], "healthy", "threshold 0 never degrades");
+        assert_eq!(under["status"], "healthy", "{}", under);
+        assert_eq!(over_status, StatusCode::OK, "a backlog still serves");
+        assert_eq!(over["status"], "degraded", "{}", over);
+    }
 }
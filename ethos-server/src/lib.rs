@@ -1,4 +1,6 @@
+pub mod audit;
 pub mod http;
+pub mod metrics;
 pub mod router;
 pub mod server;
 pub mod subsystems;
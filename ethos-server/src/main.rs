@@ -1,4 +1,4 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use ethos_core::EthosConfig;
 use tokio::sync::broadcast;
 use tracing_subscriber::{fmt, EnvFilter};
@@ -13,6 +13,20 @@ struct Args {
 
     #[arg(long)]
     health: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Re-embed stored memories with the configured backend, then exit
+    Reembed {
+        /// Re-embed every row and resize the vector column to the backend's
+        /// dimensions (needed after switching between Gemini and ONNX)
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 #[tokio::main]
@@ -66,6 +80,62 @@ async fn main() -> anyhow::Result<()> {
         return Ok(());
     }
 
+    if let Some(Command::Reembed { force }) = args.command {
+        let backend = ethos_server::subsystems::embedder::create_backend_from_config(&config)?;
+        let (embedded, skipped) = if force {
+            ethos_server::subsystems::reembed::run_reembed_force(
+                &pool,
+                backend.as_ref(),
+                &config.embedding,
+            )
+            .await?
+        } else {
+            ethos_server::subsystems::reembed::run_reembed_drain(
+                &pool,
+                backend.as_ref(),
+                &config.embedding,
+            )
+            .await?
+        };
+        println!(
+            "✅ Re-embedded {} rows with {} ({} skipped)",
+            embedded,
+            backend.name(),
+            skipped
+        );
+        return Ok(());
+    }
+
+    // Warn early if the backend can't search what is already stored
+    match ethos_server::subsystems::embedder::active_backend(&config) {
+        Ok(backend) => {
+            if let Err(e) = ethos_server::subsystems::embedder::check_vector_dimensions(
+                &pool,
+                backend.name(),
+                backend.dimensions(),
+            )
+            .await
+            {
+                tracing::error!("Startup dimension check failed: {}", e);
+            }
+        }
+        Err(e) => tracing::warn!("Startup dimension check skipped: {}", e),
+    }
+
+    if config.service.warm_up {
+        match ethos_server::subsystems::embedder::active_backend(&config) {
+            Ok(backend) => {
+                ethos_server::subsystems::retrieve::warm_up(
+                    &pool,
+                    backend.as_ref(),
+                    &config.retrieval,
+                )
+                .await;
+            }
+            Err(e) => tracing::warn!("Warm-up skipped: {}", e),
+        }
+    }
+
     // IPC Server
     let (tx, _rx) = broadcast::channel(1);
     let shutdown_tx = tx.clone();
@@ -96,27 +166,45 @@ async fn main() -> anyhow::Result<()> {
         .await;
     });
 
+    // Spawn buffered retrieval recorder if a flush window is configured
+    if config.decay.retrieval_flush_ms > 0 {
+        let recorder =
+            std::sync::Arc::new(ethos_server::subsystems::decay::RetrievalRecorder::new(
+                pool.clone(),
+                config.retrieval.retrieval_confidence_gain,
+            ));
+        ethos_server::subsystems::decay::install_retrieval_recorder(recorder.clone());
+        tokio::spawn(ethos_server::subsystems::decay::run_retrieval_recorder(
+            recorder,
+            std::time::Duration::from_millis(config.decay.retrieval_flush_ms),
+            tx.subscribe(),
+        ));
+    }
+
+    // Spawn background edge suggestion if an interval is configured
+    if config.linker.suggest_interval_minutes > 0 {
+        tokio::spawn(ethos_server::subsystems::linker::run_edge_suggester(
+            pool.clone(),
+            config.linker.clone(),
+            tx.subscribe(),
+        ));
+    }
+
+    // Spawn the search audit writer if enabled
+    if config.logging.audit_searches {
+        let audit_path = shellexpand::tilde(&config.logging.audit_path).into_owned();
+        tracing::info!(path = %audit_path, "Search audit log enabled");
+        ethos_server::audit::install_search_audit(ethos_server::audit::SearchAudit::spawn(
+            audit_path.into(),
+        ));
+    }
+
     // Spawn re-embed backfill worker (Story 013)
     if config.embedding.reembed_enabled {
-        match ethos_server::subsystems::embedder::create_backend_from_config(&config) {
-            Ok(backend) => {
-                let reembed_pool = pool.clone();
-                let reembed_config = config.embedding.clone();
-                let reembed_backend: std::sync::Arc<dyn ethos_core::embeddings::EmbeddingBackend> =
-                    std::sync::Arc::from(backend);
-                tokio::spawn(ethos_server::subsystems::reembed::run_reembed_worker(
-                    reembed_pool,
-                    reembed_backend,
-                    reembed_config,
-                ));
-            }
-            Err(e) => {
-                tracing::warn!(
-                    "Re-embed worker skipped: failed to create embedding backend: {}",
-                    e
-                );
-            }
-        }
+        tokio::spawn(ethos_server::subsystems::reembed::run_reembed_worker(
+            pool.clone(),
+            config.clone(),
+        ));
     } else {
         tracing::info!("Re-embed worker disabled via config");
     }
@@ -135,6 +223,20 @@ async fn main() -> anyhow::Result<()> {
         });
     }
 
+    // Spawn IPC-over-TCP listener if configured
+    if let Some(tcp_addr) = config.service.ipc_tcp_addr.clone() {
+        let tcp_pool = pool.clone();
+        let tcp_config = config.clone();
+        let tcp_shutdown = tx.subscribe();
+        tokio::spawn(async move {
+            if let Err(e) =
+                server::run_tcp_server(&tcp_addr, tcp_pool, tcp_config, tcp_shutdown).await
+            {
+                tracing::error!("IPC TCP server error: {}", e);
+            }
+        });
+    }
+
     let socket_path = config.service.socket_path.clone();
     server::run_unix_server(&socket_path, pool, config, tx.subscribe()).await?;
 
@@ -0,0 +1,118 @@
+//! Process-wide counters exposed as Prometheus text on `GET /metrics`
+//!
+//! Counters are bumped where the work happens (router, consolidation cycle,
+//! decay sweep), so IPC and HTTP traffic and background loops all count.
+//! Gauges are read from the database at scrape time.
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Monotonic counters since process start.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    searches: AtomicU64,
+    ingests: AtomicU64,
+    consolidation_cycles: AtomicU64,
+    facts_created: AtomicU64,
+    decay_pruned: AtomicU64,
+}
+
+static METRICS: Metrics = Metrics::new();
+
+/// The process-wide metrics registry.
+pub fn metrics() -> &'static Metrics {
+    &METRICS
+}
+
+/// Current row counts reported as gauges.
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryGauges {
+    pub memory_vectors: i64,
+    pub semantic_facts: i64,
+}
+
+impl Metrics {
+    pub const fn new() -> Self {
+        Self {
+            searches: AtomicU64::new(0),
+            ingests: AtomicU64::new(0),
+            consolidation_cycles: AtomicU64::new(0),
+            facts_created: AtomicU64::new(0),
+            decay_pruned: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record_search(&self) {
+        self.searches.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_ingests(&self, count: u64) {
+        self.ingests.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn record_consolidation_cycle(&self, facts_created: u64) {
+        self.consolidation_cycles.fetch_add(1, Ordering::Relaxed);
+        self.facts_created
+            .fetch_add(facts_created, Ordering::Relaxed);
+    }
+
+    pub fn record_decay_pruned(&self, count: u64) {
+        self.decay_pruned.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Render in Prometheus text exposition format. Gauges are omitted when
+    /// `gauges` is `None` (e.g. the database is unreachable).
+    pub fn render(&self, gauges: Option<MemoryGauges>) -> String {
+        let mut out = String::new();
+        let counters = [
+            (
+                "ethos_searches_total",
+                "Search requests handled",
+                &self.searches,
+            ),
+            ("ethos_ingests_total", "Memories ingested", &self.ingests),
+            (
+                "ethos_consolidation_cycles_total",
+                "Consolidation cycles run",
+                &self.consolidation_cycles,
+            ),
+            (
+                "ethos_facts_created_total",
+                "Semantic facts created by consolidation",
+                &self.facts_created,
+            ),
+            (
+                "ethos_decay_pruned_total",
+                "Rows pruned by decay sweeps",
+                &self.decay_pruned,
+            ),
+        ];
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} counter");
+            let _ = writeln!(out, "{name} {}", value.load(Ordering::Relaxed));
+        }
+
+        if let Some(gauges) = gauges {
+            let values = [
+                (
+                    "ethos_memory_vectors",
+                    "Memory vectors not pruned",
+                    gauges.memory_vectors,
+                ),
+                (
+                    "ethos_semantic_facts",
+                    "Semantic facts not pruned",
+                    gauges.semantic_facts,
+                ),
+            ];
+            for (name, help, value) in values {
+                let _ = writeln!(out, "# HELP {name} {help}");
+                let _ = writeln!(out, "# TYPE {name} gauge");
+                let _ = writeln!(out, "{name} {value}");
+            }
+        }
+
+        out
+    }
+}
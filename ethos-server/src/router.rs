@@ -1,6 +1,12 @@
-use crate::subsystems::{consolidate, embedder, ingest, retrieve};
+use crate::audit::{search_audit, SearchAuditRecord};
+use crate::metrics::metrics;
+use crate::subsystems::{
+    changefeed, consolidate, decay, embedder, forget, ingest, retrieve, sessions,
+};
 use ethos_core::ipc::{EthosRequest, EthosResponse};
+use ethos_core::{ErrorKind, EthosError};
 use sqlx::PgPool;
+use std::time::Instant;
 
 pub async fn handle_request(request: EthosRequest, pool: &PgPool) -> EthosResponse {
     handle_request_with_config(request, pool, None).await
@@ -17,11 +23,21 @@ pub async fn handle_request_with_config(
         EthosRequest::Health => {
             let pg_ver = match ethos_core::db::health_check(pool).await {
                 Ok(v) => v,
-                Err(e) => return EthosResponse::err(format!("DB Health Check failed: {}", e)),
+                Err(e) => {
+                    return EthosResponse::err_kind(
+                        ErrorKind::Unavailable,
+                        format!("DB Health Check failed: {}", e),
+                    )
+                }
             };
             let vec_ver = match ethos_core::db::check_pgvector(pool).await {
                 Ok(v) => v,
-                Err(e) => return EthosResponse::err(format!("pgvector Check failed: {}", e)),
+                Err(e) => {
+                    return EthosResponse::err_kind(
+                        ErrorKind::Unavailable,
+                        format!("pgvector Check failed: {}", e),
+                    )
+                }
             };
             EthosResponse::ok(serde_json::json!({
                 "postgresql": pg_ver,
@@ -29,39 +45,120 @@ pub async fn handle_request_with_config(
                 "status": "healthy"
             }))
         }
+        EthosRequest::Ingest { payload } if payload["chunk"].as_bool() == Some(true) => {
+            match ingest::ingest_chunked(payload, pool, config.as_ref()).await {
+                Ok(outcome) => {
+                    if outcome.queued {
+                        metrics().record_ingests(outcome.ids.len() as u64);
+                    }
+                    EthosResponse::ok(serde_json::json!({
+                        "queued": outcome.queued,
+                        "id": outcome.ids.first(),
+                        "document_id": outcome.document_id,
+                        "ids": outcome.ids,
+                    }))
+                }
+                Err(e) => EthosResponse::from_error(&e),
+            }
+        }
         EthosRequest::Ingest { payload } => {
             match ingest::ingest_payload_with_embedding(payload, pool, config.as_ref()).await {
-                Ok(id) => EthosResponse::ok(serde_json::json!({
-                    "queued": true,
-                    "id": id
-                })),
-                Err(e) => EthosResponse::err(e.to_string()),
+                Ok(outcome) => {
+                    if outcome.queued {
+                        metrics().record_ingests(1);
+                    }
+                    EthosResponse::ok(serde_json::json!({
+                        "queued": outcome.queued,
+                        "id": outcome.id
+                    }))
+                }
+                Err(e) => EthosResponse::from_error(&e),
+            }
+        }
+        EthosRequest::IngestBatch { payloads } => {
+            match ingest::ingest_batch(payloads, pool, config.as_ref()).await {
+                Ok(report) => {
+                    metrics().record_ingests(report.queued as u64);
+                    EthosResponse::ok(serde_json::to_value(report).unwrap_or_default())
+                }
+                Err(e) => EthosResponse::from_error(&e),
             }
         }
         EthosRequest::Search {
             query,
             limit,
+            offset,
             use_spreading,
+            diversify,
+            group_by_document,
+            explain,
+            trace,
+            expand,
+            task_type,
+            search_dimensions,
+            include_related_facts,
             resource_id,
             thread_id,
             agent_id,
+            min_score,
+            source_filter,
+            metadata_contains,
+            created_after,
+            created_before,
+            topics,
+            include_pruned,
         } => {
+            metrics().record_search();
+            let use_spreading = config
+                .as_ref()
+                .map(|c| c.retrieval.use_spreading(use_spreading))
+                .unwrap_or_default();
+            let audit = search_audit().map(|audit| (audit, query.clone(), Instant::now()));
             match handle_search_request(
                 query,
-                limit,
-                use_spreading,
+                retrieve::SearchOptions {
+                    limit,
+                    offset,
+                    use_spreading,
+                    diversify,
+                    group_by_document,
+                    explain,
+                    trace,
+                    expand,
+                    task_type,
+                    search_dimensions,
+                    include_related_facts,
+                },
                 retrieve::SearchFilters {
                     resource_id,
                     thread_id,
                     agent_id,
+                    min_score,
+                    source_filter,
+                    metadata_contains,
+                    created_after,
+                    created_before,
+                    topics,
+                    include_pruned,
                 },
                 pool,
                 config.as_ref(),
             )
             .await
             {
-                Ok(data) => EthosResponse::ok(data),
-                Err(e) => EthosResponse::err(e.to_string()),
+                Ok(data) => {
+                    if let Some((audit, query, start)) = audit {
+                        audit.record(SearchAuditRecord::from_response(
+                            query,
+                            limit,
+                            use_spreading,
+                            &data,
+                            start.elapsed().as_millis() as u64,
+                        ));
+                    }
+                    EthosResponse::ok(data)
+                }
+                Err(e) => EthosResponse::from_error(&e),
             }
         }
         EthosRequest::Consolidate { session, reason } => {
@@ -93,13 +190,195 @@ pub async fn handle_request_with_config(
                     "facts_updated": report.facts_updated,
                     "facts_superseded": report.facts_superseded,
                     "facts_flagged": report.facts_flagged,
+                    "episodes_deduplicated": report.episodes_deduplicated,
                 })),
-                Err(e) => EthosResponse::err(e.to_string()),
+                Err(e) => EthosResponse::from_error(&e),
             }
         }
         EthosRequest::Embed { id } => match handle_embed_request(id, pool, config.as_ref()).await {
             Ok(_) => EthosResponse::ok(serde_json::json!({"embedded": true, "id": id})),
-            Err(e) => EthosResponse::err(e.to_string()),
+            Err(e) => EthosResponse::from_error(&e),
+        },
+        EthosRequest::ConsolidationCandidates { session, limit } => {
+            let consolidation_config = match config {
+                Some(c) => c.consolidation,
+                None => {
+                    return EthosResponse::err("No config available for consolidation");
+                }
+            };
+            match consolidate::diagnose_candidates(pool, &consolidation_config, session, limit)
+                .await
+            {
+                Ok(candidates) => EthosResponse::ok(serde_json::json!({
+                    "count": candidates.len(),
+                    "candidates": candidates,
+                })),
+                Err(e) => EthosResponse::from_error(&e),
+            }
+        }
+        EthosRequest::ConsolidationHistory => {
+            let runs = consolidate::consolidation_history();
+            EthosResponse::ok(serde_json::json!({
+                "count": runs.len(),
+                "capacity": consolidate::CONSOLIDATION_HISTORY_LEN,
+                "last_run_at": runs.first().map(|run| run.finished_at),
+                "runs": runs,
+            }))
+        }
+        EthosRequest::ConsolidationPreview => {
+            let consolidation_config = match config {
+                Some(c) => c.consolidation,
+                None => {
+                    return EthosResponse::err("No config available for consolidation");
+                }
+            };
+            match consolidate::preview_consolidation(pool, &consolidation_config).await {
+                Ok(preview) => EthosResponse::ok(serde_json::json!({
+                    "episodes_scanned": preview.episodes_scanned,
+                    "count": preview.facts.len(),
+                    "facts": preview.facts,
+                })),
+                Err(e) => EthosResponse::from_error(&e),
+            }
+        }
+        EthosRequest::ListFacts {
+            subject,
+            flagged,
+            limit,
+        } => match consolidate::list_facts(pool, subject, flagged, limit).await {
+            Ok(facts) => EthosResponse::ok(serde_json::json!({
+                "count": facts.len(),
+                "facts": facts,
+            })),
+            Err(e) => EthosResponse::from_error(&e),
+        },
+        EthosRequest::FactSources { fact_id } => {
+            match consolidate::fact_sources(pool, fact_id).await {
+                Ok(Some(sources)) => EthosResponse::ok(serde_json::json!({
+                    "id": fact_id,
+                    "count": sources.len(),
+                    "sources": sources,
+                })),
+                Ok(None) => EthosResponse::err_kind(
+                    ErrorKind::NotFound,
+                    format!("Fact {} not found", fact_id),
+                ),
+                Err(e) => EthosResponse::from_error(&e),
+            }
+        }
+        EthosRequest::EditFact { fact_id, edit } => {
+            match consolidate::edit_fact(pool, fact_id, edit).await {
+                Ok(Some(fact)) => EthosResponse::ok(serde_json::json!({
+                    "updated": true,
+                    "fact": fact,
+                })),
+                Ok(None) => EthosResponse::err_kind(
+                    ErrorKind::NotFound,
+                    format!("Fact {} not found", fact_id),
+                ),
+                Err(e) => EthosResponse::from_error(&e),
+            }
+        }
+        EthosRequest::ResolveConflict { fact_id, action } => {
+            match consolidate::resolve_conflict(pool, fact_id, action).await {
+                Ok(()) => EthosResponse::ok(serde_json::json!({
+                    "resolved": true,
+                    "id": fact_id,
+                    "resolution": action,
+                })),
+                Err(e) => EthosResponse::from_error(&e),
+            }
+        }
+        EthosRequest::Decay { dry_run } => {
+            let decay_config = match config {
+                Some(c) => c.decay,
+                None => return EthosResponse::err("No config available for decay"),
+            };
+            match decay::run_decay_sweep_with_options(pool, &decay_config, dry_run).await {
+                Ok(report) => EthosResponse::ok(serde_json::to_value(report).unwrap_or_default()),
+                Err(e) => EthosResponse::from_error(&e),
+            }
+        }
+        EthosRequest::Delete { id, hard } => match forget::delete_memory(pool, id, hard).await {
+            Ok(Some(outcome)) => EthosResponse::ok(serde_json::json!({
+                "deleted": true,
+                "id": outcome.id,
+                "hard": outcome.hard,
+                "links_removed": outcome.links_removed,
+                "events_removed": outcome.events_removed,
+            })),
+            Ok(None) => {
+                EthosResponse::err_kind(ErrorKind::NotFound, format!("Memory {} not found", id))
+            }
+            Err(e) => EthosResponse::from_error(&e),
+        },
+        EthosRequest::ListSessions {
+            agent_id,
+            active_since,
+            limit,
+        } => match sessions::list_sessions(pool, agent_id, active_since, limit).await {
+            Ok(sessions) => EthosResponse::ok(serde_json::json!({
+                "count": sessions.len(),
+                "sessions": sessions,
+            })),
+            Err(e) => EthosResponse::from_error(&e),
+        },
+        EthosRequest::ListMemories {
+            since,
+            cursor,
+            limit,
+        } => match changefeed::list_memories(pool, since, cursor.as_deref(), limit).await {
+            Ok(page) => EthosResponse::ok(serde_json::json!({
+                "count": page.memories.len(),
+                "memories": page.memories,
+                "next_cursor": page.next_cursor,
+            })),
+            Err(e) => EthosResponse::from_error(&e),
+        },
+        EthosRequest::Gc { retention_days } => {
+            let retention_days = match (retention_days, config.as_ref()) {
+                (Some(days), _) => days,
+                (None, Some(c)) => c.decay.gc_retention_days,
+                (None, None) => return EthosResponse::err("No config available for gc"),
+            };
+            match forget::collect_garbage(pool, retention_days).await {
+                Ok(report) => EthosResponse::ok(serde_json::to_value(report).unwrap_or_default()),
+                Err(e) => EthosResponse::from_error(&e),
+            }
+        }
+        EthosRequest::PinMemory { id, pinned } => match decay::pin_memory(pool, id, pinned).await {
+            Ok(Some(updated)) => EthosResponse::ok(serde_json::json!({
+                "id": id,
+                "pinned": pinned,
+                "updated": updated,
+            })),
+            Ok(None) => {
+                EthosResponse::err_kind(ErrorKind::NotFound, format!("Memory {} not found", id))
+            }
+            Err(e) => EthosResponse::from_error(&e),
+        },
+        EthosRequest::Neighbors { id, depth, limit } => {
+            let Some(cfg) = config.as_ref() else {
+                return EthosResponse::err("No config available for neighbors");
+            };
+            match retrieve::memory_neighbors(pool, id, depth, limit, &cfg.retrieval).await {
+                Ok(Some(neighbors)) => EthosResponse::ok(serde_json::json!({
+                    "id": id,
+                    "count": neighbors.len(),
+                    "neighbors": neighbors,
+                })),
+                Ok(None) => {
+                    EthosResponse::err_kind(ErrorKind::NotFound, format!("Memory {} not found", id))
+                }
+                Err(e) => EthosResponse::from_error(&e),
+            }
+        }
+        EthosRequest::SetBackend { backend } => match config.as_ref() {
+            Some(c) => match embedder::set_active_backend(c, &backend) {
+                Ok(name) => EthosResponse::ok(serde_json::json!({ "backend": name })),
+                Err(e) => EthosResponse::from_error(&e),
+            },
+            None => EthosResponse::err("No config available for embedding"),
         },
         _ => EthosResponse::ok(serde_json::json!({"stub": true})),
     }
@@ -118,7 +397,7 @@ async fn handle_embed_request(
         }
     };
 
-    let backend = embedder::create_backend_from_config(config)?;
+    let backend = embedder::active_backend(config)?;
     embedder::embed_by_id(id, pool, backend.as_ref()).await?;
 
     Ok(())
@@ -127,8 +406,7 @@ async fn handle_embed_request(
 /// Handle Search request with semantic retrieval
 async fn handle_search_request(
     query: String,
-    limit: Option<u32>,
-    use_spreading: bool,
+    options: retrieve::SearchOptions,
     filters: retrieve::SearchFilters,
     pool: &PgPool,
     config: Option<&ethos_core::EthosConfig>,
@@ -140,12 +418,20 @@ async fn handle_search_request(
         }
     };
 
-    let backend = embedder::create_backend_from_config(config)?;
+    // A topic filter alone lists by topic; that never embeds, so it works
+    // without a usable backend
+    if query.trim().is_empty() {
+        if filters.has_topics() {
+            return retrieve::list_by_topics(options, filters, pool).await;
+        }
+        return Err(EthosError::BadRequest("query field is required".to_string()).into());
+    }
+
+    let backend = embedder::active_backend(config)?;
 
     let result = retrieve::search_memory(
         query,
-        limit,
-        use_spreading,
+        options,
         filters,
         pool,
         backend.as_ref(),
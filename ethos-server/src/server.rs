@@ -1,3 +1,4 @@
+use crate::http::token_matches;
 use crate::router;
 use bytes::Bytes;
 use ethos_core::{
@@ -5,12 +6,39 @@ use ethos_core::{
     EthosConfig,
 };
 use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
 use sqlx::PgPool;
 use std::path::Path;
-use tokio::net::UnixListener;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpListener, UnixListener};
 use tokio::sync::broadcast;
 use tokio_util::codec::{FramedRead, FramedWrite, LengthDelimitedCodec};
 
+/// Payload encoding used inside each length-prefixed frame.
+#[derive(Debug, Clone, Copy)]
+enum WireFormat {
+    /// Unix socket: MessagePack
+    MessagePack,
+    /// TCP: JSON
+    Json,
+}
+
+impl WireFormat {
+    fn decode(self, bytes: &[u8]) -> Result<EthosRequest, String> {
+        match self {
+            WireFormat::MessagePack => rmp_serde::from_slice(bytes).map_err(|e| e.to_string()),
+            WireFormat::Json => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+        }
+    }
+
+    fn encode(self, response: &EthosResponse) -> Result<Vec<u8>, String> {
+        match self {
+            WireFormat::MessagePack => rmp_serde::to_vec_named(response).map_err(|e| e.to_string()),
+            WireFormat::Json => serde_json::to_vec(response).map_err(|e| e.to_string()),
+        }
+    }
+}
+
 pub async fn run_unix_server(
     socket_path: &str,
     pool: PgPool,
@@ -32,46 +60,8 @@ pub async fn run_unix_server(
                 let config = config.clone();
                 tokio::spawn(async move {
                     let (read, write) = stream.into_split();
-                    // Spec: 4-byte Little Endian length prefix + MessagePack payload
-                    let le_codec = || LengthDelimitedCodec::builder().little_endian().new_codec();
-                    let mut framed_read = FramedRead::new(read, le_codec());
-                    let mut framed_write = FramedWrite::new(write, le_codec());
-
-                    while let Some(frame) = framed_read.next().await {
-                        match frame {
-                            Ok(bytes_mut) => {
-                                let request: EthosRequest = match rmp_serde::from_slice(&bytes_mut) {
-                                    Ok(req) => req,
-                                    Err(e) => {
-                                        let resp = EthosResponse::err(format!("Deserialization error: {}", e));
-                                        match rmp_serde::to_vec_named(&resp) {
-                                            Ok(resp_bytes) => { let _ = framed_write.send(Bytes::from(resp_bytes)).await; }
-                                            Err(se) => tracing::error!("Failed to serialize error response: {}", se),
-                                        }
-                                        continue;
-                                    }
-                                };
-
-                                let response = router::handle_request_with_config(request, &pool, Some(config.clone())).await;
-                                match rmp_serde::to_vec_named(&response) {
-                                    Ok(resp_bytes) => {
-                                        if let Err(e) = framed_write.send(Bytes::from(resp_bytes)).await {
-                                            tracing::error!("Failed to send response: {}", e);
-                                            break;
-                                        }
-                                    }
-                                    Err(e) => {
-                                        tracing::error!("Failed to serialize response: {}", e);
-                                        break;
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                tracing::error!("Frame error: {}", e);
-                                break;
-                            }
-                        }
-                    }
+                    serve_connection(read, write, WireFormat::MessagePack, None, pool, config)
+                        .await;
                 });
             }
             _ = shutdown.recv() => {
@@ -87,3 +77,157 @@ pub async fn run_unix_server(
 
     Ok(())
 }
+
+/// Serve the IPC protocol over TCP on `addr` (`[service] ipc_tcp_addr`).
+///
+/// With `[service] ipc_tcp_token` set, every connection must authenticate
+/// first (see [`serve_tcp`]) and any address may be used. Without it the
+/// listener is unauthenticated, so only loopback addresses are accepted.
+pub async fn run_tcp_server(
+    addr: &str,
+    pool: PgPool,
+    config: EthosConfig,
+    shutdown: broadcast::Receiver<()>,
+) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    let local = listener.local_addr()?;
+    if !local.ip().is_loopback() && tcp_token(&config).is_none() {
+        anyhow::bail!(
+            "refusing to serve unauthenticated IPC over TCP on non-loopback address {} — \
+             set [service] ipc_tcp_token or bind 127.0.0.1",
+            local
+        );
+    }
+    tracing::info!("IPC TCP Server listening on {}", listener.local_addr()?);
+    serve_tcp(listener, pool, config, shutdown).await
+}
+
+/// Accept loop for an already-bound TCP listener.
+///
+/// Frames use the same 4-byte Little Endian length prefix as the Unix socket,
+/// but carry JSON instead of MessagePack. With `[service] ipc_tcp_token` set,
+/// the first frame must be `{"token": "<ipc_tcp_token>"}`: a match is answered
+/// with `{"authenticated": true}`, anything else with an error before the
+/// connection is closed.
+pub async fn serve_tcp(
+    listener: TcpListener,
+    pool: PgPool,
+    config: EthosConfig,
+    mut shutdown: broadcast::Receiver<()>,
+) -> anyhow::Result<()> {
+    loop {
+        tokio::select! {
+            res = listener.accept() => {
+                let (stream, peer) = res?;
+                tracing::debug!("IPC TCP connection from {}", peer);
+                let pool = pool.clone();
+                let config = config.clone();
+                tokio::spawn(async move {
+                    let token = tcp_token(&config).map(str::to_string);
+                    let (read, write) = stream.into_split();
+                    serve_connection(read, write, WireFormat::Json, token, pool, config).await;
+                });
+            }
+            _ = shutdown.recv() => {
+                tracing::info!("Shutting down IPC TCP server...");
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// `[service] ipc_tcp_token`, if set and non-empty
+fn tcp_token(config: &EthosConfig) -> Option<&str> {
+    config
+        .service
+        .ipc_tcp_token
+        .as_deref()
+        .filter(|token| !token.is_empty())
+}
+
+/// First frame of a TCP connection when `[service] ipc_tcp_token` is set
+#[derive(Deserialize)]
+struct TcpHandshake {
+    token: String,
+}
+
+/// Read framed requests from one connection and answer each through the
+/// router. With a `token`, the first frame must be a matching [`TcpHandshake`].
+async fn serve_connection<R, W>(
+    read: R,
+    write: W,
+    format: WireFormat,
+    token: Option<String>,
+    pool: PgPool,
+    config: EthosConfig,
+) where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    // Spec: 4-byte Little Endian length prefix + encoded payload
+    let le_codec = || LengthDelimitedCodec::builder().little_endian().new_codec();
+    let mut framed_read = FramedRead::new(read, le_codec());
+    let mut framed_write = FramedWrite::new(write, le_codec());
+
+    let mut awaiting_token = token.is_some();
+    while let Some(frame) = framed_read.next().await {
+        match frame {
+            Ok(bytes_mut) if awaiting_token => {
+                let expected = token.as_deref().unwrap_or_default();
+                let accepted = serde_json::from_slice::<TcpHandshake>(&bytes_mut)
+                    .is_ok_and(|handshake| token_matches(&handshake.token, expected));
+                let response = if accepted {
+                    EthosResponse::ok(serde_json::json!({ "authenticated": true }))
+                } else {
+                    tracing::warn!("IPC TCP connection failed to authenticate");
+                    EthosResponse::err("missing or invalid IPC token")
+                };
+                if let Ok(resp_bytes) = format.encode(&response) {
+                    let _ = framed_write.send(Bytes::from(resp_bytes)).await;
+                }
+                if !accepted {
+                    break;
+                }
+                awaiting_token = false;
+            }
+            Ok(bytes_mut) => {
+                let request = match format.decode(&bytes_mut) {
+                    Ok(req) => req,
+                    Err(e) => {
+                        let resp = EthosResponse::err(format!("Deserialization error: {}", e));
+                        match format.encode(&resp) {
+                            Ok(resp_bytes) => {
+                                let _ = framed_write.send(Bytes::from(resp_bytes)).await;
+                            }
+                            Err(se) => {
+                                tracing::error!("Failed to serialize error response: {}", se)
+                            }
+                        }
+                        continue;
+                    }
+                };
+
+                let response =
+                    router::handle_request_with_config(request, &pool, Some(config.clone())).await;
+                match format.encode(&response) {
+                    Ok(resp_bytes) => {
+                        if let Err(e) = framed_write.send(Bytes::from(resp_bytes)).await {
+                            tracing::error!("Failed to send response: {}", e);
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to serialize response: {}", e);
+                        break;
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::error!("Frame error: {}", e);
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Changefeed subsystem — memories in change order for external mirrors
+//!
+//! Backs `EthosRequest::ListMemories` / `GET /memory?since=`. Rows of
+//! `memory_vectors` are returned least recently changed first by
+//! `(updated_at, id)`, independent of vector similarity, so a caller can copy
+//! everything changed since a timestamp and then follow `next_cursor` page by
+//! page. Content edits and prunes bump `updated_at`, so an edited or pruned
+//! row shows up again (with `pruned: true` once pruned) and mirrors can
+//! update or drop it. Hard deletes leave no row behind and are not reported.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use ethos_core::compression;
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Default and maximum number of memories per changefeed page
+pub const DEFAULT_CHANGEFEED_LIMIT: i64 = 100;
+pub const MAX_CHANGEFEED_LIMIT: i64 = 1000;
+
+/// Position after the last memory of a page: its `updated_at` (microseconds
+/// since the epoch, Postgres' precision) and id, rendered as `<micros>:<uuid>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub updated_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    /// Parse a cursor previously returned as `next_cursor`.
+    pub fn parse(cursor: &str) -> Result<Self> {
+        let invalid = || anyhow!("Invalid cursor '{}'", cursor);
+        let (micros, id) = cursor.split_once(':').ok_or_else(invalid)?;
+        let micros: i64 = micros.parse().map_err(|_| invalid())?;
+        Ok(Self {
+            updated_at: DateTime::from_timestamp_micros(micros).ok_or_else(invalid)?,
+            id: Uuid::parse_str(id).map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl std::fmt::Display for Cursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.updated_at.timestamp_micros(), self.id)
+    }
+}
+
+/// A memory as exported by the changefeed.
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedMemory {
+    pub id: Uuid,
+    pub source: Option<String>,
+    pub content: String,
+    pub metadata: Option<serde_json::Value>,
+    pub pruned: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One page of the changefeed. `next_cursor` is `None` on the last page.
+#[derive(Debug, Serialize)]
+pub struct FeedPage {
+    pub memories: Vec<FeedMemory>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct FeedRow {
+    id: Uuid,
+    source: Option<String>,
+    content: Option<String>,
+    content_compressed: bool,
+    metadata: Option<serde_json::Value>,
+    pruned: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+/// List memories changed at or after `since`, least recently changed first,
+/// resuming after `cursor` when given.
+pub async fn list_memories(
+    pool: &PgPool,
+    since: Option<DateTime<Utc>>,
+    cursor: Option<&str>,
+    limit: Option<u32>,
+) -> Result<FeedPage> {
+    let cursor = cursor.map(Cursor::parse).transpose()?;
+    let limit = limit
+        .map(|l| (l as i64).clamp(1, MAX_CHANGEFEED_LIMIT))
+        .unwrap_or(DEFAULT_CHANGEFEED_LIMIT);
+
+    // One extra row tells whether another page follows
+    let mut rows = sqlx::query_as::<_, FeedRow>(
+        r#"
+        SELECT id, source, content, content_compressed, metadata, pruned, created_at, updated_at
+        FROM memory_vectors
+        WHERE ($1::timestamptz IS NULL OR updated_at >= $1)
+          AND ($2::timestamptz IS NULL OR (updated_at, id) > ($2, $3))
+        ORDER BY updated_at ASC, id ASC
+        LIMIT $4
+        "#,
+    )
+    .bind(since)
+    .bind(cursor.map(|c| c.updated_at))
+    .bind(cursor.map(|c| c.id))
+    .bind(limit + 1)
+    .fetch_all(pool)
+    .await?;
+
+    let has_more = rows.len() as i64 > limit;
+    rows.truncate(limit as usize);
+    let next_cursor = rows.last().filter(|_| has_more).map(|row| {
+        Cursor {
+            updated_at: row.updated_at,
+            id: row.id,
+        }
+        .to_string()
+    });
+
+    let memories = rows
+        .into_iter()
+        .map(|row| {
+            let content = compression::decode_content(
+                row.content.unwrap_or_default(),
+                row.content_compressed,
+            )?;
+            Ok(FeedMemory {
+                id: row.id,
+                source: row.source,
+                content,
+                metadata: row.metadata,
+                pruned: row.pruned,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(FeedPage {
+        memories,
+        next_cursor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trips() {
+        let cursor = Cursor {
+            updated_at: DateTime::from_timestamp_micros(1_760_000_000_123_456).unwrap(),
+            id: Uuid::new_v4(),
+        };
+        assert_eq!(Cursor::parse(&cursor.to_string()).unwrap(), cursor);
+    }
+
+    #[test]
+    fn test_cursor_rejects_garbage() {
+        assert!(Cursor::parse("").is_err());
+        assert!(Cursor::parse("123").is_err());
+        assert!(Cursor::parse("abc:00000000-0000-0000-0000-000000000000").is_err());
+        assert!(Cursor::parse("123:not-a-uuid").is_err());
+    }
+}
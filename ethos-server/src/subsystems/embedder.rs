@@ -6,31 +6,146 @@
 //! - Writing the resulting vectors back to the database
 //!
 //! Embedding runs in tokio::spawn AFTER the IPC response is sent — never blocks the caller.
+//!
+//! The backend can be swapped at runtime (`EthosRequest::SetBackend`); see
+//! [`active_backend`]. Swapping to a backend with a different width makes the
+//! stored vectors incompatible; [`check_vector_dimensions`] reports that.
+
+use std::collections::HashMap;
+use std::sync::Arc;
 
+use arc_swap::ArcSwapOption;
 use ethos_core::{
-    embeddings::{BackendConfig, EmbeddingBackend, EmbeddingConfig, EmbeddingError, OnnxConfig},
-    onnx_embedder, EthosConfig,
+    compression,
+    embeddings::{
+        prepare_text_for_embedding, BackendConfig, CircuitBreakerConfig, EmbeddingBackend,
+        EmbeddingConfig, EmbeddingError, OnnxConfig, SourceRoutedBackend,
+    },
+    onnx_embedder, EthosConfig, EthosError,
 };
 use pgvector::Vector;
 use sqlx::PgPool;
 use uuid::Uuid;
 
+/// Backend names accepted by `[embedding] backend` and `SetBackend`.
+pub const BACKEND_NAMES: [&str; 3] = ["gemini", "onnx", "gemini-fallback-onnx"];
+
+/// Backend installed at runtime, shared by retrieval and the embedder. Until
+/// the first swap, [`active_backend`] builds one from `[embedding] backend`.
+static ACTIVE_BACKEND: ArcSwapOption<ActiveBackend> = ArcSwapOption::const_empty();
+
+struct ActiveBackend(Arc<dyn EmbeddingBackend>);
+
+/// Backend name and width last found to match the stored vectors, so
+/// [`check_vector_dimensions`] only queries again after a swap.
+static VERIFIED_DIMENSIONS: ArcSwapOption<(String, usize)> = ArcSwapOption::const_empty();
+
+/// The embedding backend subsystems should use right now: the one installed
+/// by [`set_active_backend`] / [`install_backend`] if any, otherwise a fresh
+/// backend from the config.
+pub fn active_backend(config: &EthosConfig) -> Result<Arc<dyn EmbeddingBackend>, EmbeddingError> {
+    match ACTIVE_BACKEND.load_full() {
+        Some(active) => Ok(active.0.clone()),
+        None => create_backend_from_config(config).map(Arc::from),
+    }
+}
+
+/// Atomically replace the active backend. In-flight calls keep the backend
+/// they already hold; subsequent calls get the new one.
+pub fn install_backend(backend: Arc<dyn EmbeddingBackend>) {
+    ACTIVE_BACKEND.store(Some(Arc::new(ActiveBackend(backend))));
+    VERIFIED_DIMENSIONS.store(None);
+}
+
+/// Construct the named backend with the rest of `[embedding]` and, if that
+/// succeeds, make it the active backend. Returns the new backend's name.
+pub fn set_active_backend(config: &EthosConfig, backend: &str) -> anyhow::Result<String> {
+    if !BACKEND_NAMES.contains(&backend) {
+        return Err(EthosError::BadRequest(format!(
+            "Unknown embedding backend '{}' (expected one of: {})",
+            backend,
+            BACKEND_NAMES.join(", ")
+        ))
+        .into());
+    }
+
+    let mut config = config.clone();
+    config.embedding.backend = backend.to_string();
+    let created: Arc<dyn EmbeddingBackend> = create_backend_from_config(&config)
+        .map_err(|e| {
+            EthosError::BadRequest(format!("Cannot switch to '{}' backend: {}", backend, e))
+        })?
+        .into();
+
+    let name = created.name().to_string();
+    install_backend(created);
+    tracing::info!(backend = %name, "Switched embedding backend");
+
+    Ok(name)
+}
+
+/// Vector width the `[embedding] backend` produces, without building it.
+pub fn configured_dimensions(config: &EthosConfig) -> usize {
+    match config.embedding.backend.as_str() {
+        "onnx" => config.embedding.onnx_dimensions as usize,
+        _ => config.embedding.gemini_dimensions as usize,
+    }
+}
+
 /// Create an embedding backend from the application config.
 ///
 /// Reads `[embedding] backend` to select Gemini, ONNX, or Gemini-fallback-ONNX.
+/// When `[embedding.source_backends]` is set, the result is a
+/// [`SourceRoutedBackend`] with `backend` as its default.
 pub fn create_backend_from_config(
     config: &EthosConfig,
+) -> Result<Box<dyn EmbeddingBackend>, EmbeddingError> {
+    let source_backends = &config.embedding.source_backends;
+    if source_backends.is_empty() {
+        return create_named_backend(config, &config.embedding.backend);
+    }
+
+    // Build each distinct backend once, however many sources share it
+    let mut built: HashMap<&str, Arc<dyn EmbeddingBackend>> = HashMap::new();
+    for name in std::iter::once(&config.embedding.backend).chain(source_backends.values()) {
+        if !built.contains_key(name.as_str()) {
+            built.insert(name, create_named_backend(config, name)?.into());
+        }
+    }
+
+    let routes = source_backends
+        .iter()
+        .map(|(source, name)| (source.clone(), built[name.as_str()].clone()))
+        .collect();
+    let default = built[config.embedding.backend.as_str()].clone();
+
+    Ok(Box::new(SourceRoutedBackend::new(default, routes)?))
+}
+
+/// Create one backend by name with the rest of `[embedding]`.
+fn create_named_backend(
+    config: &EthosConfig,
+    backend: &str,
 ) -> Result<Box<dyn EmbeddingBackend>, EmbeddingError> {
     let api_key = std::env::var("GOOGLE_API_KEY").unwrap_or_default();
+    let breaker = CircuitBreakerConfig {
+        failure_threshold: config.embedding.circuit_breaker_threshold,
+        window: std::time::Duration::from_secs(config.embedding.circuit_breaker_window_seconds),
+        cooldown: std::time::Duration::from_secs(config.embedding.circuit_breaker_cooldown_seconds),
+    };
 
-    let backend_cfg = match config.embedding.backend.as_str() {
+    let backend_cfg = match backend {
         "onnx" => {
             let (model_path, tokenizer_path) =
                 onnx_embedder::resolve_onnx_paths(&config.embedding.onnx_model_path);
             BackendConfig::Onnx(OnnxConfig {
                 model_path,
                 tokenizer_path,
+                expected_sha256: config.embedding.onnx_model_sha256.clone(),
                 dimensions: config.embedding.onnx_dimensions as usize,
+                normalize: config.embedding.normalize.unwrap_or(true),
+                intra_threads: config.embedding.onnx_intra_threads,
+                inter_threads: config.embedding.onnx_inter_threads,
             })
         }
         "gemini-fallback-onnx" => BackendConfig::GeminiFallbackOnnx(EmbeddingConfig {
@@ -39,6 +154,11 @@ pub fn create_backend_from_config(
             dimensions: config.embedding.gemini_dimensions as usize,
             max_retries: 3,
             retry_delay_ms: 1000,
+            max_delay_ms: config.embedding.retry_max_delay_ms,
+            jitter: config.embedding.retry_jitter,
+            normalize: config.embedding.normalize.unwrap_or(false),
+            max_input_chars: config.embedding.max_input_chars,
+            breaker,
         }),
         _ => {
             // Default: "gemini"
@@ -48,6 +168,11 @@ pub fn create_backend_from_config(
                 dimensions: config.embedding.gemini_dimensions as usize,
                 max_retries: 3,
                 retry_delay_ms: 1000,
+                max_delay_ms: config.embedding.retry_max_delay_ms,
+                jitter: config.embedding.retry_jitter,
+                normalize: config.embedding.normalize.unwrap_or(false),
+                max_input_chars: config.embedding.max_input_chars,
+                breaker,
             })
         }
     };
@@ -55,8 +180,79 @@ pub fn create_backend_from_config(
     ethos_core::embeddings::create_backend(backend_cfg)
 }
 
+/// Stored vectors are a different width from what the embedding backend
+/// produces (e.g. 768-dim Gemini rows queried with a 384-dim ONNX backend).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DimensionMismatch {
+    pub backend: String,
+    pub backend_dimensions: usize,
+    pub stored_dimensions: usize,
+}
+
+impl std::fmt::Display for DimensionMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Embedding dimension mismatch: backend '{}' produces {}-dim vectors but stored memories are {}-dim. \
+             Switch back to the original backend or run `ethos-server reembed --force` to re-embed every row",
+            self.backend, self.backend_dimensions, self.stored_dimensions
+        )
+    }
+}
+
+impl std::error::Error for DimensionMismatch {}
+
+/// Width of the stored vectors, sampled from one embedded row.
+///
+/// Returns `None` when nothing has been embedded yet.
+pub async fn stored_vector_dimensions(pool: &PgPool) -> Result<Option<usize>, sqlx::Error> {
+    let dims: Option<i32> = sqlx::query_scalar(
+        "SELECT vector_dims(vector) FROM memory_vectors WHERE vector IS NOT NULL LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(dims.map(|d| d as usize))
+}
+
+/// Fail with a [`DimensionMismatch`] if stored vectors are not `dimensions`
+/// wide. Run at startup and before every vector search so a backend switch
+/// surfaces as a clear error rather than a pgvector "different vector
+/// dimensions" failure.
+///
+/// A match is remembered until the next [`install_backend`], so searches only
+/// pay for the lookup while it fails or nothing is embedded yet.
+pub async fn check_vector_dimensions(
+    pool: &PgPool,
+    backend: &str,
+    dimensions: usize,
+) -> anyhow::Result<()> {
+    if let Some(verified) = VERIFIED_DIMENSIONS.load().as_deref() {
+        if verified.0 == backend && verified.1 == dimensions {
+            return Ok(());
+        }
+    }
+
+    match stored_vector_dimensions(pool).await? {
+        Some(stored) if stored != dimensions => Err(DimensionMismatch {
+            backend: backend.to_string(),
+            backend_dimensions: dimensions,
+            stored_dimensions: stored,
+        }
+        .into()),
+        Some(_) => {
+            VERIFIED_DIMENSIONS.store(Some(Arc::new((backend.to_string(), dimensions))));
+            Ok(())
+        }
+        None => Ok(()),
+    }
+}
+
 /// Embed a single memory vector by ID using the provided backend.
 ///
+/// A row whose vector was embedded from its current `content_hash` is
+/// skipped; one whose content changed since is embedded again.
+///
 /// Returns Ok(true) if successful, Ok(false) if row not found or already embedded.
 pub async fn embed_by_id(
     id: Uuid,
@@ -66,30 +262,45 @@ pub async fn embed_by_id(
     #[derive(sqlx::FromRow)]
     struct MemoryRow {
         content: Option<String>,
+        content_compressed: bool,
+        source: Option<String>,
         vector: Option<Vector>,
+        content_hash: Option<String>,
+        embedded_hash: Option<String>,
     }
 
-    let row: MemoryRow = sqlx::query_as("SELECT content, vector FROM memory_vectors WHERE id = $1")
-        .bind(id)
-        .fetch_optional(pool)
-        .await?
-        .ok_or_else(|| anyhow::anyhow!("Memory vector {} not found", id))?;
+    let row: MemoryRow = sqlx::query_as(
+        "SELECT content, content_compressed, source, vector, content_hash, embedded_hash \
+         FROM memory_vectors WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| EthosError::NotFound(format!("Memory vector {} not found", id)))?;
 
-    if row.vector.is_some() {
-        tracing::debug!(id = %id, "Vector already populated, skipping");
+    if row.vector.is_some() && row.content_hash == row.embedded_hash {
+        tracing::debug!(id = %id, "Vector already matches content, skipping");
         return Ok(false);
     }
 
     let content = row
         .content
         .ok_or_else(|| anyhow::anyhow!("Memory vector {} has no content", id))?;
+    let content = compression::decode_content(content, row.content_compressed)?;
 
-    match backend.embed(&content).await {
+    match backend
+        .embed_for_source(
+            prepare_text_for_embedding(&content, backend.max_input_chars()),
+            row.source.as_deref(),
+        )
+        .await
+    {
         Ok(Some(embedding)) => {
             let vector = Vector::from(embedding);
-            sqlx::query("UPDATE memory_vectors SET vector = $1 WHERE id = $2")
+            sqlx::query("UPDATE memory_vectors SET vector = $1, embedded_hash = $3 WHERE id = $2")
                 .bind(&vector)
                 .bind(id)
+                .bind(&row.content_hash)
                 .execute(pool)
                 .await?;
             tracing::info!(id = %id, backend = backend.name(), "Successfully embedded memory vector");
@@ -115,7 +326,7 @@ pub async fn embed_by_id(
 pub fn spawn_embed_task(id: Uuid, pool: PgPool, config: &EthosConfig) {
     let config = config.clone();
     tokio::spawn(async move {
-        let backend = match create_backend_from_config(&config) {
+        let backend = match active_backend(&config) {
             Ok(b) => b,
             Err(e) => {
                 tracing::error!(id = %id, error = %e, "Failed to create embedding backend");
@@ -131,6 +342,9 @@ pub fn spawn_embed_task(id: Uuid, pool: PgPool, config: &EthosConfig) {
     });
 }
 
+/// Rows handed to [`EmbeddingBackend::embed_batch`] at once by [`embed_all_pending`]
+const PENDING_EMBED_CHUNK: usize = 32;
+
 /// Process all unembedded rows (for batch/scheduled processing).
 ///
 /// Returns the number of successfully embedded rows.
@@ -143,10 +357,13 @@ pub async fn embed_all_pending(
     struct PendingRow {
         id: Uuid,
         content: Option<String>,
+        content_compressed: bool,
+        source: Option<String>,
+        content_hash: Option<String>,
     }
 
     let rows: Vec<PendingRow> = sqlx::query_as(
-        "SELECT id, content FROM memory_vectors
+        "SELECT id, content, content_compressed, source, content_hash FROM memory_vectors
          WHERE vector IS NULL AND content IS NOT NULL
          ORDER BY created_at ASC LIMIT $1",
     )
@@ -154,35 +371,64 @@ pub async fn embed_all_pending(
     .fetch_all(pool)
     .await?;
 
+    let mut pending = Vec::with_capacity(rows.len());
+    for row in rows {
+        match compression::decode_content(row.content.unwrap_or_default(), row.content_compressed) {
+            Ok(content) => pending.push((row.id, content, row.source, row.content_hash)),
+            Err(e) => {
+                tracing::error!(id = %row.id, error = %e, "Failed to decompress content");
+            }
+        }
+    }
+
     let mut success_count = 0;
 
-    for row in rows {
-        let content = row.content.unwrap_or_default();
-
-        match backend.embed(&content).await {
-            Ok(Some(embedding)) => {
-                let vector = Vector::from(embedding);
-                match sqlx::query("UPDATE memory_vectors SET vector = $1 WHERE id = $2")
-                    .bind(&vector)
-                    .bind(row.id)
-                    .execute(pool)
-                    .await
-                {
-                    Ok(_) => {
-                        success_count += 1;
-                        tracing::info!(id = %row.id, "Embedded pending memory vector");
-                    }
-                    Err(e) => {
-                        tracing::error!(id = %row.id, error = %e, "Failed to write vector to DB");
-                    }
-                }
+    // Group by source so each chunk goes to a single routed backend
+    pending.sort_by(|(_, _, a, _), (_, _, b, _)| a.cmp(b));
+    let chunks = pending
+        .chunk_by(|(_, _, a, _), (_, _, b, _)| a == b)
+        .flat_map(|group| group.chunks(PENDING_EMBED_CHUNK));
+
+    // One backend call per chunk; batching backends (ONNX) embed each in a single pass
+    for chunk in chunks {
+        let source = chunk[0].2.as_deref();
+        let texts: Vec<&str> = chunk
+            .iter()
+            .map(|(_, content, _, _)| {
+                prepare_text_for_embedding(content, backend.max_input_chars())
+            })
+            .collect();
+        let embeddings = match backend.embed_batch_for_source(&texts, source).await {
+            Ok(embeddings) => embeddings,
+            Err(e) => {
+                tracing::error!(rows = chunk.len(), error = %e, "Failed to embed content");
+                continue;
             }
-            Ok(None) => {
+        };
+
+        for ((id, _, _, hash), embedding) in chunk.iter().zip(embeddings) {
+            let Some(embedding) = embedding else {
                 // Fallback: no embedding produced — skip (not a success)
-                tracing::info!(id = %row.id, "No embedding available, skipping");
-            }
-            Err(e) => {
-                tracing::error!(id = %row.id, error = %e, "Failed to embed content");
+                tracing::info!(id = %id, "No embedding available, skipping");
+                continue;
+            };
+            let vector = Vector::from(embedding);
+            match sqlx::query(
+                "UPDATE memory_vectors SET vector = $1, embedded_hash = $3 WHERE id = $2",
+            )
+            .bind(&vector)
+            .bind(id)
+            .bind(hash)
+            .execute(pool)
+            .await
+            {
+                Ok(_) => {
+                    success_count += 1;
+                    tracing::info!(id = %id, "Embedded pending memory vector");
+                }
+                Err(e) => {
+                    tracing::error!(id = %id, error = %e, "Failed to write vector to DB");
+                }
             }
         }
     }
@@ -198,7 +444,8 @@ pub async fn embed_all_pending(
 mod tests {
     use super::*;
     use ethos_core::embeddings::{
-        EmbeddingConfig as CoreEmbeddingConfig, GeminiEmbeddingClient, GEMINI_DIMENSIONS,
+        CircuitBreakerConfig, EmbeddingConfig as CoreEmbeddingConfig, GeminiEmbeddingClient,
+        DEFAULT_MAX_INPUT_CHARS, GEMINI_DIMENSIONS,
     };
     use wiremock::matchers::method;
     use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -219,6 +466,11 @@ mod tests {
             dimensions: GEMINI_DIMENSIONS,
             max_retries: 1,
             retry_delay_ms: 10,
+            max_delay_ms: 10_000,
+            jitter: true,
+            normalize: false,
+            max_input_chars: DEFAULT_MAX_INPUT_CHARS,
+            breaker: CircuitBreakerConfig::default(),
         };
 
         Box::new(
@@ -0,0 +1,181 @@
+//! Forget subsystem — removes a single memory on demand
+//!
+//! Backs `EthosRequest::Delete` / `DELETE /memory/{id}` (right to be forgotten):
+//! - Soft delete marks the `memory_vectors` row `pruned`, hiding it from search
+//! - Hard delete removes the row entirely, along with the `session_events`
+//!   row it was ingested with (for a chunked document, any chunk's deletion
+//!   removes the document's event)
+//! - Either way, `memory_graph_links` touching the memory are removed so it
+//!   can no longer be reached through spreading activation
+//!
+//! Also backs `EthosRequest::Gc` / `POST /admin/gc`, which hard-deletes rows
+//! that have been pruned for longer than the retention window.
+
+use anyhow::Result;
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Result of deleting a memory.
+#[derive(Debug, Serialize)]
+pub struct DeleteOutcome {
+    pub id: Uuid,
+    pub hard: bool,
+    /// Graph edges removed along with the memory
+    pub links_removed: u64,
+    /// Transcript rows removed by a hard delete
+    pub events_removed: u64,
+}
+
+/// Delete a memory vector by id. Returns `Ok(None)` if no such memory exists.
+///
+/// Edges are matched on both the memory id and its `source_id`, since links
+/// may reference either the vector row or the episode/fact it embeds.
+pub async fn delete_memory(pool: &PgPool, id: Uuid, hard: bool) -> Result<Option<DeleteOutcome>> {
+    let mut tx = pool.begin().await?;
+
+    let row: Option<(Option<Uuid>, Option<String>)> = if hard {
+        sqlx::query_as(
+            "DELETE FROM memory_vectors WHERE id = $1 RETURNING source_id, metadata->>'document_id'",
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+    } else {
+        sqlx::query_as(
+            "UPDATE memory_vectors SET pruned = true, updated_at = NOW() WHERE id = $1 RETURNING source_id, metadata->>'document_id'",
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+    };
+
+    let (source_id, document_id) = match row {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+
+    let events_removed = if hard {
+        sqlx::query(
+            r#"
+            DELETE FROM session_events
+            WHERE memory_id = $1
+               OR memory_id IN (SELECT id FROM memory_vectors WHERE metadata->>'document_id' = $2)
+            "#,
+        )
+        .bind(id)
+        .bind(document_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+    } else {
+        0
+    };
+
+    let linked_ids: Vec<Uuid> = std::iter::once(id).chain(source_id).collect();
+    let links_removed =
+        sqlx::query("DELETE FROM memory_graph_links WHERE from_id = ANY($1) OR to_id = ANY($1)")
+            .bind(&linked_ids)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+    tx.commit().await?;
+
+    tracing::info!(id = %id, hard, links_removed, events_removed, "Deleted memory");
+
+    Ok(Some(DeleteOutcome {
+        id,
+        hard,
+        links_removed,
+        events_removed,
+    }))
+}
+
+/// Rows removed by [`collect_garbage`], per table.
+#[derive(Debug, Default, Serialize)]
+pub struct GcReport {
+    pub retention_days: u32,
+    pub memory_vectors: u64,
+    pub episodic_traces: u64,
+    pub semantic_facts: u64,
+    /// Graph edges that touched a deleted row
+    pub memory_graph_links: u64,
+    /// Transcript rows of deleted memories
+    pub session_events: u64,
+}
+
+/// Hard-delete rows in `memory_vectors`, `episodic_traces`, and
+/// `semantic_facts` that are `pruned` and were last updated more than
+/// `retention_days` ago, plus the graph edges left pointing at them and the
+/// `session_events` rows the deleted memories were ingested with.
+///
+/// Facts still named in the `superseded_by` of a row that is kept are skipped
+/// until that row is collected too.
+pub async fn collect_garbage(pool: &PgPool, retention_days: u32) -> Result<GcReport> {
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(retention_days as i64);
+    let mut tx = pool.begin().await?;
+
+    let vectors: Vec<Uuid> = sqlx::query_scalar(
+        "DELETE FROM memory_vectors WHERE pruned AND updated_at < $1 RETURNING id",
+    )
+    .bind(cutoff)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let episodes: Vec<Uuid> = sqlx::query_scalar(
+        "DELETE FROM episodic_traces WHERE pruned AND updated_at < $1 RETURNING id",
+    )
+    .bind(cutoff)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let facts: Vec<Uuid> = sqlx::query_scalar(
+        r#"
+        DELETE FROM semantic_facts f
+        WHERE f.pruned AND f.updated_at < $1
+          AND NOT EXISTS (
+              SELECT 1 FROM semantic_facts s
+              WHERE s.superseded_by = f.id
+                AND NOT (s.pruned AND s.updated_at < $1)
+          )
+        RETURNING id
+        "#,
+    )
+    .bind(cutoff)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let deleted_ids: Vec<Uuid> = vectors
+        .iter()
+        .chain(&episodes)
+        .chain(&facts)
+        .copied()
+        .collect();
+    let links_removed =
+        sqlx::query("DELETE FROM memory_graph_links WHERE from_id = ANY($1) OR to_id = ANY($1)")
+            .bind(&deleted_ids)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+    let events_removed = sqlx::query("DELETE FROM session_events WHERE memory_id = ANY($1)")
+        .bind(&vectors)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+    tx.commit().await?;
+
+    let report = GcReport {
+        retention_days,
+        memory_vectors: vectors.len() as u64,
+        episodic_traces: episodes.len() as u64,
+        semantic_facts: facts.len() as u64,
+        memory_graph_links: links_removed,
+        session_events: events_removed,
+    };
+    tracing::info!(?report, "Collected pruned rows");
+
+    Ok(report)
+}
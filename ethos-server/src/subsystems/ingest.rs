@@ -1,6 +1,10 @@
 use crate::subsystems::embedder;
+use ethos_core::compression;
+use ethos_core::EthosError;
+use serde::Serialize;
 use serde_json::Value;
-use sqlx::PgPool;
+use sha2::{Digest, Sha256};
+use sqlx::{Connection, PgConnection, PgPool};
 use uuid::Uuid;
 
 pub async fn ingest_payload(payload: Value, pool: &PgPool) -> anyhow::Result<()> {
@@ -8,91 +12,711 @@ pub async fn ingest_payload(payload: Value, pool: &PgPool) -> anyhow::Result<()>
     Ok(())
 }
 
+/// Result of ingesting one payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct IngestOutcome {
+    pub id: Uuid,
+    /// `false` when the payload's `idempotency_key` matched an existing
+    /// memory and `id` refers to that memory
+    pub queued: bool,
+}
+
+/// SHA-256 of `content` as lowercase hex, stored as `memory_vectors.content_hash`.
+pub fn content_hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+/// Replace a memory's content in place. The new `content_hash` no longer
+/// matches the hash its vector was embedded from, so the re-embed worker
+/// regenerates the vector. Returns `false` if no such memory exists.
+pub async fn update_memory_content(
+    pool: &PgPool,
+    id: Uuid,
+    content: &str,
+    storage: &ethos_core::config::StorageConfig,
+) -> anyhow::Result<bool> {
+    let (stored_content, compressed) = compression::encode_content(content, storage)?;
+    let updated = sqlx::query(
+        "UPDATE memory_vectors \
+         SET content = $2, content_compressed = $3, content_hash = $4, updated_at = NOW() \
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(stored_content)
+    .bind(compressed)
+    .bind(content_hash(content))
+    .execute(pool)
+    .await?;
+    Ok(updated.rows_affected() > 0)
+}
+
+/// Content limit used when ingesting without a config
+pub const DEFAULT_MAX_CONTENT_BYTES: usize = 1024 * 1024;
+
+/// Ingest one payload and queue it for embedding.
+///
+/// Content over `[service] max_content_bytes` is rejected with
+/// "content exceeds N bytes" before anything is written.
+///
+/// A payload carrying an `idempotency_key` already stored on a memory is not
+/// inserted again: the existing id is returned with `queued: false`.
 pub async fn ingest_payload_with_embedding(
     payload: Value,
     pool: &PgPool,
     config: Option<&ethos_core::EthosConfig>,
-) -> anyhow::Result<Uuid> {
-    // Extract data from payload
-    let content = payload["content"]
-        .as_str()
-        .ok_or_else(|| anyhow::anyhow!("Missing 'content'"))?;
-
-    let source = payload["source"]
-        .as_str()
-        .ok_or_else(|| anyhow::anyhow!("Missing 'source'"))?;
-
-    let metadata = payload["metadata"].as_object();
-
-    let session_id = metadata
-        .and_then(|m| m.get("session_id"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("default");
-
-    let agent_id = metadata
-        .and_then(|m| m.get("agent_id"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("ethos");
-
-    let author = metadata
-        .and_then(|m| m.get("author"))
-        .and_then(|v| v.as_str())
-        .unwrap_or(source);
-
-    // Mapping source to role
-    let role = match source {
-        "user" => "user",
-        "assistant" => "assistant",
-        "system" => "system",
-        "tool" => "tool",
-        _ => "user",
-    };
+) -> anyhow::Result<IngestOutcome> {
+    let parsed = ParsedPayload::from_value(&payload, max_content_bytes(config))?;
 
     // Atomic transaction
     let mut tx = pool.begin().await?;
+    let outcome = insert_payload(&mut tx, &parsed, config).await?;
+    tx.commit().await?;
+
+    if !outcome.queued {
+        tracing::info!(
+            memory_id = %outcome.id,
+            "Idempotency key already ingested, returning existing memory"
+        );
+        return Ok(outcome);
+    }
+
+    tracing::info!(
+        "Successfully ingested payload into DB, memory_id: {}",
+        outcome.id
+    );
+
+    // Spawn embedding task in background (non-blocking)
+    if let Some(cfg) = config {
+        embedder::spawn_embed_task(outcome.id, pool.clone(), cfg);
+    }
+
+    Ok(outcome)
+}
+
+/// Chunk length and overlap used when ingesting without a config
+pub const DEFAULT_CHUNK_SIZE: usize = 2000;
+pub const DEFAULT_CHUNK_OVERLAP: usize = 200;
+
+/// Result of ingesting one payload with `"chunk": true`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChunkedIngestOutcome {
+    /// Shared by every chunk as `metadata.document_id`
+    pub document_id: Uuid,
+    /// Chunk memory ids in document order
+    pub ids: Vec<Uuid>,
+    /// `false` when the payload's `idempotency_key` matched an earlier
+    /// ingest and `ids` refer to those memories
+    pub queued: bool,
+}
+
+/// Ingest a long document as several overlapping memories.
+///
+/// The content is split by [`chunk_content`] using `[service] chunk_size`
+/// and `chunk_overlap`. Each chunk becomes its own `memory_vectors` row with
+/// the payload's metadata plus `document_id`, `chunk_index` and
+/// `chunk_count`, and is embedded separately; the session event records the
+/// whole document once. An `idempotency_key` is stored on the first chunk.
+pub async fn ingest_chunked(
+    payload: Value,
+    pool: &PgPool,
+    config: Option<&ethos_core::EthosConfig>,
+) -> anyhow::Result<ChunkedIngestOutcome> {
+    let parsed = ParsedPayload::from_value(&payload, max_content_bytes(config))?;
+    let (size, overlap) = config
+        .map(|c| (c.service.chunk_size, c.service.chunk_overlap))
+        .unwrap_or((DEFAULT_CHUNK_SIZE, DEFAULT_CHUNK_OVERLAP));
+    let chunks = chunk_content(parsed.content, size, overlap);
+
+    let mut tx = pool.begin().await?;
+    let outcome = insert_chunks(&mut tx, &parsed, &chunks, config).await?;
+    tx.commit().await?;
+
+    if !outcome.queued {
+        tracing::info!(
+            document_id = %outcome.document_id,
+            "Idempotency key already ingested, returning existing chunks"
+        );
+        return Ok(outcome);
+    }
+
+    tracing::info!(
+        document_id = %outcome.document_id,
+        chunks = outcome.ids.len(),
+        "Successfully ingested chunked document into DB"
+    );
+
+    if let Some(cfg) = config {
+        for id in &outcome.ids {
+            embedder::spawn_embed_task(*id, pool.clone(), cfg);
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Split `content` into chunks of at most `size` characters, each repeating
+/// up to `overlap` characters from the end of the previous one.
+///
+/// A chunk ends at its last paragraph break, else its last whitespace, as
+/// long as that keeps it at least half of `size`, so paragraphs and words
+/// stay whole where possible. Content of at most `size` characters (or a
+/// `size` of 0) is a single chunk.
+pub fn chunk_content(content: &str, size: usize, overlap: usize) -> Vec<&str> {
+    // Byte offset of every char, plus the end of the string
+    let offsets: Vec<usize> = content
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(content.len()))
+        .collect();
+    let char_count = offsets.len() - 1;
+    if size == 0 || char_count <= size {
+        return vec![content];
+    }
+    let overlap = overlap.min(size / 2);
+    let char_at = |byte: usize| offsets.partition_point(|&b| b < byte);
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    loop {
+        let hard_end = (start + size).min(char_count);
+        if hard_end == char_count {
+            chunks.push(&content[offsets[start]..]);
+            break;
+        }
+
+        let window = &content[offsets[start]..offsets[hard_end]];
+        let min_cut = offsets[start + size / 2] - offsets[start];
+        let cut = window
+            .rfind("\n\n")
+            .filter(|&i| i >= min_cut)
+            .or_else(|| window.rfind(char::is_whitespace).filter(|&i| i >= min_cut))
+            .unwrap_or(window.len());
+        let end_byte = offsets[start] + cut;
+        chunks.push(&content[offsets[start]..end_byte]);
+
+        // Start the overlap on a word boundary
+        let mut next = char_at(end_byte).saturating_sub(overlap);
+        if let Some(ws) = content[offsets[next]..end_byte].find(char::is_whitespace) {
+            next = char_at(offsets[next] + ws) + 1;
+        }
+        start = next.max(start + 1);
+    }
+
+    let chunks: Vec<&str> = chunks
+        .into_iter()
+        .map(str::trim)
+        .filter(|chunk| !chunk.is_empty())
+        .collect();
+    if chunks.is_empty() {
+        vec![content]
+    } else {
+        chunks
+    }
+}
+
+/// A batch item that could not be ingested.
+#[derive(Debug, Serialize)]
+pub struct BatchFailure {
+    /// Position of the item in the submitted array
+    pub index: usize,
+    pub error: String,
+}
+
+/// Outcome of [`ingest_batch`].
+#[derive(Debug, Serialize)]
+pub struct BatchIngestReport {
+    /// Ids of the ingested memories, in submission order. Items whose
+    /// `idempotency_key` was already ingested report the existing id.
+    pub ids: Vec<Uuid>,
+    /// Newly inserted memories (excludes repeated idempotency keys)
+    pub queued: usize,
+    pub failed: Vec<BatchFailure>,
+}
+
+/// Ingest many payloads in one transaction.
+///
+/// Each item gets its own savepoint, so an invalid or rejected item is
+/// reported in `failed` without rolling back the rest of the batch.
+/// Embedding tasks are spawned once the batch commits.
+pub async fn ingest_batch(
+    payloads: Vec<Value>,
+    pool: &PgPool,
+    config: Option<&ethos_core::EthosConfig>,
+) -> anyhow::Result<BatchIngestReport> {
+    let mut ids = Vec::new();
+    let mut new_ids = Vec::new();
+    let mut failed = Vec::new();
+
+    let max_content_bytes = max_content_bytes(config);
+    let mut tx = pool.begin().await?;
+    for (index, payload) in payloads.iter().enumerate() {
+        let parsed = match ParsedPayload::from_value(payload, max_content_bytes) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                failed.push(BatchFailure {
+                    index,
+                    error: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        let mut savepoint = tx.begin().await?;
+        match insert_payload(&mut savepoint, &parsed, config).await {
+            Ok(outcome) => {
+                savepoint.commit().await?;
+                ids.push(outcome.id);
+                if outcome.queued {
+                    new_ids.push(outcome.id);
+                }
+            }
+            Err(e) => {
+                savepoint.rollback().await?;
+                failed.push(BatchFailure {
+                    index,
+                    error: e.to_string(),
+                });
+            }
+        }
+    }
+    tx.commit().await?;
+
+    tracing::info!(
+        ingested = new_ids.len(),
+        repeated = ids.len() - new_ids.len(),
+        failed = failed.len(),
+        "Successfully ingested batch into DB"
+    );
+
+    if let Some(cfg) = config {
+        for id in &new_ids {
+            embedder::spawn_embed_task(*id, pool.clone(), cfg);
+        }
+    }
+
+    Ok(BatchIngestReport {
+        queued: new_ids.len(),
+        ids,
+        failed,
+    })
+}
+
+fn max_content_bytes(config: Option<&ethos_core::EthosConfig>) -> usize {
+    config
+        .map(|c| c.service.max_content_bytes)
+        .unwrap_or(DEFAULT_MAX_CONTENT_BYTES)
+}
 
-    // 1. Insert into session_events
+/// Fields extracted from an ingest payload.
+struct ParsedPayload<'a> {
+    content: &'a str,
+    metadata: Option<&'a serde_json::Map<String, Value>>,
+    session_id: &'a str,
+    agent_id: &'a str,
+    author: &'a str,
+    role: &'static str,
+    /// Client-chosen key that makes retries of this payload a no-op
+    idempotency_key: Option<&'a str>,
+}
+
+impl<'a> ParsedPayload<'a> {
+    /// `max_content_bytes` of 0 accepts any length.
+    fn from_value(payload: &'a Value, max_content_bytes: usize) -> anyhow::Result<Self> {
+        // Extract data from payload
+        let content = payload["content"]
+            .as_str()
+            .ok_or_else(|| EthosError::BadRequest("Missing 'content'".to_string()))?;
+
+        if max_content_bytes > 0 && content.len() > max_content_bytes {
+            return Err(EthosError::PayloadTooLarge(format!(
+                "content exceeds {} bytes",
+                max_content_bytes
+            ))
+            .into());
+        }
+
+        let source = payload["source"]
+            .as_str()
+            .ok_or_else(|| EthosError::BadRequest("Missing 'source'".to_string()))?;
+
+        let metadata = payload["metadata"].as_object();
+
+        let idempotency_key = payload["idempotency_key"]
+            .as_str()
+            .map(str::trim)
+            .filter(|key| !key.is_empty());
+
+        let session_id = metadata
+            .and_then(|m| m.get("session_id"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("default");
+
+        let agent_id = metadata
+            .and_then(|m| m.get("agent_id"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("ethos");
+
+        let author = metadata
+            .and_then(|m| m.get("author"))
+            .and_then(|v| v.as_str())
+            .unwrap_or(source);
+
+        // Mapping source to role
+        let role = match source {
+            "user" => "user",
+            "assistant" => "assistant",
+            "system" => "system",
+            "tool" => "tool",
+            _ => "user",
+        };
+
+        Ok(Self {
+            content,
+            metadata,
+            session_id,
+            agent_id,
+            author,
+            role,
+            idempotency_key,
+        })
+    }
+}
+
+/// Write one payload to `session_events` and `memory_vectors`, returning the
+/// memory id. A repeated idempotency key writes nothing and returns the
+/// existing id with `queued: false`.
+async fn insert_payload(
+    conn: &mut PgConnection,
+    parsed: &ParsedPayload<'_>,
+    config: Option<&ethos_core::EthosConfig>,
+) -> anyhow::Result<IngestOutcome> {
+    // Compress large content at rest if enabled ([storage] compress_content)
+    let (stored_content, compressed) = match config {
+        Some(cfg) => compression::encode_content(parsed.content, &cfg.storage)?,
+        None => (parsed.content.to_string(), false),
+    };
+    let metadata = serde_json::to_value(parsed.metadata).unwrap_or(serde_json::json!({}));
+    let emotional_tone = estimate_emotional_tone(parsed.content);
+
+    // 1. Insert into memory_vectors; a repeated idempotency key conflicts on the
+    //    unique index and yields no row
+    let inserted = sqlx::query!(
+        r#"
+        INSERT INTO memory_vectors (content, source, metadata, content_compressed, emotional_tone, idempotency_key, content_hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (idempotency_key) WHERE idempotency_key IS NOT NULL DO NOTHING
+        RETURNING id
+        "#,
+        stored_content,
+        parsed.author,
+        metadata,
+        compressed,
+        emotional_tone,
+        parsed.idempotency_key,
+        content_hash(parsed.content)
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let id = match inserted {
+        Some(row) => row.id,
+        None => {
+            let existing = sqlx::query_scalar!(
+                "SELECT id FROM memory_vectors WHERE idempotency_key = $1",
+                parsed.idempotency_key
+            )
+            .fetch_one(&mut *conn)
+            .await?;
+            return Ok(IngestOutcome {
+                id: existing,
+                queued: false,
+            });
+        }
+    };
+
+    // 2. Insert into session_events
     sqlx::query!(
         r#"
-        INSERT INTO session_events (session_id, agent_id, role, content, metadata)
-        VALUES ($1, $2, $3, $4, $5)
+        INSERT INTO session_events (session_id, agent_id, role, content, metadata, memory_id)
+        VALUES ($1, $2, $3, $4, $5, $6)
         "#,
-        session_id,
-        agent_id,
-        role,
-        content,
-        serde_json::to_value(metadata).unwrap_or(serde_json::json!({}))
+        parsed.session_id,
+        parsed.agent_id,
+        parsed.role,
+        parsed.content,
+        metadata,
+        id
     )
-    .execute(&mut *tx)
+    .execute(&mut *conn)
     .await?;
 
-    // 2. Insert into memory_vectors and return the ID
-    let row = sqlx::query!(
+    Ok(IngestOutcome { id, queued: true })
+}
+
+/// Write each chunk to `memory_vectors` and the whole document to
+/// `session_events`, linked to the first chunk. A repeated idempotency key writes nothing and returns
+/// the chunks stored under it with `queued: false`.
+async fn insert_chunks(
+    conn: &mut PgConnection,
+    parsed: &ParsedPayload<'_>,
+    chunks: &[&str],
+    config: Option<&ethos_core::EthosConfig>,
+) -> anyhow::Result<ChunkedIngestOutcome> {
+    let document_id = Uuid::new_v4();
+    let mut ids = Vec::with_capacity(chunks.len());
+
+    for (index, chunk) in chunks.iter().enumerate() {
+        let (stored_content, compressed) = match config {
+            Some(cfg) => compression::encode_content(chunk, &cfg.storage)?,
+            None => (chunk.to_string(), false),
+        };
+        let mut metadata = parsed.metadata.cloned().unwrap_or_default();
+        metadata.insert("document_id".into(), document_id.to_string().into());
+        metadata.insert("chunk_index".into(), index.into());
+        metadata.insert("chunk_count".into(), chunks.len().into());
+        let idempotency_key = parsed.idempotency_key.filter(|_| index == 0);
+
+        let inserted: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            INSERT INTO memory_vectors (content, source, metadata, content_compressed, emotional_tone, idempotency_key, content_hash)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (idempotency_key) WHERE idempotency_key IS NOT NULL DO NOTHING
+            RETURNING id
+            "#,
+        )
+        .bind(stored_content)
+        .bind(parsed.author)
+        .bind(Value::Object(metadata))
+        .bind(compressed)
+        .bind(estimate_emotional_tone(chunk))
+        .bind(idempotency_key)
+        .bind(content_hash(chunk))
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        match inserted {
+            Some(id) => ids.push(id),
+            // Only the first chunk carries the key, so nothing was written yet
+            None => return existing_chunks(conn, parsed.idempotency_key).await,
+        }
+    }
+
+    let metadata = serde_json::to_value(parsed.metadata).unwrap_or(serde_json::json!({}));
+    sqlx::query(
         r#"
-        INSERT INTO memory_vectors (content, source, metadata)
-        VALUES ($1, $2, $3)
-        RETURNING id
+        INSERT INTO session_events (session_id, agent_id, role, content, metadata, memory_id)
+        VALUES ($1, $2, $3, $4, $5, $6)
         "#,
-        content,
-        author,
-        serde_json::to_value(metadata).unwrap_or(serde_json::json!({}))
     )
-    .fetch_one(&mut *tx)
+    .bind(parsed.session_id)
+    .bind(parsed.agent_id)
+    .bind(parsed.role)
+    .bind(parsed.content)
+    .bind(metadata)
+    .bind(ids[0])
+    .execute(&mut *conn)
     .await?;
 
-    let memory_id = row.id;
+    Ok(ChunkedIngestOutcome {
+        document_id,
+        ids,
+        queued: true,
+    })
+}
 
-    tx.commit().await?;
+/// The chunks of the document first ingested under `idempotency_key`. A key
+/// first used without chunking reports its single memory as the document.
+async fn existing_chunks(
+    conn: &mut PgConnection,
+    idempotency_key: Option<&str>,
+) -> anyhow::Result<ChunkedIngestOutcome> {
+    let (id, document_id): (Uuid, Option<String>) = sqlx::query_as(
+        "SELECT id, metadata->>'document_id' FROM memory_vectors WHERE idempotency_key = $1",
+    )
+    .bind(idempotency_key)
+    .fetch_one(&mut *conn)
+    .await?;
 
-    tracing::info!(
-        "Successfully ingested payload into DB, memory_id: {}",
-        memory_id
-    );
+    let Some(document_id) = document_id.and_then(|d| d.parse::<Uuid>().ok()) else {
+        return Ok(ChunkedIngestOutcome {
+            document_id: id,
+            ids: vec![id],
+            queued: false,
+        });
+    };
 
-    // 3. Spawn embedding task in background (non-blocking)
-    if let Some(cfg) = config {
-        embedder::spawn_embed_task(memory_id, pool.clone(), cfg);
+    let ids = sqlx::query_scalar(
+        r#"
+        SELECT id FROM memory_vectors
+        WHERE metadata->>'document_id' = $1
+        ORDER BY (metadata->>'chunk_index')::int
+        "#,
+    )
+    .bind(document_id.to_string())
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(ChunkedIngestOutcome {
+        document_id,
+        ids,
+        queued: false,
+    })
+}
+
+/// Strong-affect words and their weight toward the emotional tone score.
+const AFFECT_LEXICON: &[(&str, f64)] = &[
+    ("love", 0.5),
+    ("hate", 0.5),
+    ("furious", 0.5),
+    ("thrilled", 0.5),
+    ("devastated", 0.5),
+    ("terrified", 0.5),
+    ("ecstatic", 0.5),
+    ("heartbroken", 0.5),
+    ("amazing", 0.4),
+    ("awful", 0.4),
+    ("terrible", 0.4),
+    ("horrible", 0.4),
+    ("wonderful", 0.4),
+    ("fantastic", 0.4),
+    ("angry", 0.4),
+    ("scared", 0.4),
+    ("afraid", 0.4),
+    ("excited", 0.4),
+    ("disgusted", 0.4),
+    ("hurt", 0.3),
+    ("sad", 0.3),
+    ("upset", 0.3),
+    ("happy", 0.3),
+    ("proud", 0.3),
+    ("worried", 0.3),
+    ("frustrated", 0.3),
+    ("annoyed", 0.2),
+    ("glad", 0.2),
+    ("sorry", 0.2),
+];
+
+/// Words that amplify whatever affect surrounds them.
+const INTENSIFIERS: &[&str] = &[
+    "absolutely",
+    "totally",
+    "really",
+    "extremely",
+    "incredibly",
+    "so",
+    "never",
+    "always",
+];
+
+/// Estimate how emotionally charged `content` is, from 0.0 (neutral) to 1.0.
+///
+/// Lexicon-based and deterministic: strong-affect words carry most of the
+/// weight, intensifiers and exclamation marks only count alongside them.
+/// Feeds `E` in the decay formula via `memory_vectors.emotional_tone`.
+pub fn estimate_emotional_tone(content: &str) -> f64 {
+    let mut affect = 0.0;
+    let mut intensity = 0.0;
+
+    for word in content
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+    {
+        let word = word.to_lowercase();
+        if let Some((_, weight)) = AFFECT_LEXICON
+            .iter()
+            .find(|(stem, _)| word.starts_with(stem))
+        {
+            affect += weight;
+        } else if INTENSIFIERS.contains(&word.as_str()) {
+            intensity += 0.15;
+        }
+    }
+
+    if affect == 0.0 {
+        return 0.0;
     }
 
-    Ok(memory_id)
+    let exclamations = content.matches('!').count().min(3) as f64;
+    (affect + intensity + 0.1 * exclamations).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ========================================================================
+    // TEST 1: High-affect text scores above neutral text
+    // ========================================================================
+    #[test]
+    fn test_high_affect_scores_higher_than_neutral() {
+        let high = estimate_emotional_tone("I absolutely love this!");
+        let neutral = estimate_emotional_tone("The meeting moved to Tuesday at 3pm.");
+
+        assert!(high > neutral, "high={} neutral={}", high, neutral);
+        assert!(high >= 0.5);
+        assert_eq!(neutral, 0.0);
+    }
+
+    // ========================================================================
+    // TEST 2: Score stays within [0, 1] and is deterministic
+    // ========================================================================
+    #[test]
+    fn test_tone_is_clamped_and_deterministic() {
+        let text = "I HATE this, I'm furious, devastated and terrified!!!!!";
+        let tone = estimate_emotional_tone(text);
+
+        assert_eq!(tone, 1.0);
+        assert_eq!(tone, estimate_emotional_tone(text));
+        assert_eq!(estimate_emotional_tone(""), 0.0);
+    }
+
+    // ========================================================================
+    // TEST 3: Intensifiers and punctuation alone are not emotional
+    // ========================================================================
+    #[test]
+    fn test_intensifiers_without_affect_are_neutral() {
+        assert_eq!(estimate_emotional_tone("It is really always so late!"), 0.0);
+        assert!(estimate_emotional_tone("I'm so happy") > estimate_emotional_tone("I'm happy"));
+    }
+
+    // ========================================================================
+    // TEST 4: Short content is one chunk; long content splits on paragraphs
+    // ========================================================================
+    #[test]
+    fn test_chunk_content_prefers_paragraph_breaks() {
+        assert_eq!(chunk_content("short note", 100, 10), vec!["short note"]);
+        assert_eq!(chunk_content("short note", 0, 10), vec!["short note"]);
+
+        let paragraph = "word ".repeat(15).trim_end().to_string(); // 74 chars
+        let doc = [paragraph.as_str(); 4].join("\n\n");
+        let chunks = chunk_content(&doc, 160, 20);
+
+        assert!(chunks.len() > 1, "{chunks:?}");
+        assert!(chunks.iter().all(|c| c.chars().count() <= 160));
+        assert!(chunks
+            .iter()
+            .all(|c| c.starts_with("word") && c.ends_with("word")));
+        // Consecutive chunks overlap
+        assert!(chunks[1].starts_with("word\n\n") || chunks[1].starts_with("word word"));
+    }
+
+    // ========================================================================
+    // TEST 5: Chunking covers the whole text and respects char boundaries
+    // ========================================================================
+    #[test]
+    fn test_chunk_content_covers_text_without_splitting_chars() {
+        let doc = "héllo wörld ünïcode ".repeat(50);
+        let chunks = chunk_content(&doc, 64, 16);
+
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 64));
+        assert!(doc.trim_end().ends_with(chunks.last().unwrap()));
+        let covered: usize = chunks.iter().map(|c| c.chars().count()).sum();
+        assert!(covered >= doc.trim_end().chars().count());
+
+        let unbroken = "x".repeat(250);
+        let chunks = chunk_content(&unbroken, 100, 10);
+        assert_eq!(chunks[0].len(), 100);
+        assert_eq!(chunks.len(), 3);
+    }
 }
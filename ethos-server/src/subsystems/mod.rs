@@ -1,7 +1,11 @@
+pub mod changefeed;
 pub mod consolidate;
 pub mod decay;
 pub mod embedder;
+pub mod export;
+pub mod forget;
 pub mod ingest;
 pub mod linker;
 pub mod reembed;
 pub mod retrieve;
+pub mod sessions;
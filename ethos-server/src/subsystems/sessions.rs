@@ -0,0 +1,77 @@
+//! Sessions subsystem — lists conversations and their activity
+//!
+//! Backs `EthosRequest::ListSessions` / `GET /sessions`. Each `sessions` row is
+//! joined with its `session_events` (matched on `session_key`) and its
+//! `episodic_traces`, so operators can see which conversations exist, how
+//! busy they were, and how much of them has been consolidated.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Default and maximum number of sessions returned by `list_sessions`
+pub const DEFAULT_SESSIONS_LIMIT: i64 = 50;
+pub const MAX_SESSIONS_LIMIT: i64 = 500;
+
+/// A session with its aggregated activity.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct SessionSummary {
+    pub id: Uuid,
+    pub session_key: String,
+    pub agent_id: String,
+    pub event_count: i64,
+    pub first_event: Option<DateTime<Utc>>,
+    pub last_event: Option<DateTime<Utc>>,
+    /// Episodes of this session already consolidated into facts
+    pub consolidated_count: i64,
+}
+
+/// List sessions, most recently active first.
+///
+/// `agent_id` keeps one agent's sessions; `active_since` keeps sessions whose
+/// latest event (or `last_active_at`, whichever is later) is at or after it.
+pub async fn list_sessions(
+    pool: &PgPool,
+    agent_id: Option<String>,
+    active_since: Option<DateTime<Utc>>,
+    limit: Option<u32>,
+) -> Result<Vec<SessionSummary>> {
+    let limit = limit
+        .map(|l| (l as i64).clamp(1, MAX_SESSIONS_LIMIT))
+        .unwrap_or(DEFAULT_SESSIONS_LIMIT);
+
+    let sessions = sqlx::query_as::<_, SessionSummary>(
+        r#"
+        SELECT s.id, s.session_key, s.agent_id,
+               COALESCE(ev.event_count, 0) AS event_count,
+               ev.first_event, ev.last_event,
+               COALESCE(ep.consolidated_count, 0) AS consolidated_count
+        FROM sessions s
+        LEFT JOIN LATERAL (
+            SELECT COUNT(*) AS event_count,
+                   MIN(created_at) AS first_event,
+                   MAX(created_at) AS last_event
+            FROM session_events
+            WHERE session_id = s.session_key
+        ) ev ON true
+        LEFT JOIN LATERAL (
+            SELECT COUNT(*) AS consolidated_count
+            FROM episodic_traces
+            WHERE session_id = s.id AND consolidated_at IS NOT NULL
+        ) ep ON true
+        WHERE ($1::text IS NULL OR s.agent_id = $1)
+          AND ($2::timestamptz IS NULL OR GREATEST(s.last_active_at, ev.last_event) >= $2)
+        ORDER BY GREATEST(s.last_active_at, ev.last_event) DESC, s.id
+        LIMIT $3
+        "#,
+    )
+    .bind(agent_id.as_deref())
+    .bind(active_since)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(sessions)
+}
@@ -4,12 +4,14 @@
 //! 1. Manual Embed trigger via IPC populates vector
 //! 2. Vector IS NULL stays on API failure
 
-use ethos_core::embeddings::{EmbeddingConfig, GeminiEmbeddingClient, GEMINI_DIMENSIONS};
+use ethos_core::embeddings::{
+    CircuitBreakerConfig, EmbeddingConfig, GeminiEmbeddingClient, DEFAULT_MAX_INPUT_CHARS,
+    GEMINI_DIMENSIONS,
+};
 use ethos_server::subsystems::embedder;
 use pgvector::Vector;
 use serde_json::json;
 use sqlx::PgPool;
-use uuid::Uuid;
 use wiremock::matchers::method;
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -29,6 +31,11 @@ fn create_test_client(mock_server: &MockServer) -> GeminiEmbeddingClient {
         dimensions: GEMINI_DIMENSIONS,
         max_retries: 1,
         retry_delay_ms: 10,
+        max_delay_ms: 10_000,
+        jitter: true,
+        normalize: false,
+        max_input_chars: DEFAULT_MAX_INPUT_CHARS,
+        breaker: CircuitBreakerConfig::default(),
     };
 
     GeminiEmbeddingClient::with_base_url(config, mock_server.uri())
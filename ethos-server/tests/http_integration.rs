@@ -7,8 +7,9 @@
 use axum::http::StatusCode;
 use ethos_core::EthosConfig;
 use ethos_server::http::{
-    build_router, consolidate_inner, health_inner, ingest_inner, search_inner, ConsolidateRequest,
-    HttpState, SearchRequest,
+    build_router, consolidate_inner, delete_inner, health_inner, ingest_batch_inner, ingest_inner,
+    resolve_inner, search_inner, sessions_inner, ApiError, ConsolidateRequest, DeleteQuery,
+    HttpState, ResolveRequest, SearchRequest, SessionsQuery,
 };
 use pgvector::Vector;
 use serde_json::json;
@@ -29,12 +30,118 @@ async fn make_state() -> Option<(PgPool, EthosConfig)> {
     Some((pool, config))
 }
 
+/// Status and body the handler would send for an inner function's result
+fn into_parts(result: Result<serde_json::Value, ApiError>) -> (StatusCode, serde_json::Value) {
+    match result {
+        Ok(body) => (StatusCode::OK, body),
+        Err(e) => (e.status(), e.body()),
+    }
+}
+
 /// Make Arc<HttpState> for router tests
 async fn make_http_state() -> Option<Arc<HttpState>> {
     let (pool, config) = make_state().await?;
     Some(Arc::new(HttpState { pool, config }))
 }
 
+/// State built from the example config and a lazy pool, so middleware tests
+/// run without ethos.toml or a live database.
+fn example_state(configure: impl FnOnce(&mut EthosConfig)) -> Arc<HttpState> {
+    let path = std::env::temp_dir().join(format!("ethos-auth-test-{}.toml", uuid::Uuid::new_v4()));
+    std::fs::write(&path, include_str!("../../ethos.toml.example")).expect("write temp config");
+    let mut config = EthosConfig::load(path.to_str().unwrap()).expect("example config should load");
+    std::fs::remove_file(&path).ok();
+    configure(&mut config);
+
+    let pool = PgPool::connect_lazy(DATABASE_URL).expect("lazy pool");
+    Arc::new(HttpState { pool, config })
+}
+
+/// POST an (invalid, empty-query) search so an authorised request stops at validation
+fn search_request(authorization: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri("/search")
+        .header("content-type", "application/json");
+    if let Some(value) = authorization {
+        builder = builder.header("authorization", value);
+    }
+    builder.body(Body::from(r#"{"query": ""}"#)).unwrap()
+}
+
+/// Insert a memory vector linked to a neighbour in both directions; returns (memory, neighbour)
+async fn insert_linked_memory(pool: &PgPool) -> (uuid::Uuid, uuid::Uuid) {
+    let neighbour = uuid::Uuid::new_v4();
+    let id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO memory_vectors (content, source, source_type, source_id) VALUES ('forget me', 'test-delete', 'episode', $1) RETURNING id",
+    )
+    .bind(uuid::Uuid::new_v4())
+    .fetch_one(pool)
+    .await
+    .expect("insert memory vector");
+
+    for (from, to) in [(id, neighbour), (neighbour, id)] {
+        sqlx::query(
+            "INSERT INTO memory_graph_links (from_type, from_id, to_type, to_id, relation, weight) VALUES ('episode', $1, 'episode', $2, 'similarity', 0.8)",
+        )
+        .bind(from)
+        .bind(to)
+        .execute(pool)
+        .await
+        .expect("insert link");
+    }
+    (id, neighbour)
+}
+
+async fn link_count(pool: &PgPool, id: uuid::Uuid) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM memory_graph_links WHERE from_id = $1 OR to_id = $1")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+/// Insert two contradicting facts, both flagged for review like the consolidation engine does
+async fn insert_flagged_pair(pool: &PgPool, subject: &str) -> (uuid::Uuid, uuid::Uuid) {
+    let mut ids = Vec::new();
+    for object in ["old value", "new value"] {
+        let row: (uuid::Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO semantic_facts (kind, statement, subject, predicate, object, flagged_for_review)
+            VALUES ('fact', $1, $2, 'value', $3, true)
+            RETURNING id
+            "#,
+        )
+        .bind(format!("{} is {}", subject, object))
+        .bind(subject)
+        .bind(object)
+        .fetch_one(pool)
+        .await
+        .expect("Failed to insert flagged fact");
+        ids.push(row.0);
+    }
+    (ids[0], ids[1])
+}
+
+/// Read (flagged_for_review, pruned, superseded_by) for a fact
+async fn fact_state(pool: &PgPool, id: uuid::Uuid) -> (bool, bool, Option<uuid::Uuid>) {
+    sqlx::query_as(
+        "SELECT flagged_for_review, pruned, superseded_by FROM semantic_facts WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_one(pool)
+    .await
+    .expect("Fact should exist")
+}
+
+async fn delete_facts_by_subject(pool: &PgPool, subject: &str) {
+    sqlx::query("DELETE FROM semantic_facts WHERE subject = $1")
+        .bind(subject)
+        .execute(pool)
+        .await
+        .ok();
+}
+
 // ===========================================================================
 // TEST 1: GET /health — server starts, responds 200 with expected fields
 // ===========================================================================
@@ -48,7 +155,7 @@ async fn test_http_server_starts() {
         }
     };
 
-    let (status, body) = health_inner(&pool, "/tmp/ethos.sock").await;
+    let (status, body) = health_inner(&pool, "/tmp/ethos.sock", 0).await;
     assert_eq!(status, StatusCode::OK, "Health check should return 200");
     assert_eq!(body["status"], "healthy", "status must be 'healthy'");
     assert!(body["version"].is_string(), "version must be present");
@@ -60,7 +167,7 @@ async fn test_http_server_starts() {
 }
 
 // ===========================================================================
-// TEST 2: GET /version via oneshot — returns version and protocol
+// TEST 2: GET /version via oneshot — returns version, protocol and build info
 // ===========================================================================
 #[tokio::test]
 async fn test_version_endpoint_integration() {
@@ -90,6 +197,10 @@ async fn test_version_endpoint_integration() {
 
     assert!(json["version"].is_string());
     assert_eq!(json["protocol"], "ethos/1");
+    assert!(json["git_sha"].is_string());
+    assert!(json["build_timestamp"].is_string());
+    assert!(json["backend"].is_string());
+    assert!(json["embedding_dimensions"].is_u64());
 }
 
 // ===========================================================================
@@ -123,7 +234,7 @@ async fn test_ingest_via_http() {
         }
     });
 
-    let (status, body) = ingest_inner(&pool, &config, payload).await;
+    let (status, body) = into_parts(ingest_inner(&pool, &config, payload).await);
 
     assert_eq!(
         status,
@@ -171,18 +282,35 @@ async fn test_search_roundtrip_http() {
     let req = SearchRequest {
         query: Some("memory search roundtrip integration test".to_string()),
         limit: Some(5),
-        use_spreading: false,
+        offset: None,
+        use_spreading: Some(false),
+        diversify: false,
+        group_by_document: false,
+        explain: false,
+        trace: false,
+        expand: false,
+        task_type: None,
+        search_dimensions: None,
+        include_related_facts: false,
         min_score: None,
+        source_filter: None,
+        metadata_contains: None,
+        created_after: None,
+        created_before: None,
+        topics: None,
+        include_pruned: false,
         resource_id: None,
         thread_id: None,
         agent_id: None,
     };
 
-    let (status, body) = search_inner(&pool, &config, req).await;
+    let (status, body) = into_parts(search_inner(&pool, &config, req).await);
 
-    // Either 200 (success, results or empty) or 500 (embedding API unavailable)
+    // Either 200 (success, results or empty) or 500/503 (embedding API unavailable)
     assert!(
-        status == StatusCode::OK || status == StatusCode::INTERNAL_SERVER_ERROR,
+        status == StatusCode::OK
+            || status == StatusCode::INTERNAL_SERVER_ERROR
+            || status == StatusCode::SERVICE_UNAVAILABLE,
         "Unexpected status code: {}",
         status
     );
@@ -211,14 +339,29 @@ async fn test_search_empty_query_http() {
     let req = SearchRequest {
         query: Some("".to_string()),
         limit: None,
-        use_spreading: false,
+        offset: None,
+        use_spreading: Some(false),
+        diversify: false,
+        group_by_document: false,
+        explain: false,
+        trace: false,
+        expand: false,
+        task_type: None,
+        search_dimensions: None,
+        include_related_facts: false,
         min_score: None,
+        source_filter: None,
+        metadata_contains: None,
+        created_after: None,
+        created_before: None,
+        topics: None,
+        include_pruned: false,
         resource_id: None,
         thread_id: None,
         agent_id: None,
     };
 
-    let (status, body) = search_inner(&pool, &config, req).await;
+    let (status, body) = into_parts(search_inner(&pool, &config, req).await);
     assert_eq!(
         status,
         StatusCode::BAD_REQUEST,
@@ -243,14 +386,29 @@ async fn test_search_no_query_field_http() {
     let req = SearchRequest {
         query: None,
         limit: Some(10),
-        use_spreading: false,
+        offset: None,
+        use_spreading: Some(false),
+        diversify: false,
+        group_by_document: false,
+        explain: false,
+        trace: false,
+        expand: false,
+        task_type: None,
+        search_dimensions: None,
+        include_related_facts: false,
         min_score: None,
+        source_filter: None,
+        metadata_contains: None,
+        created_after: None,
+        created_before: None,
+        topics: None,
+        include_pruned: false,
         resource_id: None,
         thread_id: None,
         agent_id: None,
     };
 
-    let (status, body) = search_inner(&pool, &config, req).await;
+    let (status, body) = into_parts(search_inner(&pool, &config, req).await);
     assert_eq!(
         status,
         StatusCode::BAD_REQUEST,
@@ -272,7 +430,7 @@ async fn test_health_response_structure() {
         }
     };
 
-    let (status, body) = health_inner(&pool, "/tmp/test.sock").await;
+    let (status, body) = health_inner(&pool, "/tmp/test.sock", 0).await;
 
     assert!(
         status == StatusCode::OK || status == StatusCode::SERVICE_UNAVAILABLE,
@@ -303,9 +461,11 @@ async fn test_consolidate_inner_integration() {
         reason: Some("Story 011 integration test".to_string()),
     };
 
-    let (status, body) = consolidate_inner(&pool, &config, req).await;
+    let (status, body) = into_parts(consolidate_inner(&pool, &config, req).await);
     assert!(
-        status == StatusCode::OK || status == StatusCode::INTERNAL_SERVER_ERROR,
+        status == StatusCode::OK
+            || status == StatusCode::INTERNAL_SERVER_ERROR
+            || status == StatusCode::SERVICE_UNAVAILABLE,
         "Unexpected status: {}",
         status
     );
@@ -388,16 +548,33 @@ async fn test_search_with_spreading_http() {
     let req = SearchRequest {
         query: Some("spreading activation test".to_string()),
         limit: Some(3),
-        use_spreading: true,
+        offset: None,
+        use_spreading: Some(true),
+        diversify: false,
+        group_by_document: false,
+        explain: false,
+        trace: false,
+        expand: false,
+        task_type: None,
+        search_dimensions: None,
+        include_related_facts: false,
         min_score: None,
+        source_filter: None,
+        metadata_contains: None,
+        created_after: None,
+        created_before: None,
+        topics: None,
+        include_pruned: false,
         resource_id: None,
         thread_id: None,
         agent_id: None,
     };
 
-    let (status, body) = search_inner(&pool, &config, req).await;
+    let (status, body) = into_parts(search_inner(&pool, &config, req).await);
     assert!(
-        status == StatusCode::OK || status == StatusCode::INTERNAL_SERVER_ERROR,
+        status == StatusCode::OK
+            || status == StatusCode::INTERNAL_SERVER_ERROR
+            || status == StatusCode::SERVICE_UNAVAILABLE,
         "Unexpected status: {}",
         status
     );
@@ -504,7 +681,7 @@ async fn test_search_scope_filters_via_http_camel_case_json() {
     let body_json: serde_json::Value =
         serde_json::from_slice(&body_bytes).expect("response body should be valid JSON");
 
-    if status == StatusCode::INTERNAL_SERVER_ERROR {
+    if status.is_server_error() {
         eprintln!(
             "Skipping test_search_scope_filters_via_http_camel_case_json: embedding backend unavailable ({})",
             body_json
@@ -565,3 +742,1783 @@ async fn test_search_scope_filters_via_http_camel_case_json() {
             .ok();
     }
 }
+
+// ===========================================================================
+// TEST 12: resolve keep — clears the review flag on only that fact
+// ===========================================================================
+#[tokio::test]
+async fn test_resolve_conflict_keep() {
+    let (pool, config) = match make_state().await {
+        Some(s) => s,
+        None => {
+            eprintln!("Skipping test_resolve_conflict_keep: DB or config unavailable");
+            return;
+        }
+    };
+
+    let subject = format!("ResolveKeep-{}", uuid::Uuid::new_v4());
+    let (old_id, new_id) = insert_flagged_pair(&pool, &subject).await;
+
+    let req = ResolveRequest {
+        action: "keep".to_string(),
+        other_id: None,
+    };
+    let (status, body) = into_parts(resolve_inner(&pool, &config, old_id, req).await);
+    assert_eq!(status, StatusCode::OK, "Unexpected body: {}", body);
+    assert_eq!(body["resolved"], true);
+
+    assert_eq!(fact_state(&pool, old_id).await, (false, false, None));
+    assert_eq!(
+        fact_state(&pool, new_id).await,
+        (true, false, None),
+        "The other side of the conflict stays flagged"
+    );
+
+    delete_facts_by_subject(&pool, &subject).await;
+}
+
+// ===========================================================================
+// TEST 13: resolve discard — prunes the fact and clears its flag
+// ===========================================================================
+#[tokio::test]
+async fn test_resolve_conflict_discard() {
+    let (pool, config) = match make_state().await {
+        Some(s) => s,
+        None => {
+            eprintln!("Skipping test_resolve_conflict_discard: DB or config unavailable");
+            return;
+        }
+    };
+
+    let subject = format!("ResolveDiscard-{}", uuid::Uuid::new_v4());
+    let (old_id, new_id) = insert_flagged_pair(&pool, &subject).await;
+
+    // keep-old: discard the new fact
+    let req = ResolveRequest {
+        action: "discard".to_string(),
+        other_id: None,
+    };
+    let (status, body) = into_parts(resolve_inner(&pool, &config, new_id, req).await);
+    assert_eq!(status, StatusCode::OK, "Unexpected body: {}", body);
+
+    assert_eq!(fact_state(&pool, new_id).await, (false, true, None));
+    assert_eq!(fact_state(&pool, old_id).await, (true, false, None));
+
+    delete_facts_by_subject(&pool, &subject).await;
+}
+
+// ===========================================================================
+// TEST 14: POST /facts/:id/resolve supersede_with via oneshot
+// ===========================================================================
+#[tokio::test]
+async fn test_resolve_conflict_supersede_with_via_oneshot() {
+    let state = match make_http_state().await {
+        Some(s) => s,
+        None => {
+            eprintln!(
+                "Skipping test_resolve_conflict_supersede_with_via_oneshot: DB or config unavailable"
+            );
+            return;
+        }
+    };
+
+    let pool = state.pool.clone();
+    let subject = format!("ResolveSupersede-{}", uuid::Uuid::new_v4());
+    let (old_id, new_id) = insert_flagged_pair(&pool, &subject).await;
+
+    // keep-new: the old fact is superseded by the new one
+    let app = build_router(state);
+    let req = Request::builder()
+        .method("POST")
+        .uri(format!("/facts/{}/resolve", old_id))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({ "action": "supersede_with", "otherId": new_id }).to_string(),
+        ))
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    assert_eq!(
+        fact_state(&pool, old_id).await,
+        (false, false, Some(new_id))
+    );
+    assert_eq!(
+        fact_state(&pool, new_id).await,
+        (false, false, None),
+        "Superseding resolves both sides of the conflict"
+    );
+
+    delete_facts_by_subject(&pool, &subject).await;
+}
+
+// ===========================================================================
+// TEST 15: resolve rejects unknown facts and self-supersession
+// ===========================================================================
+#[tokio::test]
+async fn test_resolve_conflict_invalid_targets() {
+    let (pool, config) = match make_state().await {
+        Some(s) => s,
+        None => {
+            eprintln!("Skipping test_resolve_conflict_invalid_targets: DB or config unavailable");
+            return;
+        }
+    };
+
+    let req = ResolveRequest {
+        action: "keep".to_string(),
+        other_id: None,
+    };
+    let (status, body) = into_parts(resolve_inner(&pool, &config, uuid::Uuid::new_v4(), req).await);
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["status"], "error");
+
+    let subject = format!("ResolveInvalid-{}", uuid::Uuid::new_v4());
+    let (old_id, _new_id) = insert_flagged_pair(&pool, &subject).await;
+
+    let req = ResolveRequest {
+        action: "supersede_with".to_string(),
+        other_id: Some(old_id),
+    };
+    let (status, _) = into_parts(resolve_inner(&pool, &config, old_id, req).await);
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        fact_state(&pool, old_id).await,
+        (true, false, None),
+        "A rejected resolution must not change the fact"
+    );
+
+    let req = ResolveRequest {
+        action: "bogus".to_string(),
+        other_id: None,
+    };
+    let (status, _) = into_parts(resolve_inner(&pool, &config, old_id, req).await);
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    delete_facts_by_subject(&pool, &subject).await;
+}
+
+// ===========================================================================
+// TEST 16: /search sourceFilter excludes rows from other sources
+// ===========================================================================
+#[tokio::test]
+async fn test_search_source_filter_via_http() {
+    let state = match make_http_state().await {
+        Some(s) => s,
+        None => {
+            eprintln!("Skipping test_search_source_filter_via_http: DB or config unavailable");
+            return;
+        }
+    };
+
+    let pool = state.pool.clone();
+    let tag = format!("http-source-filter-{}", uuid::Uuid::new_v4());
+    let vec_data: Vec<f32> = (0..768).map(|i| (i as f32) / 768.0).collect();
+    let vector = Vector::from(vec_data);
+
+    let mut rows = Vec::new();
+    for source in ["user", "agent", "system"] {
+        let row: (uuid::Uuid,) = sqlx::query_as(
+            "INSERT INTO memory_vectors (content, source, vector, metadata) VALUES ($1, $2, $3, $4) RETURNING id",
+        )
+        .bind(format!("http source filter {source} {tag}"))
+        .bind(source)
+        .bind(&vector)
+        .bind(json!({ "tag": tag.clone() }))
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to insert row");
+        rows.push((source, row.0));
+    }
+
+    let app = build_router(state);
+    let payload = json!({
+        "query": "source filter over http",
+        "limit": 10,
+        "sourceFilter": ["user", "system"],
+        "metadataContains": { "tag": tag },
+    });
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/search")
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .expect("request should build");
+
+    let resp = app
+        .oneshot(req)
+        .await
+        .expect("/search request should complete");
+    let status = resp.status();
+    let body_bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .expect("response body should read");
+    let body_json: serde_json::Value =
+        serde_json::from_slice(&body_bytes).expect("response body should be valid JSON");
+
+    if status.is_server_error() {
+        eprintln!(
+            "Skipping test_search_source_filter_via_http: embedding backend unavailable ({})",
+            body_json
+        );
+    } else {
+        assert_eq!(status, StatusCode::OK, "Expected 200 search response");
+
+        let ids: Vec<String> = body_json["results"]
+            .as_array()
+            .expect("results must be an array")
+            .iter()
+            .filter_map(|item| item["id"].as_str().map(ToString::to_string))
+            .collect();
+
+        for (source, id) in &rows {
+            assert_eq!(
+                ids.contains(&id.to_string()),
+                *source != "agent",
+                "Row with source '{}' filtered incorrectly",
+                source
+            );
+        }
+    }
+
+    for (_, id) in rows {
+        sqlx::query("DELETE FROM memory_vectors WHERE id = $1")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .ok();
+    }
+}
+
+// ===========================================================================
+// TEST 17: api_token — missing or wrong bearer token is rejected with 401
+// ===========================================================================
+#[tokio::test]
+async fn test_api_token_rejects_missing_or_wrong_token() {
+    let app = build_router(example_state(|c| {
+        c.http.api_token = Some("s3cret".to_string())
+    }));
+
+    for authorization in [
+        None,
+        Some("Bearer wrong"),
+        Some("s3cret"),
+        Some("Basic s3cret"),
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(search_request(authorization))
+            .await
+            .unwrap();
+        assert_eq!(
+            resp.status(),
+            StatusCode::UNAUTHORIZED,
+            "authorization {:?} should be rejected",
+            authorization
+        );
+    }
+
+    // Health and version stay public
+    let req = Request::builder()
+        .method("GET")
+        .uri("/version")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+// ===========================================================================
+// TEST 18: api_token — correct bearer token reaches the handler
+// ===========================================================================
+#[tokio::test]
+async fn test_api_token_accepts_correct_token() {
+    let app = build_router(example_state(|c| {
+        c.http.api_token = Some("s3cret".to_string())
+    }));
+
+    let resp = app
+        .oneshot(search_request(Some("Bearer s3cret")))
+        .await
+        .unwrap();
+
+    // Empty query is rejected by the search handler itself, not the auth layer
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+// ===========================================================================
+// TEST 19: requests_per_minute — a burst past the limit gets 429 + Retry-After
+// ===========================================================================
+#[tokio::test]
+async fn test_rate_limit_burst_returns_429() {
+    let limit = 3;
+    let app = build_router(example_state(|c| c.http.requests_per_minute = limit));
+
+    for _ in 0..limit {
+        let resp = app.clone().oneshot(search_request(None)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "within limit");
+    }
+
+    let resp = app.clone().oneshot(search_request(None)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = resp.headers()["retry-after"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after >= 1);
+
+    // A made-up bearer token doesn't buy a fresh bucket
+    let resp = app
+        .oneshot(search_request(Some("Bearer other-client")))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // Requests carrying the configured token share its bucket
+    let app = build_router(example_state(|c| {
+        c.http.requests_per_minute = 1;
+        c.http.api_token = Some("s3cret".to_string());
+    }));
+    let resp = app
+        .clone()
+        .oneshot(search_request(Some("Bearer s3cret")))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "within limit");
+    let resp = app
+        .oneshot(search_request(Some("Bearer s3cret")))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+// ===========================================================================
+// TEST 20: POST /ingest/batch — valid items are ingested, invalid ones reported
+// ===========================================================================
+#[tokio::test]
+async fn test_ingest_batch_reports_partial_failure() {
+    let state = match make_http_state().await {
+        Some(s) => s,
+        None => {
+            eprintln!(
+                "Skipping test_ingest_batch_reports_partial_failure: DB or config unavailable"
+            );
+            return;
+        }
+    };
+
+    let test_session = format!("http-batch-ingest-{}", uuid::Uuid::new_v4());
+    let pool = state.pool.clone();
+    let app = build_router(state);
+
+    let batch = json!([
+        { "content": "batch line one", "source": "user", "metadata": { "session_id": test_session } },
+        { "source": "user", "metadata": { "session_id": test_session } },
+        { "content": "batch line three", "source": "assistant", "metadata": { "session_id": test_session } }
+    ]);
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/ingest/batch")
+        .header("content-type", "application/json")
+        .body(Body::from(batch.to_string()))
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json["queued"], 2, "body: {}", json);
+    assert_eq!(json["ids"].as_array().unwrap().len(), 2);
+    let failed = json["failed"].as_array().unwrap();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0]["index"], 1);
+    assert!(failed[0]["error"].as_str().unwrap().contains("content"));
+
+    let events: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM session_events WHERE session_id = $1")
+            .bind(&test_session)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(events, 2);
+
+    for id in json["ids"].as_array().unwrap() {
+        sqlx::query("DELETE FROM memory_vectors WHERE id = $1::uuid")
+            .bind(id.as_str().unwrap())
+            .execute(&pool)
+            .await
+            .ok();
+    }
+    sqlx::query("DELETE FROM session_events WHERE session_id = $1")
+        .bind(&test_session)
+        .execute(&pool)
+        .await
+        .ok();
+}
+
+// ===========================================================================
+// TEST 21: DELETE /memory/:id — soft delete prunes the row and drops its edges
+// ===========================================================================
+#[tokio::test]
+async fn test_delete_memory_soft() {
+    let (pool, config) = match make_state().await {
+        Some(s) => s,
+        None => {
+            eprintln!("Skipping test_delete_memory_soft: DB or config unavailable");
+            return;
+        }
+    };
+
+    let (id, neighbour) = insert_linked_memory(&pool).await;
+    let (status, body) = into_parts(delete_inner(&pool, &config, id, DeleteQuery::default()).await);
+
+    assert_eq!(status, StatusCode::OK, "body: {}", body);
+    assert_eq!(body["hard"], false);
+    assert_eq!(body["links_removed"], 2);
+
+    let pruned: bool = sqlx::query_scalar("SELECT pruned FROM memory_vectors WHERE id = $1")
+        .bind(id)
+        .fetch_one(&pool)
+        .await
+        .expect("soft-deleted row should remain");
+    assert!(pruned);
+    assert_eq!(
+        link_count(&pool, neighbour).await,
+        0,
+        "edges should cascade"
+    );
+
+    sqlx::query("DELETE FROM memory_vectors WHERE id = $1")
+        .bind(id)
+        .execute(&pool)
+        .await
+        .ok();
+}
+
+// ===========================================================================
+// TEST 22: DELETE /memory/:id?hard=true — removes the row and its edges
+// ===========================================================================
+#[tokio::test]
+async fn test_delete_memory_hard_via_oneshot() {
+    let state = match make_http_state().await {
+        Some(s) => s,
+        None => {
+            eprintln!("Skipping test_delete_memory_hard_via_oneshot: DB or config unavailable");
+            return;
+        }
+    };
+    let pool = state.pool.clone();
+    let (id, neighbour) = insert_linked_memory(&pool).await;
+    sqlx::query(
+        "INSERT INTO session_events (session_id, agent_id, role, content, memory_id) VALUES ('forget-test', 'test-delete', 'user', 'forget me', $1)",
+    )
+    .bind(id)
+    .execute(&pool)
+    .await
+    .expect("insert session event");
+
+    let req = Request::builder()
+        .method("DELETE")
+        .uri(format!("/memory/{}?hard=true", id))
+        .body(Body::empty())
+        .unwrap();
+    let resp = build_router(state).oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM memory_vectors WHERE id = $1")
+        .bind(id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 0);
+    assert_eq!(link_count(&pool, id).await, 0);
+    assert_eq!(link_count(&pool, neighbour).await, 0);
+
+    let events: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM session_events WHERE memory_id = $1")
+            .bind(id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(events, 0, "the transcript row should be forgotten too");
+}
+
+// ===========================================================================
+// TEST 23: DELETE /memory/:id — unknown id returns 404
+// ===========================================================================
+#[tokio::test]
+async fn test_delete_memory_unknown_returns_404() {
+    let (pool, config) = match make_state().await {
+        Some(s) => s,
+        None => {
+            eprintln!("Skipping test_delete_memory_unknown_returns_404: DB or config unavailable");
+            return;
+        }
+    };
+
+    for hard in [false, true] {
+        let (status, body) = into_parts(
+            delete_inner(&pool, &config, uuid::Uuid::new_v4(), DeleteQuery { hard }).await,
+        );
+        assert_eq!(status, StatusCode::NOT_FOUND, "body: {}", body);
+        assert_eq!(body["status"], "error");
+    }
+}
+
+/// POST /search with a JSON body padded to exactly `len` bytes (empty query → 400 once parsed)
+fn padded_search_request(len: usize) -> Request<Body> {
+    let skeleton = r#"{"query": "", "pad": ""}"#;
+    let body = format!(
+        r#"{{"query": "", "pad": "{}"}}"#,
+        "x".repeat(len - skeleton.len())
+    );
+    assert_eq!(body.len(), len);
+    Request::builder()
+        .method("POST")
+        .uri("/search")
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+// ===========================================================================
+// TEST 24: body just over max_body_bytes is rejected with 413
+// ===========================================================================
+#[tokio::test]
+async fn test_body_over_limit_returns_413() {
+    let app = build_router(example_state(|c| c.http.max_body_bytes = 256));
+
+    let resp = app.oneshot(padded_search_request(257)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+// ===========================================================================
+// TEST 25: body at max_body_bytes reaches the handler
+// ===========================================================================
+#[tokio::test]
+async fn test_body_under_limit_passes() {
+    let app = build_router(example_state(|c| c.http.max_body_bytes = 256));
+
+    // Empty query → 400 from the handler, i.e. the body was accepted and parsed
+    let resp = app.oneshot(padded_search_request(256)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+// ===========================================================================
+// TEST 26: a request body that never finishes times out with 408
+// ===========================================================================
+#[tokio::test]
+async fn test_stalled_request_times_out_with_408() {
+    let app = build_router(example_state(|c| c.http.request_timeout_seconds = 1));
+
+    let stalled = futures::stream::pending::<Result<bytes::Bytes, std::io::Error>>();
+    let req = Request::builder()
+        .method("POST")
+        .uri("/search")
+        .header("content-type", "application/json")
+        .body(Body::from_stream(stalled))
+        .unwrap();
+
+    let resp = tokio::time::timeout(std::time::Duration::from_secs(5), app.oneshot(req))
+        .await
+        .expect("server should answer instead of hanging")
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::REQUEST_TIMEOUT);
+}
+
+/// Read a counter's value from a Prometheus text body
+fn prometheus_value(body: &str, name: &str) -> Option<u64> {
+    body.lines()
+        .find_map(|line| line.strip_prefix(name)?.strip_prefix(' ')?.parse().ok())
+}
+
+async fn scrape_searches_total(app: axum::Router) -> u64 {
+    let req = Request::builder()
+        .uri("/metrics")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8(bytes.to_vec()).unwrap();
+    prometheus_value(&body, "ethos_searches_total").expect("searches counter exported")
+}
+
+// ===========================================================================
+// TEST 27: /search increments the search counter exposed on /metrics
+// ===========================================================================
+#[tokio::test]
+async fn test_search_increments_metrics_counter() {
+    let app = build_router(example_state(|_| {}));
+
+    let before = scrape_searches_total(app.clone()).await;
+
+    // Counted when routed, whether or not embedding the query succeeds
+    let req = Request::builder()
+        .method("POST")
+        .uri("/search")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"query": "metrics probe"}"#))
+        .unwrap();
+    app.clone().oneshot(req).await.unwrap();
+
+    let after = scrape_searches_total(app).await;
+    assert!(after > before, "before={} after={}", before, after);
+}
+
+// ===========================================================================
+// TEST 28: /reembed/status reports backfill progress
+// ===========================================================================
+#[tokio::test]
+async fn test_reembed_status_endpoint() {
+    let app = build_router(example_state(|_| {}));
+
+    let req = Request::builder()
+        .uri("/reembed/status")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(json["enabled"].is_boolean());
+    assert!(json["total_null"].is_u64());
+    assert!(json["embedded_so_far"].is_u64());
+    assert!(json["done"].is_boolean());
+}
+
+// ===========================================================================
+// TEST 29: audit_searches — a search appends a JSONL line with its result ids
+// ===========================================================================
+#[tokio::test]
+async fn test_search_appends_audit_record() {
+    use ethos_server::audit::{install_search_audit, SearchAudit, SearchAuditRecord};
+
+    let state = example_state(|_| {});
+    let pool = match PgPool::connect(DATABASE_URL).await {
+        Ok(pool) => pool,
+        Err(_) => {
+            eprintln!("Skipping test_search_appends_audit_record: DB unavailable");
+            return;
+        }
+    };
+
+    let audit_path =
+        std::env::temp_dir().join(format!("ethos-audit-test-{}.jsonl", uuid::Uuid::new_v4()));
+    assert!(install_search_audit(SearchAudit::spawn(audit_path.clone())));
+
+    // A topic listing needs no embedding backend
+    let topic = format!("audit-{}", uuid::Uuid::new_v4());
+    let fact_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO semantic_facts (kind, statement, subject, predicate, object, topics) \
+         VALUES ('fact', 'audited fact', 'AuditTest', 'is', 'audited', $1) RETURNING id",
+    )
+    .bind(vec![topic.clone()])
+    .fetch_one(&pool)
+    .await
+    .expect("insert fact");
+    let memory_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO memory_vectors (content, source, source_type, source_id) \
+         VALUES ('audited fact', 'user', 'fact', $1) RETURNING id",
+    )
+    .bind(fact_id)
+    .fetch_one(&pool)
+    .await
+    .expect("insert memory vector");
+
+    let req = SearchRequest {
+        query: None,
+        limit: Some(3),
+        offset: None,
+        use_spreading: Some(false),
+        diversify: false,
+        group_by_document: false,
+        explain: false,
+        trace: false,
+        expand: false,
+        task_type: None,
+        search_dimensions: None,
+        include_related_facts: false,
+        min_score: None,
+        source_filter: None,
+        metadata_contains: None,
+        created_after: None,
+        created_before: None,
+        topics: Some(vec![topic]),
+        include_pruned: false,
+        resource_id: None,
+        thread_id: None,
+        agent_id: None,
+    };
+    let (status, body) = into_parts(search_inner(&state.pool, &state.config, req).await);
+
+    // The record is written off the request path; wait for it to land
+    let mut records: Vec<SearchAuditRecord> = Vec::new();
+    for _ in 0..50 {
+        if let Ok(contents) = std::fs::read_to_string(&audit_path) {
+            records = contents
+                .lines()
+                .map(|line| serde_json::from_str(line).expect("audit line is JSON"))
+                .collect();
+            if records.iter().any(|r| r.result_ids.contains(&memory_id)) {
+                break;
+            }
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+
+    sqlx::query("DELETE FROM memory_vectors WHERE id = $1")
+        .bind(memory_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM semantic_facts WHERE id = $1")
+        .bind(fact_id)
+        .execute(&pool)
+        .await
+        .ok();
+    std::fs::remove_file(&audit_path).ok();
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let record = records
+        .iter()
+        .find(|r| r.result_ids.contains(&memory_id))
+        .unwrap_or_else(|| panic!("no audit record with {}: {:?}", memory_id, records));
+    assert_eq!(record.query, "");
+    assert_eq!(record.limit, Some(3));
+    assert!(!record.use_spreading);
+}
+
+/// Count memories stored under an idempotency key
+async fn idempotency_key_count(pool: &PgPool, key: &str) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM memory_vectors WHERE idempotency_key = $1")
+        .bind(key)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+async fn delete_by_idempotency_key(pool: &PgPool, key: &str, session: &str) {
+    sqlx::query("DELETE FROM memory_vectors WHERE idempotency_key = $1")
+        .bind(key)
+        .execute(pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM session_events WHERE session_id = $1")
+        .bind(session)
+        .execute(pool)
+        .await
+        .ok();
+}
+
+// ===========================================================================
+// TEST 30: ingest with a repeated idempotency_key returns the first id
+// ===========================================================================
+#[tokio::test]
+async fn test_ingest_idempotency_key_prevents_duplicates() {
+    let state = example_state(|_| {});
+    if sqlx::query("SELECT 1").execute(&state.pool).await.is_err() {
+        eprintln!("Skipping test_ingest_idempotency_key_prevents_duplicates: DB unavailable");
+        return;
+    }
+
+    let key = format!("retry-{}", uuid::Uuid::new_v4());
+    let test_session = format!("http-idempotent-{}", uuid::Uuid::new_v4());
+    let payload = json!({
+        "content": "ingested once despite the retry",
+        "source": "user",
+        "idempotency_key": key,
+        "metadata": { "session_id": test_session }
+    });
+
+    let (first_status, first) =
+        into_parts(ingest_inner(&state.pool, &state.config, payload.clone()).await);
+    let (second_status, second) =
+        into_parts(ingest_inner(&state.pool, &state.config, payload).await);
+    let rows = idempotency_key_count(&state.pool, &key).await;
+    let events: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM session_events WHERE session_id = $1")
+            .bind(&test_session)
+            .fetch_one(&state.pool)
+            .await
+            .unwrap();
+    delete_by_idempotency_key(&state.pool, &key, &test_session).await;
+
+    assert_eq!(first_status, StatusCode::OK, "{}", first);
+    assert_eq!(second_status, StatusCode::OK, "{}", second);
+    assert_eq!(first["queued"], true);
+    assert_eq!(
+        second["queued"], false,
+        "a repeated key must not queue again"
+    );
+    assert_eq!(
+        second["id"], first["id"],
+        "the retry must reference the first id"
+    );
+    assert_eq!(rows, 1);
+    assert_eq!(events, 1);
+}
+
+// ===========================================================================
+// TEST 31: /ingest/batch reuses the stored id for a repeated idempotency_key
+// ===========================================================================
+#[tokio::test]
+async fn test_ingest_batch_idempotency_key() {
+    let state = example_state(|_| {});
+    if sqlx::query("SELECT 1").execute(&state.pool).await.is_err() {
+        eprintln!("Skipping test_ingest_batch_idempotency_key: DB unavailable");
+        return;
+    }
+
+    let key = format!("batch-retry-{}", uuid::Uuid::new_v4());
+    let test_session = format!("http-idempotent-batch-{}", uuid::Uuid::new_v4());
+    let item = json!({
+        "content": "batched once",
+        "source": "user",
+        "idempotency_key": key,
+        "metadata": { "session_id": test_session }
+    });
+
+    let (status, body) =
+        into_parts(ingest_batch_inner(&state.pool, &state.config, vec![item.clone(), item]).await);
+    let rows = idempotency_key_count(&state.pool, &key).await;
+    delete_by_idempotency_key(&state.pool, &key, &test_session).await;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["queued"], 1, "body: {}", body);
+    let ids = body["ids"].as_array().unwrap();
+    assert_eq!(ids.len(), 2);
+    assert_eq!(ids[0], ids[1]);
+    assert_eq!(rows, 1);
+}
+
+/// Insert a pruned memory vector, episode, and fact last updated `age_days`
+/// ago, plus an edge from the vector; returns (vector, episode, fact, session)
+async fn insert_pruned_rows(
+    pool: &PgPool,
+    age_days: i64,
+) -> (uuid::Uuid, uuid::Uuid, uuid::Uuid, uuid::Uuid) {
+    let updated_at = chrono::Utc::now() - chrono::Duration::days(age_days);
+    let session_id = uuid::Uuid::new_v4();
+    sqlx::query("INSERT INTO sessions (id, session_key, agent_id) VALUES ($1, $2, 'test')")
+        .bind(session_id)
+        .bind(format!("test-gc-{}", session_id))
+        .execute(pool)
+        .await
+        .expect("insert session");
+    let vector_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO memory_vectors (content, source, pruned, updated_at) \
+         VALUES ('gc vector', 'test-gc', true, $1) RETURNING id",
+    )
+    .bind(updated_at)
+    .fetch_one(pool)
+    .await
+    .expect("insert memory vector");
+    let episode_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO episodic_traces (session_id, agent_id, turn_index, role, content, pruned, updated_at) \
+         VALUES ($1, 'test', 0, 'user', 'gc episode', true, $2) RETURNING id",
+    )
+    .bind(session_id)
+    .bind(updated_at)
+    .fetch_one(pool)
+    .await
+    .expect("insert episode");
+    let fact_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO semantic_facts (kind, statement, subject, predicate, object, pruned, updated_at) \
+         VALUES ('fact', 'gc fact', 'GcTest', 'is', 'pruned', true, $1) RETURNING id",
+    )
+    .bind(updated_at)
+    .fetch_one(pool)
+    .await
+    .expect("insert fact");
+    sqlx::query(
+        "INSERT INTO memory_graph_links (from_type, from_id, to_type, to_id, relation, weight) \
+         VALUES ('episode', $1, 'fact', $2, 'similarity', 0.5)",
+    )
+    .bind(vector_id)
+    .bind(fact_id)
+    .execute(pool)
+    .await
+    .expect("insert link");
+    (vector_id, episode_id, fact_id, session_id)
+}
+
+/// Whether each of (vector, episode, fact) still exists
+async fn pruned_rows_exist(
+    pool: &PgPool,
+    (vector_id, episode_id, fact_id, _): (uuid::Uuid, uuid::Uuid, uuid::Uuid, uuid::Uuid),
+) -> [bool; 3] {
+    let mut exists = [false; 3];
+    for (slot, (table, id)) in [
+        ("memory_vectors", vector_id),
+        ("episodic_traces", episode_id),
+        ("semantic_facts", fact_id),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        exists[slot] = sqlx::query_scalar(&format!(
+            "SELECT EXISTS (SELECT 1 FROM {} WHERE id = $1)",
+            table
+        ))
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    }
+    exists
+}
+
+// ===========================================================================
+// TEST 32: POST /admin/gc removes long-pruned rows and keeps recent ones
+// ===========================================================================
+#[tokio::test]
+async fn test_admin_gc_removes_old_pruned_rows() {
+    let state = example_state(|c| c.http.api_token = Some("s3cret".to_string()));
+    if sqlx::query("SELECT 1").execute(&state.pool).await.is_err() {
+        eprintln!("Skipping test_admin_gc_removes_old_pruned_rows: DB unavailable");
+        return;
+    }
+    let pool = state.pool.clone();
+    let retention_days = state.config.decay.gc_retention_days as i64;
+    let app = build_router(state);
+
+    let old = insert_pruned_rows(&pool, retention_days + 10).await;
+    let recent = insert_pruned_rows(&pool, retention_days - 10).await;
+
+    let gc_request = |authorization: Option<&str>| {
+        let mut builder = Request::builder().method("POST").uri("/admin/gc");
+        if let Some(value) = authorization {
+            builder = builder.header("authorization", value);
+        }
+        builder.body(Body::empty()).unwrap()
+    };
+
+    let unauthorized = app.clone().oneshot(gc_request(None)).await.unwrap();
+    assert_eq!(unauthorized.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(pruned_rows_exist(&pool, old).await, [true; 3]);
+
+    let resp = app
+        .oneshot(gc_request(Some("Bearer s3cret")))
+        .await
+        .unwrap();
+    let status = resp.status();
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    let old_exists = pruned_rows_exist(&pool, old).await;
+    let recent_exists = pruned_rows_exist(&pool, recent).await;
+    let old_links = link_count(&pool, old.0).await;
+
+    for (vector_id, episode_id, fact_id, session_id) in [old, recent] {
+        sqlx::query("DELETE FROM memory_graph_links WHERE from_id = $1")
+            .bind(vector_id)
+            .execute(&pool)
+            .await
+            .ok();
+        for (table, id) in [
+            ("memory_vectors", vector_id),
+            ("episodic_traces", episode_id),
+            ("semantic_facts", fact_id),
+            ("sessions", session_id),
+        ] {
+            sqlx::query(&format!("DELETE FROM {} WHERE id = $1", table))
+                .bind(id)
+                .execute(&pool)
+                .await
+                .ok();
+        }
+    }
+
+    assert_eq!(status, StatusCode::OK, "body: {}", json);
+    assert_eq!(
+        old_exists, [false; 3],
+        "rows pruned past the window are deleted"
+    );
+    assert_eq!(
+        recent_exists, [true; 3],
+        "rows pruned within the window survive"
+    );
+    assert_eq!(old_links, 0, "edges to deleted rows are removed");
+    for table in [
+        "memory_vectors",
+        "episodic_traces",
+        "semantic_facts",
+        "memory_graph_links",
+    ] {
+        assert!(json[table].as_u64().unwrap() >= 1, "body: {}", json);
+    }
+    assert_eq!(json["retention_days"], retention_days);
+}
+
+// ===========================================================================
+// TEST 33: GET /facts filters by subject and by flagged-for-review
+// ===========================================================================
+#[tokio::test]
+async fn test_facts_lists_active_and_flagged() {
+    let state = example_state(|_| {});
+    if sqlx::query("SELECT 1").execute(&state.pool).await.is_err() {
+        eprintln!("Skipping test_facts_lists_active_and_flagged: DB unavailable");
+        return;
+    }
+    let pool = state.pool.clone();
+    let subject = format!("facts-list-{}", uuid::Uuid::new_v4());
+    let (old_id, new_id) = insert_flagged_pair(&pool, &subject).await;
+    let steady: (uuid::Uuid,) = sqlx::query_as(
+        r#"
+        INSERT INTO semantic_facts (kind, statement, subject, predicate, object)
+        VALUES ('fact', $1, $2, 'colour', 'blue')
+        RETURNING id
+        "#,
+    )
+    .bind(format!("{} is blue", subject))
+    .bind(&subject)
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to insert fact");
+    sqlx::query("UPDATE semantic_facts SET superseded_by = $1 WHERE id = $2")
+        .bind(new_id)
+        .bind(old_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let app = build_router(state);
+    let list = |query: String| {
+        let app = app.clone();
+        async move {
+            let resp = app
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/facts?{}", query))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let status = resp.status();
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let ids: Vec<String> = json["facts"]
+                .as_array()
+                .map(|facts| {
+                    facts
+                        .iter()
+                        .filter_map(|f| f["id"].as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default();
+            (status, ids)
+        }
+    };
+
+    let (all_status, all) = list(format!("subject={}", subject.to_uppercase())).await;
+    let (flagged_status, flagged) = list(format!("subject={}&flagged=true", subject)).await;
+
+    delete_facts_by_subject(&pool, &subject).await;
+
+    assert_eq!(all_status, StatusCode::OK);
+    assert_eq!(flagged_status, StatusCode::OK);
+    assert_eq!(
+        all,
+        vec![steady.0.to_string(), new_id.to_string()],
+        "superseded facts are hidden; ordered by predicate"
+    );
+    assert_eq!(flagged, vec![new_id.to_string()]);
+}
+
+// ===========================================================================
+// TEST 34: ingest rejects content over [service] max_content_bytes with 413
+// ===========================================================================
+#[tokio::test]
+async fn test_ingest_rejects_oversized_content() {
+    let state = example_state(|c| c.service.max_content_bytes = 64);
+    if sqlx::query("SELECT 1").execute(&state.pool).await.is_err() {
+        eprintln!("Skipping test_ingest_rejects_oversized_content: DB unavailable");
+        return;
+    }
+
+    let test_session = format!("http-oversized-{}", uuid::Uuid::new_v4());
+    let payload = json!({
+        "content": "x".repeat(65),
+        "source": "user",
+        "metadata": { "session_id": test_session }
+    });
+
+    let (status, body) =
+        into_parts(ingest_inner(&state.pool, &state.config, payload.clone()).await);
+    let (batch_status, batch) =
+        into_parts(ingest_batch_inner(&state.pool, &state.config, vec![payload]).await);
+    let events: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM session_events WHERE session_id = $1")
+            .bind(&test_session)
+            .fetch_one(&state.pool)
+            .await
+            .unwrap();
+
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE, "{}", body);
+    assert_eq!(body["status"], "error");
+    assert_eq!(body["error"], "content exceeds 64 bytes");
+    assert_eq!(batch_status, StatusCode::OK, "{}", batch);
+    assert_eq!(batch["queued"], 0);
+    assert_eq!(batch["failed"][0]["error"], "content exceeds 64 bytes");
+    assert_eq!(events, 0, "nothing may be written for rejected content");
+}
+
+// ===========================================================================
+// TEST 35: GET /sessions aggregates event and consolidated episode counts
+// ===========================================================================
+#[tokio::test]
+async fn test_sessions_aggregates_activity() {
+    let state = example_state(|_| {});
+    if sqlx::query("SELECT 1").execute(&state.pool).await.is_err() {
+        eprintln!("Skipping test_sessions_aggregates_activity: DB unavailable");
+        return;
+    }
+    let pool = &state.pool;
+    let agent = format!("sessions-agent-{}", uuid::Uuid::new_v4());
+    let session_key = format!("sessions-key-{}", uuid::Uuid::new_v4());
+    let session_id = uuid::Uuid::new_v4();
+
+    sqlx::query("INSERT INTO sessions (id, session_key, agent_id) VALUES ($1, $2, $3)")
+        .bind(session_id)
+        .bind(&session_key)
+        .bind(&agent)
+        .execute(pool)
+        .await
+        .unwrap();
+    for (minutes_ago, role) in [(30, "user"), (20, "assistant"), (10, "user")] {
+        sqlx::query(
+            "INSERT INTO session_events (session_id, agent_id, role, content, created_at) \
+             VALUES ($1, $2, $3, 'hello', NOW() - make_interval(mins => $4))",
+        )
+        .bind(&session_key)
+        .bind(&agent)
+        .bind(role)
+        .bind(minutes_ago)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+    for (turn, consolidated) in [(0, true), (1, false)] {
+        sqlx::query(
+            "INSERT INTO episodic_traces (session_id, agent_id, turn_index, role, content, consolidated_at) \
+             VALUES ($1, $2, $3, 'user', 'hello', CASE WHEN $4 THEN NOW() END)",
+        )
+        .bind(session_id)
+        .bind(&agent)
+        .bind(turn)
+        .bind(consolidated)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    let (status, body) = into_parts(
+        sessions_inner(
+            pool,
+            &state.config,
+            SessionsQuery {
+                agent: Some(agent.clone()),
+                ..Default::default()
+            },
+        )
+        .await,
+    );
+    let (_, future) = into_parts(
+        sessions_inner(
+            pool,
+            &state.config,
+            SessionsQuery {
+                agent: Some(agent.clone()),
+                active_since: Some(chrono::Utc::now() + chrono::Duration::hours(1)),
+                limit: None,
+            },
+        )
+        .await,
+    );
+
+    sqlx::query("DELETE FROM session_events WHERE session_id = $1")
+        .bind(&session_key)
+        .execute(pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM sessions WHERE id = $1")
+        .bind(session_id)
+        .execute(pool)
+        .await
+        .ok();
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["count"], 1, "{}", body);
+    let session = &body["sessions"][0];
+    assert_eq!(session["id"], session_id.to_string());
+    assert_eq!(session["session_key"], session_key);
+    assert_eq!(session["event_count"], 3);
+    assert_eq!(session["consolidated_count"], 1);
+    let first: chrono::DateTime<chrono::Utc> =
+        serde_json::from_value(session["first_event"].clone()).unwrap();
+    let last: chrono::DateTime<chrono::Utc> =
+        serde_json::from_value(session["last_event"].clone()).unwrap();
+    assert!(last - first >= chrono::Duration::minutes(19), "{}", session);
+    assert_eq!(future["count"], 0, "active_since filters idle sessions");
+}
+
+// ===========================================================================
+// TEST 36: GET /memory/:id/neighbors returns linked memories with weights
+// ===========================================================================
+#[tokio::test]
+async fn test_memory_neighbors_follow_graph_links() {
+    let state = example_state(|_| {});
+    if sqlx::query("SELECT 1").execute(&state.pool).await.is_err() {
+        eprintln!("Skipping test_memory_neighbors_follow_graph_links: DB unavailable");
+        return;
+    }
+    let pool = state.pool.clone();
+    let subject = format!("neighbors-{}", uuid::Uuid::new_v4());
+    let (seed, near) = insert_flagged_pair(&pool, &subject).await;
+    let (far, _) = insert_flagged_pair(&pool, &subject).await;
+    for (from, to, weight) in [(seed, near, 0.7), (near, far, 0.5)] {
+        sqlx::query(
+            "INSERT INTO memory_graph_links (from_type, from_id, to_type, to_id, relation, weight) \
+             VALUES ('fact', $1, 'fact', $2, 'similarity', $3)",
+        )
+        .bind(from)
+        .bind(to)
+        .bind(weight)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let app = build_router(state);
+    let get = |uri: String| {
+        let app = app.clone();
+        async move {
+            let resp = app
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = resp.status();
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            (status, json)
+        }
+    };
+
+    let (status, one_hop) = get(format!("/memory/{}/neighbors", seed)).await;
+    let (_, two_hops) = get(format!("/memory/{}/neighbors?depth=2", seed)).await;
+    let (missing_status, _) = get(format!("/memory/{}/neighbors", uuid::Uuid::new_v4())).await;
+
+    sqlx::query("DELETE FROM memory_graph_links WHERE from_id = ANY($1)")
+        .bind(vec![seed, near])
+        .execute(&pool)
+        .await
+        .ok();
+    delete_facts_by_subject(&pool, &subject).await;
+
+    assert_eq!(status, StatusCode::OK, "{}", one_hop);
+    assert_eq!(one_hop["count"], 1, "{}", one_hop);
+    let neighbor = &one_hop["neighbors"][0];
+    assert_eq!(neighbor["id"], near.to_string());
+    assert_eq!(neighbor["via"], seed.to_string());
+    assert_eq!(neighbor["depth"], 1);
+    assert!((neighbor["edge_weight"].as_f64().unwrap() - 0.7).abs() < 1e-6);
+
+    assert_eq!(two_hops["count"], 2, "{}", two_hops);
+    let far_node = two_hops["neighbors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|n| n["id"] == far.to_string())
+        .expect("second hop reached");
+    assert_eq!(far_node["depth"], 2);
+    assert_eq!(far_node["via"], near.to_string());
+    assert!((far_node["edge_weight"].as_f64().unwrap() - 0.5).abs() < 1e-6);
+
+    assert_eq!(missing_status, StatusCode::NOT_FOUND);
+}
+
+// ===========================================================================
+// TEST 37: GET /memory pages through memories changed since a timestamp by
+// cursor, and reports a row again once it is pruned
+// ===========================================================================
+#[tokio::test]
+async fn test_memory_changefeed_pages_by_cursor() {
+    let state = example_state(|_| {});
+    if sqlx::query("SELECT 1").execute(&state.pool).await.is_err() {
+        eprintln!("Skipping test_memory_changefeed_pages_by_cursor: DB unavailable");
+        return;
+    }
+    let pool = state.pool.clone();
+    let source = format!("changefeed-{}", uuid::Uuid::new_v4());
+
+    // Far-future timestamps keep other rows out of the feed; two rows share
+    // an instant so the cursor must break the tie on id
+    let since = chrono::DateTime::parse_from_rfc3339("2199-01-01T00:00:00Z")
+        .unwrap()
+        .with_timezone(&chrono::Utc);
+    let mut expected = Vec::new();
+    for (i, minutes) in [-10i64, 0, 5, 5, 20].into_iter().enumerate() {
+        let id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO memory_vectors (content, source, updated_at) VALUES ($1, $2, $3) RETURNING id",
+        )
+        .bind(format!("changefeed row {i}"))
+        .bind(&source)
+        .bind(since + chrono::Duration::minutes(minutes))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        if minutes >= 0 {
+            expected.push((minutes, id));
+        }
+    }
+    expected.sort();
+    let expected: Vec<String> = expected.into_iter().map(|(_, id)| id.to_string()).collect();
+
+    let app = build_router(state);
+    let get = |uri: String| {
+        let app = app.clone();
+        async move {
+            let resp = app
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = resp.status();
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            (status, json)
+        }
+    };
+
+    let mut seen = Vec::new();
+    let mut pages = 0;
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut uri = "/memory?since=2199-01-01T00:00:00Z&limit=2".to_string();
+        if let Some(cursor) = &cursor {
+            uri.push_str(&format!("&cursor={cursor}"));
+        }
+        let (status, page) = get(uri).await;
+        assert_eq!(status, StatusCode::OK, "{}", page);
+        pages += 1;
+        for memory in page["memories"].as_array().unwrap() {
+            assert_eq!(memory["source"], source.as_str());
+            seen.push(memory["id"].as_str().unwrap().to_string());
+        }
+        match page["next_cursor"].as_str() {
+            Some(next) => cursor = Some(next.to_string()),
+            None => break,
+        }
+        assert!(pages < 10, "cursor must terminate");
+    }
+    let (bad_status, _) = get("/memory?cursor=not-a-cursor".to_string()).await;
+
+    // Pruning the oldest row moves it to the end of the feed
+    sqlx::query("UPDATE memory_vectors SET pruned = true, updated_at = $2 WHERE id = $1")
+        .bind(uuid::Uuid::parse_str(&expected[0]).unwrap())
+        .bind(since + chrono::Duration::minutes(30))
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (_, after_prune) = get("/memory?since=2199-01-01T00:20:00Z".to_string()).await;
+
+    sqlx::query("DELETE FROM memory_vectors WHERE source = $1")
+        .bind(&source)
+        .execute(&pool)
+        .await
+        .ok();
+
+    assert_eq!(pages, 2, "4 rows at limit 2");
+    assert_eq!(seen, expected, "oldest first, earlier rows excluded");
+    assert_eq!(bad_status, StatusCode::BAD_REQUEST);
+    let after_prune = after_prune["memories"].as_array().unwrap();
+    let ids: Vec<&str> = after_prune
+        .iter()
+        .map(|m| m["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, [expected[3].as_str(), expected[0].as_str()]);
+    assert_eq!(after_prune[1]["pruned"], true);
+}
+
+// ===========================================================================
+// TEST 38: POST /consolidate/preview extracts facts without writing them
+// ===========================================================================
+#[tokio::test]
+async fn test_consolidate_preview_writes_nothing() {
+    let state = example_state(|_| {});
+    if sqlx::query("SELECT 1").execute(&state.pool).await.is_err() {
+        eprintln!("Skipping test_consolidate_preview_writes_nothing: DB unavailable");
+        return;
+    }
+    let pool = &state.pool;
+    let session_id = uuid::Uuid::new_v4();
+    let object = format!("Preview{}", uuid::Uuid::new_v4().simple());
+
+    sqlx::query("INSERT INTO sessions (id, session_key, agent_id) VALUES ($1, $2, 'preview-test')")
+        .bind(session_id)
+        .bind(format!("preview-key-{session_id}"))
+        .execute(pool)
+        .await
+        .unwrap();
+    let mut episode_ids = Vec::new();
+    for turn in 0..2 {
+        let id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO episodic_traces (session_id, agent_id, turn_index, role, content, importance) \
+             VALUES ($1, 'preview-test', $2, 'user', $3, 1.0) RETURNING id",
+        )
+        .bind(session_id)
+        .bind(turn)
+        .bind(format!("We decided to use {object} for turn {turn}"))
+        .fetch_one(pool)
+        .await
+        .expect("insert episode");
+        episode_ids.push(id);
+    }
+
+    let app = build_router(state.clone());
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/consolidate/preview")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+    let facts_written: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM semantic_facts WHERE object = $1")
+            .bind(&object)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+    let still_unconsolidated: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM episodic_traces WHERE id = ANY($1) AND consolidated_at IS NULL",
+    )
+    .bind(&episode_ids)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    sqlx::query("DELETE FROM episodic_traces WHERE session_id = $1")
+        .bind(session_id)
+        .execute(pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM sessions WHERE id = $1")
+        .bind(session_id)
+        .execute(pool)
+        .await
+        .ok();
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let previewed: Vec<&serde_json::Value> = body["facts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|fact| fact["object"] == object.as_str())
+        .collect();
+    assert_eq!(previewed.len(), 2, "{}", body);
+    for fact in previewed {
+        let source: uuid::Uuid = serde_json::from_value(fact["source_episode"].clone()).unwrap();
+        assert!(episode_ids.contains(&source), "{}", fact);
+        assert_eq!(fact["predicate"], "uses");
+    }
+    assert_eq!(facts_written, 0, "preview must not upsert facts");
+    assert_eq!(still_unconsolidated, 2, "preview must not mark episodes");
+}
+
+// ===========================================================================
+// TEST 39: GET /facts/:id/sources returns the episodes a fact came from
+// ===========================================================================
+#[tokio::test]
+async fn test_fact_sources_returns_source_episode() {
+    let state = example_state(|_| {});
+    if sqlx::query("SELECT 1").execute(&state.pool).await.is_err() {
+        eprintln!("Skipping test_fact_sources_returns_source_episode: DB unavailable");
+        return;
+    }
+    let pool = &state.pool;
+    let session_id = uuid::Uuid::new_v4();
+    let content = format!(
+        "We picked Sources{} for tracing",
+        uuid::Uuid::new_v4().simple()
+    );
+
+    sqlx::query("INSERT INTO sessions (id, session_key, agent_id) VALUES ($1, $2, 'sources-test')")
+        .bind(session_id)
+        .bind(format!("sources-key-{session_id}"))
+        .execute(pool)
+        .await
+        .unwrap();
+    let episode_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO episodic_traces (session_id, agent_id, turn_index, role, content) \
+         VALUES ($1, 'sources-test', 0, 'user', $2) RETURNING id",
+    )
+    .bind(session_id)
+    .bind(&content)
+    .fetch_one(pool)
+    .await
+    .expect("insert episode");
+    let fact_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO semantic_facts (kind, statement, subject, predicate, object, source_episodes) \
+         VALUES ('fact', 'sources test fact', 'SourcesTest', 'uses', 'tracing', $1) RETURNING id",
+    )
+    .bind(vec![episode_id])
+    .fetch_one(pool)
+    .await
+    .expect("insert fact");
+
+    let get = |id: uuid::Uuid| {
+        build_router(state.clone()).oneshot(
+            Request::builder()
+                .uri(format!("/facts/{id}/sources"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+    let response = get(fact_id).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    let missing = get(uuid::Uuid::new_v4()).await.unwrap().status();
+
+    sqlx::query("DELETE FROM semantic_facts WHERE id = $1")
+        .bind(fact_id)
+        .execute(pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM episodic_traces WHERE session_id = $1")
+        .bind(session_id)
+        .execute(pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM sessions WHERE id = $1")
+        .bind(session_id)
+        .execute(pool)
+        .await
+        .ok();
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["count"], 1, "{}", body);
+    let source = &body["sources"][0];
+    assert_eq!(source["id"], episode_id.to_string());
+    assert_eq!(source["content"], content.as_str());
+    assert_eq!(source["agent_id"], "sources-test");
+    assert!(source["created_at"].is_string(), "{}", source);
+    assert_eq!(missing, StatusCode::NOT_FOUND);
+}
+
+// ===========================================================================
+// TEST 40: PATCH /facts/:id edits each field, clears the flag, rejects junk
+// ===========================================================================
+#[tokio::test]
+async fn test_patch_fact_updates_each_field() {
+    let state = example_state(|_| {});
+    if sqlx::query("SELECT 1").execute(&state.pool).await.is_err() {
+        eprintln!("Skipping test_patch_fact_updates_each_field: DB unavailable");
+        return;
+    }
+    let pool = &state.pool;
+    let fact_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO semantic_facts (kind, statement, subject, predicate, object, confidence, flagged_for_review) \
+         VALUES ('decision', 'Team uses MySQL', 'PatchTest', 'uses', 'MySQL', 0.6, true) RETURNING id",
+    )
+    .fetch_one(pool)
+    .await
+    .expect("insert fact");
+
+    let patch = |id: uuid::Uuid, body: serde_json::Value| {
+        let app = build_router(state.clone());
+        async move {
+            let response = app
+                .oneshot(
+                    Request::builder()
+                        .method("PATCH")
+                        .uri(format!("/facts/{id}"))
+                        .header("content-type", "application/json")
+                        .body(Body::from(body.to_string()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let status = response.status();
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            (status, body)
+        }
+    };
+
+    let statement = patch(
+        fact_id,
+        serde_json::json!({ "statement": "Team uses Postgres" }),
+    )
+    .await;
+    let object = patch(fact_id, serde_json::json!({ "object": "Postgres" })).await;
+    let confidence = patch(fact_id, serde_json::json!({ "confidence": 1.5 })).await;
+    let topics = patch(fact_id, serde_json::json!({ "topics": ["database"] })).await;
+    let unknown_field = patch(fact_id, serde_json::json!({ "subject": "Someone" })).await;
+    let empty = patch(fact_id, serde_json::json!({})).await;
+    let missing = patch(
+        uuid::Uuid::new_v4(),
+        serde_json::json!({ "confidence": 0.5 }),
+    )
+    .await;
+
+    let stored: (String, String, f64, Vec<String>, bool, String) = sqlx::query_as(
+        "SELECT statement, object, confidence, topics, flagged_for_review, subject \
+         FROM semantic_facts WHERE id = $1",
+    )
+    .bind(fact_id)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    sqlx::query("DELETE FROM semantic_facts WHERE id = $1")
+        .bind(fact_id)
+        .execute(pool)
+        .await
+        .ok();
+
+    for (status, body) in [&statement, &object, &confidence, &topics] {
+        assert_eq!(*status, StatusCode::OK, "{}", body);
+        assert_eq!(body["fact"]["flagged_for_review"], false, "{}", body);
+    }
+    assert_eq!(statement.1["fact"]["statement"], "Team uses Postgres");
+    assert_eq!(
+        confidence.1["fact"]["confidence"], 1.0,
+        "confidence is clamped"
+    );
+    assert_eq!(
+        unknown_field.0,
+        StatusCode::BAD_REQUEST,
+        "{}",
+        unknown_field.1
+    );
+    assert_eq!(empty.0, StatusCode::BAD_REQUEST, "{}", empty.1);
+    assert_eq!(missing.0, StatusCode::NOT_FOUND, "{}", missing.1);
+
+    let (stored_statement, stored_object, stored_confidence, stored_topics, flagged, subject) =
+        stored;
+    assert_eq!(stored_statement, "Team uses Postgres");
+    assert_eq!(stored_object, "Postgres");
+    assert_eq!(stored_confidence, 1.0);
+    assert_eq!(stored_topics, vec!["database".to_string()]);
+    assert!(!flagged);
+    assert_eq!(subject, "PatchTest", "unknown fields change nothing");
+}
+
+// ===========================================================================
+// TEST 41: /consolidate/history returns recorded runs newest first, bounded
+// ===========================================================================
+#[tokio::test]
+async fn test_consolidation_history_endpoint() {
+    use ethos_server::subsystems::consolidate::{
+        record_consolidation, ConsolidationReport, CONSOLIDATION_HISTORY_LEN,
+    };
+
+    let base = 1_000_000;
+    let total = CONSOLIDATION_HISTORY_LEN + 5;
+    for i in 0..total {
+        record_consolidation(ConsolidationReport {
+            episodes_scanned: base + i,
+            facts_created: 1,
+            ..Default::default()
+        });
+    }
+
+    let app = build_router(example_state(|_| {}));
+    let req = Request::builder()
+        .uri("/consolidate/history")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let runs = json["runs"].as_array().expect("runs array");
+    assert_eq!(runs.len(), CONSOLIDATION_HISTORY_LEN, "{}", json);
+    assert_eq!(json["count"], CONSOLIDATION_HISTORY_LEN);
+    assert_eq!(json["capacity"], CONSOLIDATION_HISTORY_LEN);
+    assert_eq!(json["last_run_at"], runs[0]["finished_at"]);
+
+    let scanned: Vec<u64> = runs
+        .iter()
+        .map(|r| r["episodes_scanned"].as_u64().unwrap())
+        .collect();
+    let expected: Vec<u64> = (total - CONSOLIDATION_HISTORY_LEN..total)
+        .rev()
+        .map(|i| (base + i) as u64)
+        .collect();
+    assert_eq!(scanned, expected, "newest first, oldest dropped");
+    assert_eq!(runs[0]["facts_created"], 1);
+}
+
+// ===========================================================================
+// TEST 42: POST /import streams its body; max_body_bytes bounds each line
+// ===========================================================================
+#[tokio::test]
+async fn test_import_streams_past_max_body_bytes() {
+    let app = build_router(example_state(|c| c.http.max_body_bytes = 64));
+
+    // Well over the limit in total, but only the second line is too long
+    let body = format!(
+        "not json\n{}\n\n{}\n",
+        "x".repeat(200),
+        "still not json\n".repeat(10)
+    );
+    let req = Request::builder()
+        .method("POST")
+        .uri("/import")
+        .body(Body::from(body))
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    let failed = json["failed"].as_array().unwrap();
+    assert_eq!(failed.len(), 12, "body: {}", json);
+    assert_eq!(failed[1]["line"], 2);
+    assert!(failed[1]["error"]
+        .as_str()
+        .unwrap()
+        .contains("max line length"));
+    assert_eq!(failed[11]["line"], 13, "blank line counted");
+    assert_eq!(json["imported"], 0);
+}
@@ -0,0 +1,8 @@
+-- Optional gzip compression of memory content ([storage] compress_content)
+-- Migration: 004
+-- Date: 2026-10-16
+
+-- TRUE when content holds base64-encoded gzip rather than plain text.
+-- Compressed rows are decompressed by the server on read; SQL text matching
+-- (ILIKE / pg_trgm) over memory_vectors.content does not see their text.
+ALTER TABLE memory_vectors ADD COLUMN IF NOT EXISTS content_compressed BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,7 @@
+-- Emotional tone for ingested memories (feeds the decay β×E boost)
+-- Migration: 005
+-- Date: 2026-10-16
+
+-- Affect intensity estimated at ingest, 0.0 (neutral) to 1.0 (strong affect).
+-- The decay sweep multiplies salience by (1 + emotional_weight × emotional_tone).
+ALTER TABLE memory_vectors ADD COLUMN IF NOT EXISTS emotional_tone FLOAT NOT NULL DEFAULT 0.0;
@@ -0,0 +1,9 @@
+-- Pinned memories never decay below [decay] min_salience_floor
+-- Migration: 006
+-- Date: 2026-10-16
+
+-- Set via EthosRequest::PinMemory. Pinning a memory vector also pins the
+-- episode or fact it embeds (memory_vectors.source_id).
+ALTER TABLE memory_vectors  ADD COLUMN IF NOT EXISTS pinned BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE episodic_traces ADD COLUMN IF NOT EXISTS pinned BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE semantic_facts  ADD COLUMN IF NOT EXISTS pinned BOOLEAN NOT NULL DEFAULT FALSE;
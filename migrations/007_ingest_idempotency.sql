@@ -0,0 +1,10 @@
+-- Idempotency keys make ingest safe to retry
+-- Migration: 007
+-- Date: 2026-10-16
+
+-- Optional `idempotency_key` from the ingest payload. A repeated key returns
+-- the existing memory id instead of inserting a duplicate row.
+ALTER TABLE memory_vectors ADD COLUMN IF NOT EXISTS idempotency_key TEXT;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_memory_vectors_idempotency_key
+    ON memory_vectors (idempotency_key)
+    WHERE idempotency_key IS NOT NULL;
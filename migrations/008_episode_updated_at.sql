@@ -0,0 +1,8 @@
+-- Track when episodes change so pruned rows can be garbage-collected
+-- Migration: 008
+-- Date: 2026-10-16
+
+-- `POST /admin/gc` hard-deletes rows pruned longer than
+-- [decay] gc_retention_days ago, judged by `updated_at`. memory_vectors and
+-- semantic_facts already carry the column; the decay sweep bumps it on prune.
+ALTER TABLE episodic_traces ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
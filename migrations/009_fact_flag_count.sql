@@ -0,0 +1,9 @@
+-- Count repeat flags instead of duplicating conflicting facts
+-- Migration: 009
+-- Date: 2026-10-16
+
+-- Consolidation used to insert another flagged copy of a contradiction every
+-- cycle until it was reviewed. A repeat now bumps `flag_count` on the earlier
+-- flag; `flagged_at` drives [conflict_resolution] flag_cooldown_cycles.
+ALTER TABLE semantic_facts ADD COLUMN IF NOT EXISTS flag_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE semantic_facts ADD COLUMN IF NOT EXISTS flagged_at TIMESTAMPTZ;
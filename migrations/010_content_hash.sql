@@ -0,0 +1,20 @@
+-- Content hashes so edited memories get re-embedded
+-- Migration: 010
+-- Date: 2026-10-16
+
+-- `content_hash` is the SHA-256 (hex) of the decoded content, written on
+-- ingest and whenever content is updated. `embedded_hash` is the content
+-- hash the stored vector was computed from; the re-embed worker picks up
+-- rows where the two differ.
+ALTER TABLE memory_vectors ADD COLUMN IF NOT EXISTS content_hash TEXT;
+ALTER TABLE memory_vectors ADD COLUMN IF NOT EXISTS embedded_hash TEXT;
+
+-- Existing vectors are taken to match their content. Compressed rows can't
+-- be hashed in SQL and keep NULL hashes until their content is next written.
+UPDATE memory_vectors
+SET content_hash = encode(sha256(convert_to(content, 'UTF8')), 'hex')
+WHERE content IS NOT NULL AND NOT content_compressed AND content_hash IS NULL;
+
+UPDATE memory_vectors
+SET embedded_hash = content_hash
+WHERE vector IS NOT NULL AND embedded_hash IS NULL;
@@ -0,0 +1,8 @@
+-- Look up facts by the episodes they were consolidated from
+-- Migration: 011
+-- Date: 2026-10-16
+
+-- Searches with `include_related_facts` attach the facts derived from each
+-- episode result (`source_episodes @> ARRAY[episode_id]`), which a GIN index
+-- answers without scanning every fact.
+CREATE INDEX IF NOT EXISTS idx_facts_source_episodes ON semantic_facts USING GIN(source_episodes);
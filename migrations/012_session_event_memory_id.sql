@@ -0,0 +1,20 @@
+-- Link transcript rows to the memory they were ingested as
+-- Migration: 012
+-- Date: 2026-10-16
+
+-- Hard-deleting a memory (`DELETE /memory/{id}?hard=true`, `/admin/gc`) also
+-- deletes the `session_events` row it was ingested with, so a forgotten memory
+-- doesn't live on in the raw transcript. A chunked document's event points at
+-- its first chunk.
+ALTER TABLE session_events ADD COLUMN IF NOT EXISTS memory_id UUID;
+CREATE INDEX IF NOT EXISTS idx_session_events_memory ON session_events(memory_id);
+
+-- Existing events are matched to the uncompressed memory written alongside
+-- them; compressed rows can't be compared in SQL and stay unlinked.
+UPDATE session_events e
+SET memory_id = m.id
+FROM memory_vectors m
+WHERE e.memory_id IS NULL
+  AND NOT m.content_compressed
+  AND m.content = e.content
+  AND m.created_at BETWEEN e.created_at - INTERVAL '5 seconds' AND e.created_at + INTERVAL '5 seconds';
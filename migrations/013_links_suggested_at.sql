@@ -0,0 +1,19 @@
+-- Remember which vectors the edge suggester has already linked
+-- Migration: 013
+-- Date: 2026-10-16
+
+-- `suggest_edges` stamps every vector it considers, whether or not any
+-- neighbour cleared the threshold, so a vector with no close neighbours isn't
+-- picked up again on every pass.
+ALTER TABLE memory_vectors ADD COLUMN IF NOT EXISTS links_suggested_at TIMESTAMPTZ;
+
+-- Vectors that already have a suggested edge count as processed
+UPDATE memory_vectors mv
+SET links_suggested_at = NOW()
+WHERE links_suggested_at IS NULL
+  AND EXISTS (
+      SELECT 1 FROM memory_graph_links l
+      WHERE l.from_type = mv.source_type
+        AND l.from_id = mv.source_id
+        AND l.relation = 'similarity'
+  );
@@ -0,0 +1,8 @@
+-- Index the changefeed's ordering
+-- Migration: 014
+-- Date: 2026-10-16
+
+-- `GET /memory` pages through memory_vectors by `(updated_at, id)` so edits
+-- and prunes reach mirrors, not just new rows.
+CREATE INDEX IF NOT EXISTS idx_memory_vectors_updated_at_id
+    ON memory_vectors (updated_at, id);